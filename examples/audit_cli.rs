@@ -139,6 +139,7 @@ fn parse_harness_arg(args: &[String]) -> HarnessType {
                 "async" => HarnessType::AsyncTuiTestHarness,
                 "bevy" => HarnessType::BevyTuiTestHarness,
                 "scarab" => HarnessType::ScarabTestHarness,
+                "auto" => HarnessType::Auto,
                 _ => HarnessType::TuiTestHarness,
             };
         }
@@ -156,7 +157,7 @@ fn print_usage() {
     println!("  audit_cli scaffold <path>    - Generate scaffolded test file");
     println!();
     println!("Options:");
-    println!("  --harness <type>             - Harness type: tui, async, bevy, scarab");
+    println!("  --harness <type>             - Harness type: tui, async, bevy, scarab, auto");
     println!();
     println!("Examples:");
     println!("  audit_cli scan tests/");
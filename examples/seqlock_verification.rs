@@ -60,9 +60,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Example 2: Write-in-Progress Detection");
     println!("---------------------------------------");
     let verifier = harness.seqlock_verifier();
-    let shm = harness.shared_memory();
+    let shm = harness
+        .shared_memory()
+        .as_daemon_shm()
+        .ok_or("harness is not connected to a daemon")?;
 
-    if verifier.is_write_in_progress(shm.as_daemon_shm()) {
+    if verifier.is_write_in_progress(shm) {
         println!("Write currently in progress (sequence number is odd)");
     } else {
         println!("No write in progress (sequence number is even)");
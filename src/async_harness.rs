@@ -10,6 +10,7 @@
 //!   runtime.
 //! - **Advanced Wait Conditions**: Support for custom timeouts, polling intervals, and multiple
 //!   conditions.
+//! - **Screen Update Streams**: Await each distinct frame via [`AsyncTuiTestHarness::updates`].
 //!
 //! # Example
 //!
@@ -35,16 +36,24 @@ use std::{
 };
 
 use portable_pty::CommandBuilder;
-use tokio::task::spawn_blocking;
+use tokio::{sync::mpsc, task::spawn_blocking};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
 
 use crate::{
     error::{Result, TermTestError},
     events::{KeyCode, Modifiers, MouseButton, MouseEvent, ScrollDirection},
     navigation::{HintLabel, NavigationTestExt},
-    screen::ScreenState,
+    screen::{GridSnapshot, ScreenState},
     TuiTestHarness,
 };
 
+/// Number of undelivered frames buffered by [`AsyncTuiTestHarness::updates`]
+/// before the reader pauses for the consumer.
+const UPDATE_CHANNEL_CAPACITY: usize = 16;
+
+/// How often [`AsyncTuiTestHarness::updates`] polls the PTY for new output.
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
 /// Result of a wait operation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WaitResult {
@@ -188,6 +197,32 @@ impl AsyncTuiTestHarness {
             .await
     }
 
+    /// Waits for a condition on the screen state.
+    ///
+    /// This is the async counterpart of [`TuiTestHarness::wait_for`]. The
+    /// condition is polled with `tokio::time` using the default timeout and
+    /// poll interval of [`AsyncWaitBuilder`]; use
+    /// [`wait_for_async`](Self::wait_for_async) to customize them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "async-tokio")]
+    /// # async fn test() -> terminal_testlib::Result<()> {
+    /// use terminal_testlib::AsyncTuiTestHarness;
+    ///
+    /// let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+    /// harness.wait_for(|state| state.cursor_position() == (0, 0)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for<F>(&mut self, condition: F) -> Result<()>
+    where
+        F: Fn(&ScreenState) -> bool + Send + Sync + 'static,
+    {
+        self.wait_for_async(condition).execute().await
+    }
+
     /// Waits for a condition with custom configuration.
     ///
     /// Returns a builder for configuring the wait operation.
@@ -205,6 +240,84 @@ impl AsyncTuiTestHarness {
         AsyncWaitAnyBuilder::new(self.inner.clone())
     }
 
+    /// Returns a stream of screen snapshots, one per distinct frame.
+    ///
+    /// A background task polls the PTY and yields a [`GridSnapshot`] each time
+    /// [`update_state`](TuiTestHarness::update_state) leaves the screen in a
+    /// different state than the last yielded frame. Frames are delivered over
+    /// a bounded channel, so a slow consumer pauses the reader instead of
+    /// buffering output without limit.
+    ///
+    /// The stream ends after the process exits (and its final output has been
+    /// yielded), on a PTY error, or once the stream is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "async-tokio")]
+    /// # async fn test() -> terminal_testlib::Result<()> {
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::AsyncTuiTestHarness;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+    /// harness.spawn(CommandBuilder::new("./my-tui")).await?;
+    ///
+    /// let mut frames = harness.updates();
+    /// while let Some(frame) = frames.next().await {
+    ///     println!("cursor at {:?}", frame.cursor);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn updates(&mut self) -> impl Stream<Item = GridSnapshot> {
+        let (tx, rx) = mpsc::channel(UPDATE_CHANNEL_CAPACITY);
+        let inner = self.inner.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(UPDATE_POLL_INTERVAL);
+            let mut last: Option<GridSnapshot> = None;
+
+            while !tx.is_closed() {
+                interval.tick().await;
+
+                let harness = inner.clone();
+                let baseline = last.is_none();
+                let polled = spawn_blocking(move || {
+                    let mut h = harness.lock().unwrap();
+                    let before = baseline.then(|| h.state().snapshot());
+                    let exited = match h.update_state() {
+                        Ok(()) => false,
+                        Err(TermTestError::ProcessExited) => true,
+                        Err(_) => return None,
+                    };
+                    Some((before, h.state().snapshot(), exited))
+                })
+                .await;
+
+                let Ok(Some((before, frame, exited))) = polled else {
+                    break;
+                };
+                if let Some(before) = before {
+                    last = Some(before);
+                }
+
+                if last.as_ref() != Some(&frame) {
+                    last = Some(frame.clone());
+                    if tx.send(frame).await.is_err() {
+                        break;
+                    }
+                }
+
+                if exited {
+                    break;
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
     /// Returns the current screen contents.
     pub async fn screen_contents(&self) -> String {
         let inner = self.inner.clone();
@@ -368,3 +481,69 @@ impl AsyncWaitAnyBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spawn_echo_and_wait() -> Result<()> {
+        let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("hello async");
+        harness.spawn(cmd).await?;
+
+        harness
+            .wait_for(|state| state.contains("hello async"))
+            .await?;
+        assert!(harness.screen_contents().await.contains("hello async"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_timeout() -> Result<()> {
+        let harness = AsyncTuiTestHarness::new(80, 24).await?;
+
+        let result = harness
+            .wait_for_async(|state| state.contains("never appears"))
+            .timeout(Duration::from_millis(100))
+            .poll_interval(Duration::from_millis(10))
+            .execute()
+            .await;
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_updates_yields_distinct_frames() -> Result<()> {
+        use tokio_stream::StreamExt;
+
+        let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "echo first; sleep 0.2; echo second"]);
+        harness.spawn(cmd).await?;
+
+        let mut frames = Vec::new();
+        let mut updates = harness.updates();
+        let collect = async {
+            while let Some(frame) = updates.next().await {
+                let text: String = frame.cells.iter().flatten().map(|cell| cell.c).collect();
+                let done = text.contains("second");
+                frames.push(frame);
+                if done {
+                    break;
+                }
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), collect)
+            .await
+            .expect("timed out waiting for frames");
+
+        assert!(!frames.is_empty());
+        for pair in frames.windows(2) {
+            assert_ne!(pair[0], pair[1], "consecutive frames should differ");
+        }
+        Ok(())
+    }
+}
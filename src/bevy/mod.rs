@@ -96,7 +96,7 @@ pub use bench::{BenchmarkResults, BenchmarkableHarness, ProfileResults};
 // Bevy ECS imports
 use bevy::app::App;
 use bevy::{
-    ecs::{component::Component, world::World},
+    ecs::{component::Component, system::Resource, world::World},
     prelude::{Entity, With},
     MinimalPlugins,
 };
@@ -205,6 +205,50 @@ impl<T: Serialize> ComponentSnapshot<T> {
     }
 }
 
+/// Terminal output written by Bevy systems.
+///
+/// [`BevyTuiTestHarness`] inserts this resource into its app. Systems that
+/// render the TUI write escape sequences and text into it, and
+/// [`BevyTuiTestHarness::render_to_screen`] feeds the accumulated bytes into
+/// a [`ScreenState`] for assertions.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use terminal_testlib::TerminalOutput;
+///
+/// fn draw_status(mut output: ResMut<TerminalOutput>) {
+///     output.write(b"\x1b[1;1HStatus: ready");
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TerminalOutput {
+    bytes: Vec<u8>,
+}
+
+impl TerminalOutput {
+    /// Appends raw output bytes.
+    pub fn write(&mut self, bytes: impl AsRef<[u8]>) {
+        self.bytes.extend_from_slice(bytes.as_ref());
+    }
+
+    /// Returns the output written since it was last taken.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns whether no output is pending.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Takes the pending output, leaving the buffer empty.
+    pub fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.bytes)
+    }
+}
+
 /// Test harness for Bevy-based TUI applications.
 ///
 /// This combines TUI testing with Bevy ECS querying and update cycle control,
@@ -260,7 +304,10 @@ impl<T: Serialize> ComponentSnapshot<T> {
 pub struct BevyTuiTestHarness {
     harness: TuiTestHarness,
     is_headless: bool,
-    app: App,
+    /// The headless Bevy app driven by this harness.
+    ///
+    /// Add systems, plugins, and resources to it directly.
+    pub app: App,
     #[cfg(feature = "shared-state")]
     shared_state_path: Option<String>,
 }
@@ -319,6 +366,7 @@ impl BevyTuiTestHarness {
             // to avoid GPU/windowing dependencies
             app.add_plugins(MinimalPlugins);
         }
+        app.init_resource::<TerminalOutput>();
 
         Ok(Self {
             harness,
@@ -359,8 +407,9 @@ impl BevyTuiTestHarness {
     /// # }
     /// # }
     /// ```
-    pub fn with_app(app: App) -> Result<Self> {
+    pub fn with_app(mut app: App) -> Result<Self> {
         let harness = TuiTestHarness::new(80, 24)?;
+        app.init_resource::<TerminalOutput>();
 
         #[cfg(feature = "headless")]
         let is_headless = true;
@@ -434,6 +483,39 @@ impl BevyTuiTestHarness {
         Ok(())
     }
 
+    /// Feeds output written by Bevy systems into a screen.
+    ///
+    /// Takes everything accumulated in the [`TerminalOutput`] resource since
+    /// the last call and feeds it to `screen`, so ECS-driven rendering can be
+    /// asserted on without a PTY.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use bevy::prelude::*;
+    /// use terminal_testlib::{BevyTuiTestHarness, ScreenState, TerminalOutput};
+    ///
+    /// fn draw(mut output: ResMut<TerminalOutput>) {
+    ///     output.write(b"Score: 10");
+    /// }
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = BevyTuiTestHarness::new()?;
+    /// harness.app.add_systems(Update, draw);
+    /// harness.update()?;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// harness.render_to_screen(&mut screen);
+    /// assert!(screen.contains("Score: 10"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render_to_screen(&mut self, screen: &mut ScreenState) {
+        if let Some(mut output) = self.app.world_mut().get_resource_mut::<TerminalOutput>() {
+            screen.feed(&output.take());
+        }
+    }
+
     // ========================================================================
     // Bevy ECS Query Methods (Issue #9)
     // ========================================================================
@@ -1429,6 +1511,44 @@ mod tests {
         assert!(!screen.contains("Level"));
     }
 
+    #[test]
+    fn test_render_to_screen_from_system() {
+        #[derive(Component)]
+        struct Score(u32);
+
+        fn score_system(mut query: Query<'_, '_, &mut Score>) {
+            for mut score in query.iter_mut() {
+                score.0 += 10;
+            }
+        }
+
+        fn draw_system(query: Query<'_, '_, &Score>, mut output: ResMut<'_, TerminalOutput>) {
+            for score in query.iter() {
+                output.write(format!("\x1b[2;1HScore: {}", score.0));
+            }
+        }
+
+        let mut harness = BevyTuiTestHarness::new().unwrap();
+        harness
+            .app
+            .add_systems(Update, (score_system, draw_system).chain());
+        harness.world_mut().spawn(Score(0));
+
+        harness.update().unwrap();
+        assert_eq!(harness.query::<Score>()[0].0, 10);
+
+        let mut screen = ScreenState::new(40, 5);
+        harness.render_to_screen(&mut screen);
+        assert_eq!(screen.row_contents(1).trim_end(), "Score: 10");
+        assert!(harness.world().resource::<TerminalOutput>().is_empty());
+
+        // Only new output is fed on the next render
+        harness.update().unwrap();
+        harness.render_to_screen(&mut screen);
+        assert_eq!(screen.find_all("Score").len(), 1);
+        assert_eq!(screen.row_contents(1).trim_end(), "Score: 20");
+    }
+
     // ========================================================================
     // Headless Mode Tests
     // ========================================================================
@@ -0,0 +1,177 @@
+//! Time sources for wait and timeout loops.
+//!
+//! The harness polling waits ([`TuiTestHarness::wait_for`], the Scarab
+//! `wait_for_*` helpers, [`SeqlockVerifier::verify_seqlock_pattern`]) ask a
+//! [`Clock`] for the current time and sleep through it between polls. In
+//! normal use that is [`SystemClock`]. Tests of the wait logic itself can
+//! swap in a [`FakeClock`], whose time only moves when it is advanced, so a
+//! timeout can be reached without actually waiting for it.
+//!
+//! The clock only covers the wait loop. I/O inside a poll still takes real
+//! time: a [`TuiTestHarness`] reading its PTY without the
+//! [background pump] blocks for up to 100ms per poll, and a Scarab harness
+//! connected to a daemon watches the shared memory sequence number on the
+//! system clock (`DaemonSharedMemory::wait_for_update`).
+//!
+//! [`TuiTestHarness::wait_for`]: crate::TuiTestHarness::wait_for
+//! [`TuiTestHarness`]: crate::TuiTestHarness
+//! [background pump]: crate::TuiTestHarness::with_background_pump
+//! [`SeqlockVerifier::verify_seqlock_pattern`]: crate::seqlock::SeqlockVerifier::verify_seqlock_pattern
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use terminal_testlib::clock::{Clock, FakeClock};
+//!
+//! let clock = FakeClock::new();
+//! let start = clock.now();
+//!
+//! // Sleeping on a fake clock returns immediately and moves its time forward
+//! clock.sleep(Duration::from_secs(30));
+//! assert_eq!(clock.elapsed_since(start), Duration::from_secs(30));
+//!
+//! // Clones share the same time
+//! let handle = clock.clone();
+//! handle.advance(Duration::from_secs(5));
+//! assert_eq!(clock.elapsed(), Duration::from_secs(35));
+//! ```
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A source of time for polling loops.
+///
+/// Implementations must be shareable across threads, since harnesses may be
+/// moved into worker threads or async tasks.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+
+    /// Blocks for `duration` as measured by this clock.
+    fn sleep(&self, duration: Duration);
+
+    /// Returns the time elapsed on this clock since `start`.
+    ///
+    /// Saturates to zero if `start` is later than [`now`](Self::now).
+    fn elapsed_since(&self, start: Instant) -> Duration {
+        self.now().saturating_duration_since(start)
+    }
+}
+
+/// The real clock: [`Instant::now`] and [`std::thread::sleep`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A manually driven clock for testing timeouts in zero real time.
+///
+/// Time starts at the moment the clock is created and only moves when
+/// [`advance`](Self::advance) is called or something sleeps on the clock;
+/// [`sleep`](Clock::sleep) returns immediately after advancing by the
+/// requested duration. Clones share the same time, so a test can keep a
+/// handle to a clock it has given to a harness.
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    origin: Instant,
+    offset: Arc<Mutex<Duration>>,
+}
+
+impl FakeClock {
+    /// Creates a fake clock whose time stands still until advanced.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            offset: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.offset.lock().unwrap() += duration;
+    }
+
+    /// Returns the total time the clock has been advanced by.
+    pub fn elapsed(&self) -> Duration {
+        *self.offset.lock().unwrap()
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_clock_only_moves_when_advanced() {
+        let clock = FakeClock::new();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(clock.elapsed_since(start), Duration::from_millis(250));
+
+        clock.sleep(Duration::from_secs(3600));
+        assert_eq!(clock.elapsed(), Duration::from_millis(3_600_250));
+    }
+
+    #[test]
+    fn test_fake_clock_sleep_takes_no_real_time() {
+        let clock = FakeClock::new();
+        let real = Instant::now();
+        clock.sleep(Duration::from_secs(24 * 3600));
+        assert!(real.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_fake_clock_clones_share_time() {
+        let clock = FakeClock::new();
+        let handle = clock.clone();
+        handle.advance(Duration::from_secs(2));
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+        assert_eq!(clock.now(), handle.now());
+    }
+
+    #[test]
+    fn test_elapsed_since_saturates() {
+        let clock = FakeClock::new();
+        let later = clock.now() + Duration::from_secs(1);
+        assert_eq!(clock.elapsed_since(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_system_clock_advances() {
+        let clock = SystemClock;
+        let start = clock.now();
+        clock.sleep(Duration::from_millis(5));
+        assert!(clock.elapsed_since(start) >= Duration::from_millis(5));
+    }
+}
@@ -56,9 +56,15 @@ pub type Result<T> = std::result::Result<T, TermTestError>;
 /// - `SnapshotMismatch`: Snapshot testing failures (requires `snapshot-insta` feature)
 /// - `SixelValidation`: Sixel graphics validation failures (requires `sixel` feature)
 /// - [`TermTestError::SpawnFailed`]: Process spawning failures
+/// - [`TermTestError::CommandNotFound`]: Spawned program does not exist or is not on `PATH`
 /// - [`TermTestError::ProcessAlreadyRunning`]: Attempt to spawn when a process is already running
 /// - [`TermTestError::NoProcessRunning`]: Attempt to interact with a non-existent process
 /// - [`TermTestError::InvalidDimensions`]: Invalid terminal size parameters
+/// - [`TermTestError::CursorTimeout`]: Cursor did not reach a position in time
+/// - [`TermTestError::BudgetExceeded`]: Text appeared, but later than its time budget
+/// - [`TermTestError::MouseReportingDisabled`]: Mouse input sent before the app enabled tracking
+/// - [`TermTestError::GraphicsPresent`]: Inline graphics rendered where none were expected
+/// - [`TermTestError::UnsupportedKey`]: Key has no escape sequence a terminal would send
 /// - `Bevy`: Bevy ECS-related errors (requires `bevy` feature)
 #[derive(Debug, Error)]
 pub enum TermTestError {
@@ -146,6 +152,17 @@ pub enum TermTestError {
     #[error("Failed to spawn process: {0}")]
     SpawnFailed(String),
 
+    /// Command not found.
+    ///
+    /// This error is returned before spawning when the program cannot be resolved,
+    /// either as a path to an existing file or as an executable on `PATH`. It
+    /// surfaces typos in binary names immediately instead of as a later wait timeout.
+    #[error("Command not found: {program}")]
+    CommandNotFound {
+        /// The program that could not be resolved.
+        program: String,
+    },
+
     /// Process already running.
     ///
     /// This error is returned when attempting to spawn a process while another
@@ -182,6 +199,59 @@ pub enum TermTestError {
     #[error("Child process has exited")]
     ProcessExited,
 
+    /// Timeout waiting for the cursor to reach a position.
+    ///
+    /// Returned by `TuiTestHarness::wait_for_cursor_at` instead of the plain
+    /// [`TermTestError::Timeout`] so the failure shows where the cursor was
+    /// when the wait gave up.
+    #[error(
+        "Timeout after {timeout_ms}ms waiting for cursor at {expected:?}, last seen at {last_seen:?}"
+    )]
+    CursorTimeout {
+        /// Position waited for as (row, col).
+        expected: (u16, u16),
+        /// Cursor position at the final check as (row, col).
+        last_seen: (u16, u16),
+        /// Timeout duration in milliseconds.
+        timeout_ms: u64,
+    },
+
+    /// Text appeared later than its time budget allowed.
+    ///
+    /// Returned by `TuiTestHarness::assert_text_within` with how long the
+    /// text actually took, measured from spawn or the last `mark_start`.
+    #[error("Text '{text}' appeared after {elapsed_ms}ms, over the budget of {budget_ms}ms")]
+    BudgetExceeded {
+        /// Text that was waited for.
+        text: String,
+        /// Allowed time in milliseconds.
+        budget_ms: u64,
+        /// Time the text took to appear in milliseconds.
+        elapsed_ms: u64,
+    },
+
+    /// Mouse reporting is not enabled.
+    ///
+    /// This error is returned when sending a mouse event through the
+    /// application's own protocol before it has enabled mouse tracking with
+    /// DECSET (e.g. `ESC [ ? 1000 h`).
+    #[error("Mouse reporting is not enabled by the application")]
+    MouseReportingDisabled,
+
+    /// Inline graphics were rendered where none were expected.
+    ///
+    /// Returned by `TuiTestHarness::assert_no_graphics`. The message gives the
+    /// image count per protocol and the position of the first image.
+    #[error("Unexpected graphics: {0}")]
+    GraphicsPresent(String),
+
+    /// A key has no byte sequence a terminal would send for it.
+    ///
+    /// Returned when sending a key such as `KeyCode::F(0)` or `KeyCode::F(40)`
+    /// instead of silently writing nothing. The message names the key.
+    #[error("Key cannot be encoded for a terminal: {0}")]
+    UnsupportedKey(String),
+
     /// Bevy ECS-specific errors.
     ///
     /// This error occurs for Bevy-related failures when using the `bevy` feature,
@@ -287,6 +357,15 @@ mod tests {
         assert!(msg.contains("command not found"));
     }
 
+    #[test]
+    fn test_command_not_found_error() {
+        let err = TermTestError::CommandNotFound { program: "no-such-binary".to_string() };
+        let msg = err.to_string();
+
+        assert!(msg.contains("Command not found"));
+        assert!(msg.contains("no-such-binary"));
+    }
+
     #[test]
     fn test_process_already_running_error() {
         let err = TermTestError::ProcessAlreadyRunning;
@@ -321,6 +400,58 @@ mod tests {
         assert!(msg.contains("Child process"));
     }
 
+    #[test]
+    fn test_cursor_timeout_error() {
+        let err = TermTestError::CursorTimeout {
+            expected: (5, 10),
+            last_seen: (4, 0),
+            timeout_ms: 250,
+        };
+        let msg = err.to_string();
+
+        assert!(msg.contains("250ms"));
+        assert!(msg.contains("cursor at (5, 10)"));
+        assert!(msg.contains("last seen at (4, 0)"));
+    }
+
+    #[test]
+    fn test_budget_exceeded_error() {
+        let err = TermTestError::BudgetExceeded {
+            text: "Ready".to_string(),
+            budget_ms: 100,
+            elapsed_ms: 340,
+        };
+        let msg = err.to_string();
+
+        assert!(msg.contains("'Ready'"));
+        assert!(msg.contains("after 340ms"));
+        assert!(msg.contains("budget of 100ms"));
+    }
+
+    #[test]
+    fn test_mouse_reporting_disabled_error() {
+        let err = TermTestError::MouseReportingDisabled;
+        let msg = err.to_string();
+
+        assert!(msg.contains("Mouse reporting"));
+    }
+
+    #[test]
+    fn test_graphics_present_error() {
+        let err =
+            TermTestError::GraphicsPresent("found 1 (1 Sixel, 0 Kitty, 0 iTerm2)".to_string());
+        let msg = err.to_string();
+
+        assert!(msg.contains("Unexpected graphics"));
+        assert!(msg.contains("1 Sixel"));
+    }
+
+    #[test]
+    fn test_unsupported_key_error() {
+        let err = TermTestError::UnsupportedKey("F(40)".to_string());
+        assert_eq!(err.to_string(), "Key cannot be encoded for a terminal: F(40)");
+    }
+
     #[cfg(feature = "sixel")]
     #[test]
     fn test_sixel_validation_error() {
@@ -30,6 +30,8 @@
 
 use bitflags::bitflags;
 
+use crate::terminal_profiles::TerminalProfile;
+
 /// Represents a keyboard key.
 ///
 /// This enum covers all keys commonly used in TUI applications, including:
@@ -95,7 +97,10 @@ pub enum KeyCode {
     /// Page Down key.
     PageDown,
 
-    /// Function keys F1-F12.
+    /// Function keys F1-F24, or F1-F35 under the Kitty keyboard protocol.
+    ///
+    /// Without the protocol, F13-F24 are sent as xterm sends Shift+F1
+    /// through Shift+F12. Other numbers cannot be encoded.
     ///
     /// # Example
     ///
@@ -295,6 +300,35 @@ impl ScrollDirection {
     }
 }
 
+/// Mouse reporting protocol an application has requested via DECSET.
+///
+/// Derived from the tracking modes (`?9`, `?1000`, `?1002`, `?1003`) and the
+/// SGR encoding mode (`?1006`). See [`ScreenState::mouse_mode`](crate::ScreenState::mouse_mode).
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::{events::MouseMode, ScreenState};
+///
+/// let mut screen = ScreenState::new(80, 24);
+/// assert_eq!(screen.mouse_mode(), MouseMode::Off);
+///
+/// screen.feed(b"\x1b[?1000;1006h");
+/// assert_eq!(screen.mouse_mode(), MouseMode::Sgr);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MouseMode {
+    /// Mouse reporting is disabled.
+    #[default]
+    Off,
+    /// X10 compatibility mode (`?9`): button presses only, legacy encoding.
+    X10,
+    /// Normal tracking (`?1000`, `?1002` or `?1003`) with legacy encoding.
+    Normal,
+    /// Normal tracking with SGR extended encoding (`?1006`).
+    Sgr,
+}
+
 /// A mouse event combining position, button/scroll, and optional modifiers.
 ///
 /// # Example
@@ -427,6 +461,37 @@ impl MouseEvent {
         }
     }
 
+    /// Creates a mouse motion event.
+    ///
+    /// Pass the held button for a drag, or `None` for a plain move.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate (column, 0-indexed)
+    /// * `y` - Y coordinate (row, 0-indexed)
+    /// * `button` - Mouse button held during the motion, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::events::{MouseButton, MouseEvent};
+    ///
+    /// let drag = MouseEvent::motion(10, 5, Some(MouseButton::Left));
+    /// assert_eq!(drag.to_bytes(), b"\x1b[<32;11;6M");
+    ///
+    /// let hover = MouseEvent::motion(10, 5, None);
+    /// assert_eq!(hover.to_bytes(), b"\x1b[<35;11;6M");
+    /// ```
+    pub fn motion(x: u16, y: u16, button: Option<MouseButton>) -> Self {
+        Self {
+            x,
+            y,
+            button_code: MOUSE_MOTION_BIT + button.map_or(3, MouseButton::to_sgr_code),
+            is_press: true, // Motion events use press encoding
+            modifiers: Modifiers::empty(),
+        }
+    }
+
     /// Converts the mouse event to bytes suitable for sending to a PTY.
     ///
     /// This generates SGR (Select Graphic Rendition) mouse encoding sequences
@@ -511,6 +576,173 @@ pub fn encode_key_event(event: &KeyEvent) -> Vec<u8> {
     }
 }
 
+/// Encodes a key event as a terminal matching `profile` would send it.
+///
+/// Home, End and the low function keys are sent differently depending on
+/// the terminfo entry named by `TERM`. Those follow the profile, and all
+/// other keys are encoded as in [`encode_key_event`]:
+/// - [`TerminalProfile::Linux`] sends Home and End as `CSI 1 ~` and
+///   `CSI 4 ~`, and F1-F5 as `CSI [ A` through `CSI [ E`.
+/// - [`TerminalProfile::Screen`] and [`TerminalProfile::Tmux`] send Home and
+///   End as `CSI 1 ~` and `CSI 4 ~`.
+/// - xterm and the remaining profiles send Home and End as `CSI H` and
+///   `CSI F`.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::{
+///     events::{encode_key_event_for, KeyCode, KeyEvent},
+///     TerminalProfile,
+/// };
+///
+/// let home = KeyEvent::new(KeyCode::Home);
+/// assert_eq!(encode_key_event_for(&home, TerminalProfile::Xterm), b"\x1b[H");
+/// assert_eq!(encode_key_event_for(&home, TerminalProfile::Linux), b"\x1b[1~");
+///
+/// let f1 = KeyEvent::new(KeyCode::F(1));
+/// assert_eq!(encode_key_event_for(&f1, TerminalProfile::Linux), b"\x1b[[A");
+/// ```
+pub fn encode_key_event_for(event: &KeyEvent, profile: TerminalProfile) -> Vec<u8> {
+    let vt220_home_end = matches!(
+        profile,
+        TerminalProfile::Linux | TerminalProfile::Screen | TerminalProfile::Tmux
+    );
+
+    match event.code {
+        KeyCode::Home if vt220_home_end => b"\x1b[1~".to_vec(),
+        KeyCode::End if vt220_home_end => b"\x1b[4~".to_vec(),
+        KeyCode::F(n @ 1..=5) if profile == TerminalProfile::Linux => {
+            vec![0x1b, b'[', b'[', b'A' + n - 1]
+        }
+        _ => encode_key_event(event),
+    }
+}
+
+/// Kitty keyboard protocol flag: disambiguate escape codes.
+///
+/// Esc and keys combined with Ctrl or Alt are sent as `CSI code ; mods u`
+/// instead of legacy bytes that collide with other keys.
+pub const KITTY_DISAMBIGUATE: u8 = 0b1;
+
+/// Kitty keyboard protocol flag: report all keys as escape codes.
+///
+/// Text keys, Enter, Tab and Backspace are sent as `CSI code u` even
+/// without modifiers.
+pub const KITTY_REPORT_ALL_KEYS: u8 = 0b1000;
+
+/// Encodes a key event for an application using the Kitty keyboard protocol.
+///
+/// `flags` are the progressive enhancement flags the application pushed, as
+/// reported by [`ScreenState::kitty_keyboard_flags`](crate::ScreenState::kitty_keyboard_flags).
+/// Only [`KITTY_DISAMBIGUATE`] and [`KITTY_REPORT_ALL_KEYS`] change the
+/// encoding; with neither set this is [`encode_key_event`].
+///
+/// Under the protocol:
+/// - Esc is `CSI 27 u`.
+/// - Characters, Enter, Tab and Backspace with modifiers other than Shift
+///   are `CSI code ; mods u`, where `code` is the unshifted code point (an
+///   uppercase ASCII letter reports Shift) and `mods` is 1 plus Shift (1),
+///   Alt (2), Ctrl (4) and Meta (8). Without such modifiers they are sent
+///   as text unless [`KITTY_REPORT_ALL_KEYS`] is set.
+/// - Arrows, Home, End, F1, F2 and F4 with modifiers are `CSI 1 ; mods X`;
+///   Insert, Delete, PageUp, PageDown, F3 and F5-F12 are `CSI n ; mods ~`.
+///   F3 uses `CSI 13 ; mods ~` because `CSI 1 ; mods R` would read as a
+///   cursor position report. Unmodified, they keep their legacy sequences.
+/// - F13-F35, which have no legacy sequences of their own, are always
+///   `CSI code ; mods u` with codes 57376-57398.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::events::{
+///     encode_key_event, encode_key_event_kitty, KeyCode, KeyEvent, Modifiers,
+///     KITTY_DISAMBIGUATE,
+/// };
+///
+/// let ctrl_i = KeyEvent::with_modifiers(KeyCode::Char('i'), Modifiers::CTRL);
+/// assert_eq!(encode_key_event(&ctrl_i), b"\t"); // same byte as Tab
+/// assert_eq!(encode_key_event_kitty(&ctrl_i, KITTY_DISAMBIGUATE), b"\x1b[105;5u");
+///
+/// let esc = KeyEvent::new(KeyCode::Esc);
+/// assert_eq!(encode_key_event_kitty(&esc, KITTY_DISAMBIGUATE), b"\x1b[27u");
+/// assert_eq!(encode_key_event_kitty(&esc, 0), b"\x1b");
+/// ```
+pub fn encode_key_event_kitty(event: &KeyEvent, flags: u8) -> Vec<u8> {
+    if flags & (KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS) == 0 {
+        return encode_key_event(event);
+    }
+
+    let report_all = flags & KITTY_REPORT_ALL_KEYS != 0;
+    let mut modifiers = event.modifiers;
+    let code = match event.code {
+        KeyCode::Char(c) => {
+            if !report_all && (modifiers - Modifiers::SHIFT).is_empty() {
+                return encode_key_event(event);
+            }
+            if c.is_ascii_uppercase() {
+                modifiers |= Modifiers::SHIFT;
+            }
+            c.to_ascii_lowercase() as u32
+        }
+        KeyCode::Enter | KeyCode::Tab | KeyCode::Backspace
+            if !report_all && modifiers.is_empty() =>
+        {
+            return encode_key_event(event);
+        }
+        KeyCode::Enter => 13,
+        KeyCode::Tab => 9,
+        KeyCode::Backspace => 127,
+        KeyCode::Esc => 27,
+        KeyCode::F(n @ 13..=35) => 57376 + u32::from(n - 13),
+        _ if modifiers.is_empty() => return encode_key_event(event),
+        KeyCode::Up => return kitty_csi(1, modifiers, 'A'),
+        KeyCode::Down => return kitty_csi(1, modifiers, 'B'),
+        KeyCode::Right => return kitty_csi(1, modifiers, 'C'),
+        KeyCode::Left => return kitty_csi(1, modifiers, 'D'),
+        KeyCode::Home => return kitty_csi(1, modifiers, 'H'),
+        KeyCode::End => return kitty_csi(1, modifiers, 'F'),
+        KeyCode::Insert => return kitty_csi(2, modifiers, '~'),
+        KeyCode::Delete => return kitty_csi(3, modifiers, '~'),
+        KeyCode::PageUp => return kitty_csi(5, modifiers, '~'),
+        KeyCode::PageDown => return kitty_csi(6, modifiers, '~'),
+        KeyCode::F(n) => {
+            return match n {
+                3 => kitty_csi(13, modifiers, '~'),
+                1..=4 => kitty_csi(1, modifiers, (b'P' + n - 1) as char),
+                5 => kitty_csi(15, modifiers, '~'),
+                6..=10 => kitty_csi(u32::from(n) + 11, modifiers, '~'),
+                11 | 12 => kitty_csi(u32::from(n) + 12, modifiers, '~'),
+                _ => Vec::new(),
+            };
+        }
+    };
+
+    kitty_csi(code, modifiers, 'u')
+}
+
+/// Formats `CSI number ; mods final`, leaving out the modifier field when no
+/// modifiers are held.
+fn kitty_csi(number: u32, modifiers: Modifiers, final_byte: char) -> Vec<u8> {
+    let mut value = 1;
+    for (modifier, bit) in [
+        (Modifiers::SHIFT, 1),
+        (Modifiers::ALT, 2),
+        (Modifiers::CTRL, 4),
+        (Modifiers::META, 8),
+    ] {
+        if modifiers.contains(modifier) {
+            value += bit;
+        }
+    }
+
+    if value == 1 {
+        format!("\x1b[{}{}", number, final_byte).into_bytes()
+    } else {
+        format!("\x1b[{};{}{}", number, value, final_byte).into_bytes()
+    }
+}
+
 /// Encodes Ctrl+character combinations.
 ///
 /// Ctrl key combinations use the ASCII control character range:
@@ -560,19 +792,22 @@ fn encode_ctrl_char(c: char) -> Vec<u8> {
     vec![byte]
 }
 
-/// Encodes function keys (F1-F12) to their VT100 escape sequences.
+/// Encodes function keys (F1-F24) to their xterm escape sequences.
 ///
 /// Function key mappings:
 /// - F1-F4 use SS3 sequences (ESC O ...)
 /// - F5-F12 use CSI sequences (ESC [ ... ~)
+/// - F13-F24 are Shift+F1 through Shift+F12 (ESC [ 1 ; 2 P ... and
+///   ESC [ ... ; 2 ~)
 ///
 /// # Arguments
 ///
-/// * `n` - Function key number (1-12)
+/// * `n` - Function key number (1-24)
 ///
 /// # Returns
 ///
-/// A vector containing the escape sequence for the function key.
+/// A vector containing the escape sequence for the function key, empty for
+/// numbers outside 1-24.
 fn encode_function_key(n: u8) -> Vec<u8> {
     match n {
         // F1-F4 use SS3 (ESC O) sequences
@@ -591,6 +826,20 @@ fn encode_function_key(n: u8) -> Vec<u8> {
         11 => b"\x1b[23~".to_vec(),
         12 => b"\x1b[24~".to_vec(),
 
+        // F13-F24 are sent as the shifted F1-F12
+        13 => b"\x1b[1;2P".to_vec(),
+        14 => b"\x1b[1;2Q".to_vec(),
+        15 => b"\x1b[1;2R".to_vec(),
+        16 => b"\x1b[1;2S".to_vec(),
+        17 => b"\x1b[15;2~".to_vec(),
+        18 => b"\x1b[17;2~".to_vec(),
+        19 => b"\x1b[18;2~".to_vec(),
+        20 => b"\x1b[19;2~".to_vec(),
+        21 => b"\x1b[20;2~".to_vec(),
+        22 => b"\x1b[21;2~".to_vec(),
+        23 => b"\x1b[23;2~".to_vec(),
+        24 => b"\x1b[24;2~".to_vec(),
+
         // For invalid function key numbers, return empty sequence
         _ => Vec::new(),
     }
@@ -627,18 +876,7 @@ fn encode_function_key(n: u8) -> Vec<u8> {
 /// // Results in: \x1b[<0;11;6M (coordinates are 1-indexed)
 /// ```
 pub fn encode_mouse_event(event: &MouseEvent) -> Vec<u8> {
-    let mut button_code = event.button_code;
-
-    // Add modifier bits to button code
-    if event.modifiers.contains(Modifiers::SHIFT) {
-        button_code += 4;
-    }
-    if event.modifiers.contains(Modifiers::ALT) {
-        button_code += 8;
-    }
-    if event.modifiers.contains(Modifiers::CTRL) {
-        button_code += 16;
-    }
+    let button_code = event.button_code + modifier_bits(event.modifiers);
 
     // Convert 0-indexed coordinates to 1-indexed for SGR format
     let x = event.x + 1;
@@ -650,6 +888,77 @@ pub fn encode_mouse_event(event: &MouseEvent) -> Vec<u8> {
     format!("\x1b[<{};{};{}{}", button_code, x, y, terminator).into_bytes()
 }
 
+/// Encodes a mouse event for the given reporting protocol.
+///
+/// - [`MouseMode::Sgr`] uses [`encode_mouse_event`].
+/// - [`MouseMode::Normal`] uses the legacy `ESC [ M Cb Cx Cy` encoding, where
+///   each value is offset by 32 and releases report button 3.
+/// - [`MouseMode::X10`] uses the same encoding for button presses only,
+///   without modifiers.
+///
+/// Returns `None` when the protocol cannot report the event: anything in
+/// [`MouseMode::Off`], releases, motion and scrolling in X10 mode, or
+/// coordinates beyond 223 in the legacy encoding.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::events::{encode_mouse_event_for, MouseButton, MouseEvent, MouseMode};
+///
+/// let click = MouseEvent::press(10, 5, MouseButton::Left);
+/// assert_eq!(
+///     encode_mouse_event_for(&click, MouseMode::Sgr),
+///     Some(b"\x1b[<0;11;6M".to_vec())
+/// );
+/// assert_eq!(
+///     encode_mouse_event_for(&click, MouseMode::Normal),
+///     Some(b"\x1b[M +&".to_vec())
+/// );
+/// assert_eq!(encode_mouse_event_for(&click, MouseMode::Off), None);
+/// ```
+pub fn encode_mouse_event_for(event: &MouseEvent, mode: MouseMode) -> Option<Vec<u8>> {
+    match mode {
+        MouseMode::Off => None,
+        MouseMode::Sgr => Some(encode_mouse_event(event)),
+        MouseMode::X10 => {
+            if !event.is_press || event.button_code >= MOUSE_MOTION_BIT {
+                return None;
+            }
+            encode_legacy_mouse(event.button_code, event.x, event.y)
+        }
+        MouseMode::Normal => {
+            let button = if event.is_press { event.button_code } else { 3 };
+            encode_legacy_mouse(button + modifier_bits(event.modifiers), event.x, event.y)
+        }
+    }
+}
+
+/// Button code bit marking a motion event.
+const MOUSE_MOTION_BIT: u8 = 32;
+
+/// Returns the button code bits for held modifiers (Shift +4, Alt +8, Ctrl +16).
+fn modifier_bits(modifiers: Modifiers) -> u8 {
+    let mut bits = 0;
+    if modifiers.contains(Modifiers::SHIFT) {
+        bits += 4;
+    }
+    if modifiers.contains(Modifiers::ALT) {
+        bits += 8;
+    }
+    if modifiers.contains(Modifiers::CTRL) {
+        bits += 16;
+    }
+    bits
+}
+
+/// Encodes `ESC [ M Cb Cx Cy` with each value offset by 32 and 1-indexed
+/// coordinates. Returns `None` if a coordinate does not fit in a byte.
+fn encode_legacy_mouse(button_code: u8, x: u16, y: u16) -> Option<Vec<u8>> {
+    let cx = u8::try_from(u32::from(x) + 33).ok()?;
+    let cy = u8::try_from(u32::from(y) + 33).ok()?;
+    Some(vec![0x1b, b'[', b'M', button_code + 32, cx, cy])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -668,6 +977,74 @@ mod tests {
         assert_eq!(event.modifiers, Modifiers::CTRL);
     }
 
+    #[test]
+    fn test_encode_key_event_kitty_modified_keys() {
+        let ctrl_shift_a =
+            KeyEvent::with_modifiers(KeyCode::Char('a'), Modifiers::CTRL | Modifiers::SHIFT);
+        assert_eq!(encode_key_event(&ctrl_shift_a), vec![0x01]);
+        assert_eq!(encode_key_event_kitty(&ctrl_shift_a, KITTY_DISAMBIGUATE), b"\x1b[97;6u");
+
+        let alt_x = KeyEvent::with_modifiers(KeyCode::Char('x'), Modifiers::ALT);
+        assert_eq!(encode_key_event(&alt_x), b"\x1bx");
+        assert_eq!(encode_key_event_kitty(&alt_x, KITTY_DISAMBIGUATE), b"\x1b[120;3u");
+
+        let shift_enter = KeyEvent::with_modifiers(KeyCode::Enter, Modifiers::SHIFT);
+        assert_eq!(encode_key_event(&shift_enter), b"\n");
+        assert_eq!(encode_key_event_kitty(&shift_enter, KITTY_DISAMBIGUATE), b"\x1b[13;2u");
+
+        let ctrl_up = KeyEvent::with_modifiers(KeyCode::Up, Modifiers::CTRL);
+        assert_eq!(encode_key_event_kitty(&ctrl_up, KITTY_DISAMBIGUATE), b"\x1b[1;5A");
+
+        let meta_f5 = KeyEvent::with_modifiers(KeyCode::F(5), Modifiers::META);
+        assert_eq!(encode_key_event_kitty(&meta_f5, KITTY_DISAMBIGUATE), b"\x1b[15;9~");
+
+        let alt_f2 = KeyEvent::with_modifiers(KeyCode::F(2), Modifiers::ALT);
+        assert_eq!(encode_key_event_kitty(&alt_f2, KITTY_DISAMBIGUATE), b"\x1b[1;3Q");
+
+        // F3 avoids CSI 1;mods R, which is also a cursor position report
+        let ctrl_f3 = KeyEvent::with_modifiers(KeyCode::F(3), Modifiers::CTRL);
+        assert_eq!(encode_key_event_kitty(&ctrl_f3, KITTY_DISAMBIGUATE), b"\x1b[13;5~");
+        let f3 = KeyEvent::new(KeyCode::F(3));
+        assert_eq!(encode_key_event_kitty(&f3, KITTY_DISAMBIGUATE), b"\x1bOR");
+
+        // F13 and up have their own codes, with or without modifiers
+        let f13 = KeyEvent::new(KeyCode::F(13));
+        assert_eq!(encode_key_event_kitty(&f13, KITTY_DISAMBIGUATE), b"\x1b[57376u");
+        let ctrl_f35 = KeyEvent::with_modifiers(KeyCode::F(35), Modifiers::CTRL);
+        assert_eq!(encode_key_event_kitty(&ctrl_f35, KITTY_DISAMBIGUATE), b"\x1b[57398;5u");
+    }
+
+    #[test]
+    fn test_encode_key_event_kitty_plain_keys() {
+        let disambiguate = KITTY_DISAMBIGUATE;
+        let all = KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS;
+
+        // Text and unmodified Enter/Tab stay legacy unless every key is reported
+        for code in [
+            KeyCode::Char('a'),
+            KeyCode::Enter,
+            KeyCode::Tab,
+            KeyCode::Up,
+        ] {
+            let event = KeyEvent::new(code);
+            assert_eq!(encode_key_event_kitty(&event, disambiguate), encode_key_event(&event));
+        }
+        let shift_a = KeyEvent::with_modifiers(KeyCode::Char('A'), Modifiers::SHIFT);
+        assert_eq!(encode_key_event_kitty(&shift_a, disambiguate), b"A");
+
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Char('a')), all), b"\x1b[97u");
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Char('A')), all), b"\x1b[97;2u");
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Enter), all), b"\x1b[13u");
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Backspace), all), b"\x1b[127u");
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Up), all), b"\x1b[A");
+
+        assert_eq!(encode_key_event_kitty(&KeyEvent::new(KeyCode::Esc), disambiguate), b"\x1b[27u");
+
+        // Flags that do not change the encoding fall back to legacy bytes
+        let ctrl_c = KeyEvent::with_modifiers(KeyCode::Char('c'), Modifiers::CTRL);
+        assert_eq!(encode_key_event_kitty(&ctrl_c, 0b10), vec![0x03]);
+    }
+
     #[test]
     fn test_encode_simple_char() {
         let event = KeyEvent::new(KeyCode::Char('a'));
@@ -716,6 +1093,42 @@ mod tests {
         assert_eq!(event.to_bytes(), b"\x1b[F");
     }
 
+    #[test]
+    fn test_encode_keys_for_profile() {
+        let home = KeyEvent::new(KeyCode::Home);
+        let end = KeyEvent::new(KeyCode::End);
+
+        for profile in [TerminalProfile::Xterm, TerminalProfile::Xterm256] {
+            assert_eq!(encode_key_event_for(&home, profile), b"\x1b[H");
+            assert_eq!(encode_key_event_for(&end, profile), b"\x1b[F");
+        }
+        for profile in [
+            TerminalProfile::Linux,
+            TerminalProfile::Screen,
+            TerminalProfile::Tmux,
+        ] {
+            assert_eq!(encode_key_event_for(&home, profile), b"\x1b[1~");
+            assert_eq!(encode_key_event_for(&end, profile), b"\x1b[4~");
+        }
+
+        // Only the Linux console has its own F1-F5
+        let keys = (1..=6).map(|n| KeyEvent::new(KeyCode::F(n)));
+        let linux: Vec<Vec<u8>> = keys
+            .clone()
+            .map(|key| encode_key_event_for(&key, TerminalProfile::Linux))
+            .collect();
+        assert_eq!(linux[0], b"\x1b[[A");
+        assert_eq!(linux[4], b"\x1b[[E");
+        assert_eq!(linux[5], b"\x1b[17~");
+        for key in keys {
+            assert_eq!(encode_key_event_for(&key, TerminalProfile::Tmux), key.to_bytes());
+        }
+
+        // Keys that do not vary are unchanged
+        let up = KeyEvent::new(KeyCode::Up);
+        assert_eq!(encode_key_event_for(&up, TerminalProfile::Linux), b"\x1b[A");
+    }
+
     #[test]
     fn test_encode_page_keys() {
         let event = KeyEvent::new(KeyCode::PageUp);
@@ -755,6 +1168,20 @@ mod tests {
 
         let event = KeyEvent::new(KeyCode::F(12));
         assert_eq!(event.to_bytes(), b"\x1b[24~");
+
+        // F13-F24 are the shifted F1-F12
+        let event = KeyEvent::new(KeyCode::F(13));
+        assert_eq!(event.to_bytes(), b"\x1b[1;2P");
+
+        let event = KeyEvent::new(KeyCode::F(17));
+        assert_eq!(event.to_bytes(), b"\x1b[15;2~");
+
+        let event = KeyEvent::new(KeyCode::F(24));
+        assert_eq!(event.to_bytes(), b"\x1b[24;2~");
+
+        // Out of range
+        assert!(KeyEvent::new(KeyCode::F(0)).to_bytes().is_empty());
+        assert!(KeyEvent::new(KeyCode::F(25)).to_bytes().is_empty());
     }
 
     #[test]
@@ -996,4 +1423,67 @@ mod tests {
         let event4 = MouseEvent::release(10, 5, MouseButton::Left);
         assert_ne!(event1, event4); // Different is_press state
     }
+
+    #[test]
+    fn test_encode_mouse_motion() {
+        let drag = MouseEvent::motion(0, 0, Some(MouseButton::Right));
+        assert_eq!(drag.to_bytes(), b"\x1b[<34;1;1M");
+        assert_eq!(
+            encode_mouse_event_for(&drag, MouseMode::Normal),
+            Some(vec![0x1b, b'[', b'M', 66, 33, 33])
+        );
+        assert_eq!(encode_mouse_event_for(&drag, MouseMode::X10), None);
+    }
+
+    #[test]
+    fn test_encode_mouse_normal_mode() {
+        let press = MouseEvent::press_with_modifiers(0, 1, MouseButton::Middle, Modifiers::CTRL);
+        assert_eq!(
+            encode_mouse_event_for(&press, MouseMode::Normal),
+            Some(vec![0x1b, b'[', b'M', 32 + 1 + 16, 33, 34])
+        );
+
+        // Releases report button 3
+        let release = MouseEvent::release(0, 1, MouseButton::Middle);
+        assert_eq!(
+            encode_mouse_event_for(&release, MouseMode::Normal),
+            Some(vec![0x1b, b'[', b'M', 32 + 3, 33, 34])
+        );
+
+        let scroll = MouseEvent::scroll(2, 2, ScrollDirection::Down);
+        assert_eq!(
+            encode_mouse_event_for(&scroll, MouseMode::Normal),
+            Some(vec![0x1b, b'[', b'M', 32 + 65, 35, 35])
+        );
+
+        // Coordinates beyond 223 cannot be encoded
+        let far = MouseEvent::press(223, 0, MouseButton::Left);
+        assert_eq!(encode_mouse_event_for(&far, MouseMode::Normal), None);
+        let edge = MouseEvent::press(222, 0, MouseButton::Left);
+        assert_eq!(
+            encode_mouse_event_for(&edge, MouseMode::Normal),
+            Some(vec![0x1b, b'[', b'M', 32, 255, 33])
+        );
+    }
+
+    #[test]
+    fn test_encode_mouse_x10_mode() {
+        let press = MouseEvent::press_with_modifiers(4, 0, MouseButton::Left, Modifiers::SHIFT);
+        assert_eq!(
+            encode_mouse_event_for(&press, MouseMode::X10),
+            Some(vec![0x1b, b'[', b'M', 32, 37, 33])
+        );
+
+        let release = MouseEvent::release(4, 0, MouseButton::Left);
+        assert_eq!(encode_mouse_event_for(&release, MouseMode::X10), None);
+        let scroll = MouseEvent::scroll(4, 0, ScrollDirection::Up);
+        assert_eq!(encode_mouse_event_for(&scroll, MouseMode::X10), None);
+    }
+
+    #[test]
+    fn test_encode_mouse_sgr_and_off_modes() {
+        let click = MouseEvent::press(10, 5, MouseButton::Left);
+        assert_eq!(encode_mouse_event_for(&click, MouseMode::Sgr), Some(b"\x1b[<0;11;6M".to_vec()));
+        assert_eq!(encode_mouse_event_for(&click, MouseMode::Off), None);
+    }
 }
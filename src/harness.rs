@@ -40,22 +40,27 @@
 //! ```
 
 use std::{
-    fs::File,
-    io::Write,
-    path::Path,
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
 use portable_pty::{CommandBuilder, ExitStatus};
 
 use crate::{
+    clock::{Clock, SystemClock},
     error::{Result, TermTestError},
     events::{
-        encode_key_event, encode_mouse_event, KeyCode, KeyEvent, Modifiers, MouseButton,
-        MouseEvent, ScrollDirection,
+        encode_key_event_for, encode_key_event_kitty, encode_mouse_event, encode_mouse_event_for,
+        KeyCode, KeyEvent, Modifiers, MouseButton, MouseEvent, MouseMode, ScrollDirection,
     },
-    pty::TestTerminal,
-    screen::ScreenState,
+    pty::{OutputPump, TestTerminal},
+    screen::{ScreenState, UnderlineColor, UnderlineStyle},
     terminal_profiles::{Feature, TerminalCapabilities, TerminalProfile},
     timing::{fps_to_frame_budget, LatencyProfile, TimingHooks, TimingRecorder},
 };
@@ -167,12 +172,153 @@ pub enum Axis {
     Vertical,
 }
 
+/// Line ending that [`TuiTestHarness::send_text`] writes for each `\n`.
+///
+/// Pressing Enter on a real terminal sends a carriage return, so the default
+/// is [`Newline::Cr`]. How the application sees it depends on the PTY mode:
+///
+/// - In cooked (canonical) mode the line discipline's `ICRNL` flag, on by
+///   default, turns an incoming `\r` into `\n`, so shells and line-reading
+///   programs see a complete line for either [`Cr`](Newline::Cr) or
+///   [`Lf`](Newline::Lf). [`Crlf`](Newline::Crlf) becomes `\n\n` there,
+///   submitting an extra empty line.
+/// - In raw mode, which full-screen TUIs enable, bytes arrive untranslated.
+///   Most input libraries report `\r` as the Enter key but a bare `\n` as
+///   Ctrl+J, so `"cmd\n"` with [`Lf`](Newline::Lf) may not submit anything.
+///
+/// Only `\n` is rewritten; a `\r` already in the text is sent as is, so
+/// `"cmd\r\n"` with the default becomes `"cmd\r\r"`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use terminal_testlib::{Newline, TuiTestHarness};
+///
+/// # fn test() -> terminal_testlib::Result<()> {
+/// // Send newlines untouched, e.g. to pipe text into a raw-mode editor
+/// let mut harness = TuiTestHarness::new(80, 24)?.with_newline(Newline::Lf);
+/// harness.send_text("line one\nline two\n")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    /// Line feed (`\n`), the text as written.
+    Lf,
+    /// Carriage return followed by line feed (`\r\n`).
+    Crlf,
+    /// Carriage return (`\r`), what the Enter key sends.
+    #[default]
+    Cr,
+}
+
+impl Newline {
+    /// Returns the bytes written for one newline.
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Newline::Lf => b"\n",
+            Newline::Crlf => b"\r\n",
+            Newline::Cr => b"\r",
+        }
+    }
+
+    /// Rewrites every `\n` in `text` to this newline.
+    fn apply(self, text: &str) -> Vec<u8> {
+        if self == Newline::Lf {
+            return text.as_bytes().to_vec();
+        }
+        let mut bytes = Vec::with_capacity(text.len());
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                bytes.extend_from_slice(self.as_bytes());
+            }
+            bytes.extend_from_slice(line.as_bytes());
+        }
+        bytes
+    }
+}
+
 /// Default polling interval for wait operations (100ms).
 const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Default buffer size for reading PTY output (4KB).
 const DEFAULT_BUFFER_SIZE: usize = 4096;
 
+/// How long to keep collecting background-pump output after the child exits.
+const PUMP_EXIT_GRACE: Duration = Duration::from_millis(100);
+
+/// Environment variable through which the stderr wrapper receives its capture file.
+const STDERR_PATH_VAR: &str = "TERMINAL_TESTLIB_STDERR";
+
+/// Environment variable that switches timeout diagnostics to
+/// [`ScreenState::contents_with_ruler`].
+const RULER_VAR: &str = "TERMINAL_TESTLIB_RULER";
+
+/// Counter for unique stderr capture file names within this process.
+static STDERR_CAPTURE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Child stderr redirected to a temporary file instead of the PTY.
+///
+/// The wrapper shell unlinks the file as soon as it has redirected stderr
+/// into it, so nothing is left in the temp directory even if the test
+/// aborts. Both sides keep reading and writing through their open handles.
+/// If the child never starts, the file is removed when the capture is
+/// dropped.
+#[derive(Debug)]
+struct StderrCapture {
+    path: PathBuf,
+    /// Read handle; its position marks what `stderr_contents` has returned.
+    file: File,
+}
+
+impl StderrCapture {
+    fn create() -> Result<Self> {
+        let id = STDERR_CAPTURE_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "terminal-testlib-stderr-{}-{}.log",
+            std::process::id(),
+            id
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    /// Rewrites `cmd` to run under `sh` with stderr appended to the capture
+    /// file, which the shell then unlinks before running the command.
+    fn wrap(&self, cmd: &mut CommandBuilder) {
+        let argv = cmd.get_argv_mut();
+        let mut wrapped = vec![
+            "sh".into(),
+            "-c".into(),
+            format!(
+                "exec 2>>\"${var}\" && rm -f -- \"${var}\" && exec \"$0\" \"$@\"",
+                var = STDERR_PATH_VAR
+            )
+            .into(),
+        ];
+        wrapped.append(argv);
+        *argv = wrapped;
+        cmd.env(STDERR_PATH_VAR, &self.path);
+    }
+
+    /// Reads stderr written since the previous call.
+    fn drain(&mut self) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.file.read_to_end(&mut bytes)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl Drop for StderrCapture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
 /// An event that occurred during test execution, recorded for debugging.
 ///
 /// This enum represents different types of events that can be captured during
@@ -250,6 +396,25 @@ pub struct TuiTestHarness {
     // Timing and latency profiling
     timing_recorder: TimingRecorder,
     latency_profile: LatencyProfile,
+    // Verify the program exists before spawning
+    check_command: bool,
+    // Environment and working directory applied at spawn
+    env: Vec<(String, String)>,
+    cwd: Option<PathBuf>,
+    // Route child stderr to a file instead of the PTY
+    separate_stderr: bool,
+    stderr_capture: Option<StderrCapture>,
+    // Drain the PTY on a background thread instead of only when polled
+    background_pump: bool,
+    pump: Option<OutputPump>,
+    // Time source for wait loops
+    clock: Arc<dyn Clock>,
+    // Encode keys with the Kitty keyboard protocol when the app enables it
+    kitty_keyboard: bool,
+    // Line ending written by send_text for each `\n`
+    newline: Newline,
+    // Reference point for assert_text_within, set on spawn or mark_start
+    start_mark: Option<Instant>,
 }
 
 impl TuiTestHarness {
@@ -264,7 +429,7 @@ impl TuiTestHarness {
     ///
     /// Returns an error if terminal creation fails.
     pub fn new(width: u16, height: u16) -> Result<Self> {
-        let terminal = TestTerminal::new(width, height)?;
+        let terminal = TestTerminal::new(width, height)?.with_buffer_size(DEFAULT_BUFFER_SIZE);
         let state = ScreenState::new(width, height);
 
         Ok(Self {
@@ -281,6 +446,17 @@ impl TuiTestHarness {
             terminal_profile: TerminalProfile::default(),
             timing_recorder: TimingRecorder::new(),
             latency_profile: LatencyProfile::new(),
+            check_command: true,
+            env: Vec::new(),
+            cwd: None,
+            separate_stderr: false,
+            stderr_capture: None,
+            background_pump: false,
+            pump: None,
+            clock: Arc::new(SystemClock),
+            kitty_keyboard: true,
+            newline: Newline::default(),
+            start_mark: None,
         })
     }
 
@@ -323,12 +499,194 @@ impl TuiTestHarness {
         self
     }
 
+    /// Sets the clock used to measure timeouts and sleep between polls.
+    ///
+    /// Defaults to [`SystemClock`]. With a [`FakeClock`](crate::FakeClock) the
+    /// timeout and the sleeps between polls take no real time, but each poll
+    /// still reads the PTY. Without the
+    /// [background pump](Self::with_background_pump) that read blocks for up
+    /// to 100ms of real time per poll, so enable the pump to drive waits to
+    /// their timeout without waiting.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for wait operations
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Enables or disables Kitty keyboard protocol encoding for key input.
+    ///
+    /// When enabled (the default), keys sent with [`send_key`](Self::send_key)
+    /// and friends are encoded with
+    /// [`encode_key_event_kitty`](crate::events::encode_key_event_kitty) using
+    /// the flags the application pushed (`ESC [ > flags u`, see
+    /// [`ScreenState::kitty_keyboard_flags`]). Applications that never enable
+    /// the protocol get legacy encodings either way. Disable this to always
+    /// send legacy bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to follow the application's Kitty keyboard flags
+    pub fn with_kitty_keyboard(mut self, enabled: bool) -> Self {
+        self.kitty_keyboard = enabled;
+        self
+    }
+
+    /// Sets the line ending [`send_text`](Self::send_text) writes for `\n`.
+    ///
+    /// Defaults to [`Newline::Cr`], matching the Enter key. See [`Newline`]
+    /// for how each choice interacts with cooked and raw terminal modes.
+    ///
+    /// # Arguments
+    ///
+    /// * `newline` - Line ending to send in place of `\n`
+    pub fn with_newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Enables or disables the pre-spawn check that the program exists.
+    ///
+    /// When enabled (the default), [`spawn`](Self::spawn) returns
+    /// [`TermTestError::CommandNotFound`] if the program is neither an existing
+    /// path nor resolvable on `PATH`, instead of failing later with a confusing
+    /// spawn error or a wait timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to check the program before spawning
+    pub fn with_command_check(mut self, enabled: bool) -> Self {
+        self.check_command = enabled;
+        self
+    }
+
+    /// Sets an environment variable for processes spawned by this harness.
+    ///
+    /// Variables are applied on top of the command's own environment at
+    /// [`spawn`](Self::spawn) time, so they override values set on the
+    /// `CommandBuilder`. `TERM` follows the
+    /// [terminal profile](Self::with_terminal_profile) unless set here.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Variable name
+    /// * `value` - Variable value
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?
+    ///     .with_env("HOME", "/tmp/test-home")
+    ///     .with_env("TERM", "xterm");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the working directory for processes spawned by this harness.
+    ///
+    /// Overrides any working directory set on the `CommandBuilder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Working directory for the child process
+    pub fn with_cwd(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(path.into());
+        self
+    }
+
+    /// Routes the child's stderr to a separate capture instead of the PTY.
+    ///
+    /// When enabled, spawned commands run under `sh` with stderr redirected
+    /// to a temporary file, so panic messages and diagnostics do not scramble
+    /// the rendered screen. Read them with [`stderr_contents`](Self::stderr_contents).
+    /// The file is unlinked as soon as the child has started, so it is not
+    /// left behind if the test panics or aborts.
+    ///
+    /// Unix only: requires `sh`, and relies on reading a file after it has
+    /// been unlinked. The default shell command (spawned with an empty
+    /// `CommandBuilder`) is not wrapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to capture stderr separately
+    #[cfg(unix)]
+    pub fn with_separate_stderr(mut self, enabled: bool) -> Self {
+        self.separate_stderr = enabled;
+        self
+    }
+
+    /// Drains PTY output on a background thread instead of only when polled.
+    ///
+    /// By default output is read synchronously by
+    /// [`update_state`](Self::update_state) and the wait methods. A chatty
+    /// child can fill the PTY's kernel buffer while the test is doing
+    /// something else (sleeping, asserting, talking to another process) and
+    /// then block on write, so a long-running app appears to hang.
+    ///
+    /// With the pump enabled, a reader thread started at the first
+    /// [`spawn`](Self::spawn) keeps the kernel buffer empty and queues output
+    /// in memory. The screen still only changes when `update_state` or a wait
+    /// method runs, so assertions see a consistent snapshot between polls.
+    ///
+    /// Tradeoffs compared with the synchronous mode:
+    /// - One extra thread per harness. It exits once the child's PTY closes,
+    ///   which normally happens when the harness is dropped and the child is
+    ///   killed. With [`with_kill_on_drop(false)`](TuiTestHarnessBuilder::with_kill_on_drop)
+    ///   a surviving child keeps the PTY open, and the thread stays blocked
+    ///   reading it until that child exits or writes again.
+    /// - Queued output is bounded at 256 reads of the
+    ///   [buffer size](TuiTestHarnessBuilder::with_buffer_size) (1 MiB with
+    ///   the default 4 KiB). Past that the child blocks on write until the
+    ///   test polls, as in the synchronous mode.
+    /// - After the child exits, remaining output is collected until the PTY
+    ///   has been quiet for 100ms, as in the synchronous mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to drain output on a background thread
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_background_pump(true);
+    /// harness.spawn(CommandBuilder::new("./verbose-app"))?;
+    ///
+    /// // The app keeps writing while the test does other work
+    /// std::thread::sleep(std::time::Duration::from_secs(1));
+    /// harness.wait_for_text("Done")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_background_pump(mut self, enabled: bool) -> Self {
+        self.background_pump = enabled;
+        self
+    }
+
     /// Configures the harness for a specific terminal emulator profile.
     ///
     /// This sets the terminal profile which controls which features are available
     /// during testing. Use this to ensure your TUI application works correctly
     /// across different terminal emulators.
     ///
+    /// The profile also sets `TERM` for processes spawned afterwards (see
+    /// [`TerminalProfile::term_name`]; the default profile gives
+    /// `xterm-256color`), and decides the bytes [`send_key`](Self::send_key)
+    /// sends for keys such as Home, End and F1 whose encoding differs between
+    /// terminals (see [`encode_key_event_for`](crate::events::encode_key_event_for)).
+    ///
     /// # Arguments
     ///
     /// * `profile` - The terminal profile to use
@@ -464,19 +822,108 @@ impl TuiTestHarness {
 
     /// Spawns a process in the PTY.
     ///
+    /// Unless `cmd` sets `TERM` itself, it is set to the
+    /// [terminal profile](Self::with_terminal_profile)'s `TERM` name. Any
+    /// variables from [`with_env`](Self::with_env) and the directory from
+    /// [`with_cwd`](Self::with_cwd) are then applied to the command.
+    ///
     /// # Arguments
     ///
     /// * `cmd` - Command to spawn
     ///
     /// # Errors
     ///
-    /// Returns an error if spawning fails.
-    pub fn spawn(&mut self, cmd: CommandBuilder) -> Result<()> {
-        self.terminal.spawn(cmd)
+    /// Returns [`TermTestError::CommandNotFound`] if the command check is enabled
+    /// and the program cannot be resolved, or another error if spawning fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::{TermTestError, TuiTestHarness};
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let result = harness.spawn(CommandBuilder::new("definitely-not-a-real-binary"));
+    /// assert!(matches!(result, Err(TermTestError::CommandNotFound { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn(&mut self, mut cmd: CommandBuilder) -> Result<()> {
+        // get_env also sees TERM inherited from this process, which the
+        // profile should override, so only look at variables set on `cmd`
+        if !cmd.iter_extra_env_as_str().any(|(key, _)| key == "TERM") {
+            cmd.env("TERM", self.terminal_profile.term_name());
+        }
+        for (key, value) in &self.env {
+            cmd.env(key, value);
+        }
+        if let Some(cwd) = &self.cwd {
+            cmd.cwd(cwd);
+        }
+
+        if self.check_command {
+            check_program_exists(&cmd)?;
+        }
+
+        self.stderr_capture = None;
+        if self.separate_stderr && !cmd.get_argv().is_empty() {
+            let capture = StderrCapture::create()?;
+            capture.wrap(&mut cmd);
+            self.stderr_capture = Some(capture);
+        }
+
+        self.terminal.spawn(cmd)?;
+        self.start_mark = Some(self.clock.now());
+        if self.background_pump && self.pump.is_none() {
+            self.pump = Some(self.terminal.start_output_pump()?);
+        }
+        Ok(())
+    }
+
+    /// Returns stderr written by the child since the previous call.
+    ///
+    /// Only available when the harness was configured with
+    /// [`with_separate_stderr`](Self::with_separate_stderr); otherwise stderr
+    /// goes to the PTY and this returns an empty string. Each call drains the
+    /// capture, returning only output not yet seen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capture file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::builder()
+    ///     .with_separate_stderr(true)
+    ///     .build()?;
+    /// harness.spawn(CommandBuilder::new("./my-tui"))?;
+    /// // ... drive the app ...
+    ///
+    /// let stderr = harness.stderr_contents()?;
+    /// assert!(!stderr.contains("panicked"), "app panicked:\n{}", stderr);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stderr_contents(&mut self) -> Result<String> {
+        match self.stderr_capture.as_mut() {
+            Some(capture) => capture.drain(),
+            None => Ok(String::new()),
+        }
     }
 
     /// Sends text to the PTY.
     ///
+    /// The whole string is written at once. To simulate typing character by
+    /// character, use [`type_text_with_delay`](Self::type_text_with_delay).
+    /// Each `\n` is sent as the configured [`Newline`] (a carriage return by
+    /// default, like pressing Enter); see [`with_newline`](Self::with_newline).
+    ///
     /// # Arguments
     ///
     /// * `text` - Text to send
@@ -489,9 +936,9 @@ impl TuiTestHarness {
         self.timing_recorder.record_event("input_sent");
         self.latency_profile.mark_input();
 
-        let bytes = text.as_bytes();
-        self.record_input(bytes);
-        self.terminal.write(bytes)?;
+        let bytes = self.newline.apply(text);
+        self.record_input(&bytes);
+        self.terminal.write(&bytes)?;
 
         // Update state, ignoring ProcessExited since the process might exit
         // after receiving input (e.g., sending 'q' to quit)
@@ -517,6 +964,8 @@ impl TuiTestHarness {
     /// # Errors
     ///
     /// Returns an error if the write fails or state update fails.
+    /// Returns [`TermTestError::UnsupportedKey`] if the key has no escape
+    /// sequence, such as `KeyCode::F(0)`.
     ///
     /// # Example
     ///
@@ -554,6 +1003,8 @@ impl TuiTestHarness {
     /// # Errors
     ///
     /// Returns an error if the write fails or state update fails.
+    /// Returns [`TermTestError::UnsupportedKey`] if the key has no escape
+    /// sequence, such as `KeyCode::F(0)`.
     ///
     /// # Example
     ///
@@ -635,6 +1086,114 @@ impl TuiTestHarness {
         self.send_keys(text)
     }
 
+    /// Types text one character at a time with a delay between characters.
+    ///
+    /// Each character is written to the PTY, then the harness sleeps for
+    /// `delay` and updates the screen state before the next one. This simulates
+    /// human typing speed, exercising debouncing and incremental behavior (e.g.
+    /// incremental search) that an instant write can hide, and lets tests
+    /// observe intermediate screens via the recording APIs.
+    ///
+    /// Use [`send_text`](Self::send_text) to write the whole string at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to type
+    /// * `delay` - Pause after each character
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn application ...
+    ///
+    /// harness.type_text_with_delay("search term", Duration::from_millis(30))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn type_text_with_delay(&mut self, text: &str, delay: Duration) -> Result<()> {
+        let mut buf = [0u8; 4];
+        for ch in text.chars() {
+            self.timing_recorder.record_event("input_sent");
+            self.latency_profile.mark_input();
+
+            let bytes = ch.encode_utf8(&mut buf).as_bytes();
+            self.record_input(bytes);
+            self.terminal.write_all(bytes)?;
+
+            std::thread::sleep(delay);
+            let _ = self.update_state();
+
+            self.timing_recorder.record_event("render_complete");
+            self.latency_profile.mark_render_end();
+            self.latency_profile.mark_frame_ready();
+        }
+        Ok(())
+    }
+
+    /// Pastes text into the PTY.
+    ///
+    /// If the application has enabled bracketed paste mode (`ESC [ ? 2004 h`),
+    /// the payload is wrapped in the `ESC [ 200 ~` / `ESC [ 201 ~` markers so
+    /// the application can tell it apart from typed input. Otherwise the text
+    /// is written as-is, like [`send_text`](Self::send_text).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to paste
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::TuiTestHarness;
+    /// use portable_pty::CommandBuilder;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-editor"))?;
+    /// harness.wait_for(|state| state.bracketed_paste_enabled())?;
+    ///
+    /// harness.paste("line one\nline two")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paste(&mut self, text: &str) -> Result<()> {
+        if !self.state.bracketed_paste_enabled() {
+            return self.send_text(text);
+        }
+
+        self.timing_recorder.record_event("input_sent");
+        self.latency_profile.mark_input();
+
+        let mut bytes = Vec::with_capacity(text.len() + 12);
+        bytes.extend_from_slice(b"\x1b[200~");
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.extend_from_slice(b"\x1b[201~");
+        self.record_input(&bytes);
+        self.terminal.write(&bytes)?;
+
+        let _ = self.update_state();
+
+        self.timing_recorder.record_event("render_complete");
+        self.latency_profile.mark_render_end();
+        self.latency_profile.mark_frame_ready();
+
+        Ok(())
+    }
+
     /// Sets the delay between consecutive events.
     ///
     /// This configures how long the harness waits after sending each event before
@@ -795,29 +1354,77 @@ impl TuiTestHarness {
     /// # }
     /// ```
     pub fn send_mouse_event(&mut self, event: MouseEvent) -> Result<()> {
-        // Record input timestamp for latency profiling
-        self.timing_recorder.record_event("input_sent");
-        self.latency_profile.mark_input();
-
-        let bytes = encode_mouse_event(&event);
-        self.record_input(&bytes);
-        self.terminal.write_all(&bytes)?;
-
-        // Apply configured event delay, or use default 50ms if no delay is set
-        let delay = if self.event_delay.is_zero() {
-            Duration::from_millis(50)
-        } else {
-            self.event_delay
-        };
-        std::thread::sleep(delay);
-
-        // Update state
-        let _ = self.update_state();
+        self.write_mouse_bytes(&encode_mouse_event(&event))
+    }
 
-        // Record render completion
-        self.timing_recorder.record_event("render_complete");
-        self.latency_profile.mark_render_end();
-        self.latency_profile.mark_frame_ready();
+    /// Sends a mouse event using the protocol the application enabled.
+    ///
+    /// Unlike [`send_mouse_event`](Self::send_mouse_event), which always uses
+    /// SGR encoding, this encodes the event for the current
+    /// [`ScreenState::mouse_mode`]: X10 (`?9`), normal tracking (`?1000`) or
+    /// SGR (`?1006`). Events the active protocol cannot report, such as
+    /// releases in X10 mode, are dropped without writing anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The mouse event to send
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::MouseReportingDisabled`] if the application
+    /// has not enabled mouse tracking, or an error if the write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::{MouseButton, MouseEvent, MouseMode, TuiTestHarness};
+    /// use portable_pty::CommandBuilder;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-tui"))?;
+    /// harness.wait_for(|state| state.mouse_mode() != MouseMode::Off)?;
+    ///
+    /// harness.send_mouse(MouseEvent::press(10, 5, MouseButton::Left))?;
+    /// harness.send_mouse(MouseEvent::release(10, 5, MouseButton::Left))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_mouse(&mut self, event: MouseEvent) -> Result<()> {
+        let mode = self.state.mouse_mode();
+        if mode == MouseMode::Off {
+            return Err(TermTestError::MouseReportingDisabled);
+        }
+        match encode_mouse_event_for(&event, mode) {
+            Some(bytes) => self.write_mouse_bytes(&bytes),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes encoded mouse input and waits for the application to react.
+    fn write_mouse_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        // Record input timestamp for latency profiling
+        self.timing_recorder.record_event("input_sent");
+        self.latency_profile.mark_input();
+
+        self.record_input(bytes);
+        self.terminal.write_all(bytes)?;
+
+        // Apply configured event delay, or use default 50ms if no delay is set
+        let delay = if self.event_delay.is_zero() {
+            Duration::from_millis(50)
+        } else {
+            self.event_delay
+        };
+        std::thread::sleep(delay);
+
+        // Update state
+        let _ = self.update_state();
+
+        // Record render completion
+        self.timing_recorder.record_event("render_complete");
+        self.latency_profile.mark_render_end();
+        self.latency_profile.mark_frame_ready();
 
         Ok(())
     }
@@ -918,11 +1525,24 @@ impl TuiTestHarness {
     /// This encodes the key event to bytes, writes to the PTY, adds a small
     /// delay for the application to process the input, and updates the screen state.
     fn send_key_event(&mut self, event: KeyEvent) -> Result<()> {
+        let flags = if self.kitty_keyboard {
+            self.state.kitty_keyboard_flags()
+        } else {
+            0
+        };
+        let bytes = if flags == 0 {
+            encode_key_event_for(&event, self.terminal_profile)
+        } else {
+            encode_key_event_kitty(&event, flags)
+        };
+        if bytes.is_empty() {
+            return Err(TermTestError::UnsupportedKey(format!("{:?}", event.code)));
+        }
+
         // Record input timestamp for latency profiling
         self.timing_recorder.record_event("input_sent");
         self.latency_profile.mark_input();
 
-        let bytes = encode_key_event(&event);
         self.record_input(&bytes);
         self.terminal.write_all(&bytes)?;
 
@@ -952,6 +1572,9 @@ impl TuiTestHarness {
     /// terminal emulator. It handles partial escape sequences correctly by continuing
     /// to read until no more data is available.
     ///
+    /// With [`with_background_pump`](Self::with_background_pump) enabled, the
+    /// output already drained by the pump thread is fed instead.
+    ///
     /// This is called automatically by other methods but can be called
     /// manually if needed.
     ///
@@ -960,6 +1583,10 @@ impl TuiTestHarness {
     /// Returns an error if reading from the PTY fails.
     /// Returns [`TermTestError::ProcessExited`] if the child process has exited.
     pub fn update_state(&mut self) -> Result<()> {
+        if self.pump.is_some() {
+            return self.update_state_from_pump();
+        }
+
         // First check if the child process has exited
         if !self.terminal.is_running() {
             // Process has exited - try to read any remaining buffered output
@@ -1006,6 +1633,28 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Feeds output queued by the background pump into the screen state.
+    fn update_state_from_pump(&mut self) -> Result<()> {
+        let running = self.terminal.is_running();
+        let chunks = match &self.pump {
+            Some(pump) if running => pump.drain(),
+            Some(pump) => pump.drain_until_quiet(PUMP_EXIT_GRACE),
+            None => Vec::new(),
+        };
+
+        for chunk in chunks {
+            self.record_output(&chunk);
+            self.state.feed(&chunk);
+            self.record_state_change();
+        }
+
+        if running {
+            Ok(())
+        } else {
+            Err(TermTestError::ProcessExited)
+        }
+    }
+
     /// Waits for a condition to be true, with timeout.
     ///
     /// This method polls the PTY output at the configured interval and checks
@@ -1051,11 +1700,61 @@ impl TuiTestHarness {
     ///
     /// Returns a `Timeout` error if the condition is not met within the configured timeout.
     /// Returns `ProcessExited` if the child process exits before the condition is met.
+    ///
+    /// Either way the screen is dumped to stderr. Set `TERMINAL_TESTLIB_RULER`
+    /// to include row numbers and a column ruler, as in
+    /// [`ScreenState::contents_with_ruler`].
     pub fn wait_for_with_context<F>(&mut self, condition: F, description: &str) -> Result<()>
     where
         F: Fn(&ScreenState) -> bool,
     {
-        let start = Instant::now();
+        let timeout = self.timeout;
+        self.wait_until(condition, description, timeout)
+    }
+
+    /// Waits for a condition with a timeout that applies to this call only.
+    ///
+    /// Like [`wait_for`](Self::wait_for), but `timeout` overrides the
+    /// harness-wide timeout without changing it for later waits.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - Condition to wait for
+    /// * `timeout` - Timeout duration for this operation
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if the condition is not met within `timeout`.
+    /// Returns `ProcessExited` if the child process exits before the condition is met.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::TuiTestHarness;
+    /// # use std::time::Duration;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_timeout(|state| state.contains("Indexed"), Duration::from_secs(30))?;
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_timeout<F>(&mut self, condition: F, timeout: Duration) -> Result<()>
+    where
+        F: Fn(&ScreenState) -> bool,
+    {
+        self.wait_until(condition, "condition", timeout)
+    }
+
+    /// Polls `condition` until it holds or `timeout` elapses, dumping the
+    /// screen to stderr on failure.
+    pub(crate) fn wait_until<F>(
+        &mut self,
+        condition: F,
+        description: &str,
+        timeout: Duration,
+    ) -> Result<()>
+    where
+        F: Fn(&ScreenState) -> bool,
+    {
+        let start = self.clock.now();
         let mut iterations = 0;
 
         loop {
@@ -1074,11 +1773,15 @@ impl TuiTestHarness {
                     }
 
                     // Condition not met and process has exited
-                    let current_state = self.state.debug_contents();
+                    let current_state = self.diagnostic_contents();
                     let cursor = self.state.cursor_position();
 
                     eprintln!("\n=== Process exited while waiting for: {} ===", description);
-                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
+                    eprintln!(
+                        "Waited: {:?} ({} iterations)",
+                        self.clock.elapsed_since(start),
+                        iterations
+                    );
                     eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
                     eprintln!("Final screen state:\n{}", current_state);
                     eprintln!("==========================================\n");
@@ -1088,10 +1791,10 @@ impl TuiTestHarness {
                 Err(e) => return Err(e),
             }
 
-            let elapsed = start.elapsed();
-            if elapsed >= self.timeout {
+            let elapsed = self.clock.elapsed_since(start);
+            if elapsed >= timeout {
                 // Create a detailed error message with current state
-                let current_state = self.state.debug_contents();
+                let current_state = self.diagnostic_contents();
                 let cursor = self.state.cursor_position();
 
                 eprintln!("\n=== Timeout waiting for: {} ===", description);
@@ -1100,13 +1803,11 @@ impl TuiTestHarness {
                 eprintln!("Current screen state:\n{}", current_state);
                 eprintln!("==========================================\n");
 
-                return Err(TermTestError::Timeout {
-                    timeout_ms: self.timeout.as_millis() as u64,
-                });
+                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
             }
 
             iterations += 1;
-            std::thread::sleep(self.poll_interval);
+            self.clock.sleep(self.poll_interval);
         }
     }
 
@@ -1163,55 +1864,187 @@ impl TuiTestHarness {
     pub fn wait_for_text_timeout(&mut self, text: &str, timeout: Duration) -> Result<()> {
         let text = text.to_string();
         let description = format!("text '{}'", text);
+        self.wait_until(move |state| state.contains(&text), &description, timeout)
+    }
 
-        let start = Instant::now();
-        let mut iterations = 0;
-
-        loop {
-            // Update state - this may return ProcessExited
-            match self.update_state() {
-                Ok(()) => {
-                    if self.state.contains(&text) {
-                        return Ok(());
-                    }
-                }
-                Err(TermTestError::ProcessExited) => {
-                    // Process exited - check condition one final time
-                    if self.state.contains(&text) {
-                        return Ok(());
-                    }
-
-                    let current_state = self.state.debug_contents();
-                    let cursor = self.state.cursor_position();
-
-                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
-                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
-                    eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                    eprintln!("Final screen state:\n{}", current_state);
-                    eprintln!("==========================================\n");
+    /// Resets the reference point for [`assert_text_within`](Self::assert_text_within)
+    /// to now.
+    ///
+    /// [`spawn`](Self::spawn) sets it automatically. Call this right before an
+    /// action whose response time is being measured, such as a key press.
+    pub fn mark_start(&mut self) {
+        self.start_mark = Some(self.clock.now());
+    }
 
-                    return Err(TermTestError::ProcessExited);
-                }
-                Err(e) => return Err(e),
-            }
+    /// Asserts that text appears within `budget` of spawn or the last
+    /// [`mark_start`](Self::mark_start).
+    ///
+    /// Time is measured from the reference point to the poll that finds the
+    /// text, so detection can lag by up to the poll interval; use a short
+    /// [`with_poll_interval`](Self::with_poll_interval) for tight budgets.
+    /// Output is only timestamped when it is read, so call this right after
+    /// the spawn or action rather than after other waits. Without a reference
+    /// point, timing starts from this call.
+    ///
+    /// Waiting continues past the budget, up to the harness timeout, so a
+    /// late frame reports how late it actually was.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to wait for
+    /// * `budget` - Longest acceptable time for the text to appear
+    ///
+    /// # Returns
+    ///
+    /// The time the text took to appear.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::BudgetExceeded`] with the actual elapsed time
+    /// if the text appeared too late, or a `Timeout` error if it did not
+    /// appear at all within the larger of the budget and the configured
+    /// timeout.
+    /// Returns `ProcessExited` if the child process exits before the text appears.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_poll_interval(Duration::from_millis(5));
+    /// harness.spawn(CommandBuilder::new("./my-tui"))?;
+    ///
+    /// // The first frame must render within 100ms of spawn
+    /// harness.assert_text_within("Dashboard", Duration::from_millis(100))?;
+    ///
+    /// // Opening the help screen must take under 50ms
+    /// harness.mark_start();
+    /// harness.send_text("?")?;
+    /// harness.assert_text_within("Keybindings", Duration::from_millis(50))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_text_within(&mut self, text: &str, budget: Duration) -> Result<Duration> {
+        let start = self.start_mark.unwrap_or_else(|| self.clock.now());
+        self.start_mark = Some(start);
+        let timeout = self
+            .timeout
+            .max(budget)
+            .saturating_sub(self.clock.elapsed_since(start));
+        self.wait_for_text_timeout(text, timeout)?;
+
+        let elapsed = self.clock.elapsed_since(start);
+        if elapsed <= budget {
+            Ok(elapsed)
+        } else {
+            Err(TermTestError::BudgetExceeded {
+                text: text.to_string(),
+                budget_ms: budget.as_millis() as u64,
+                elapsed_ms: elapsed.as_millis() as u64,
+            })
+        }
+    }
 
-            let elapsed = start.elapsed();
-            if elapsed >= timeout {
-                let current_state = self.state.debug_contents();
-                let cursor = self.state.cursor_position();
+    /// Waits for whichever of several texts appears first.
+    ///
+    /// Useful when a command can end with different messages, such as
+    /// success or an error, so the wait does not run out the timeout on one
+    /// guess. Every candidate is checked on each poll. Uses the configured
+    /// timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - Candidate texts
+    ///
+    /// # Returns
+    ///
+    /// The index in `texts` of the candidate found. If several are on screen
+    /// at the same poll, the lowest index wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if none of the texts appear within the configured timeout.
+    /// Returns `ProcessExited` if the child process exits before any of them appear.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// match harness.wait_for_any(&["Saved", "Error"])? {
+    ///     0 => println!("saved"),
+    ///     _ => println!("failed: {}", harness.screen_contents()),
+    /// }
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_any(&mut self, texts: &[&str]) -> Result<usize> {
+        let timeout = self.timeout;
+        self.wait_for_any_timeout(texts, timeout)
+    }
 
-                eprintln!("\n=== Timeout waiting for: {} ===", description);
-                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
-                eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                eprintln!("Current screen state:\n{}", current_state);
-                eprintln!("==========================================\n");
+    /// Waits for whichever of several texts appears first, with a custom timeout.
+    ///
+    /// See [`wait_for_any`](Self::wait_for_any).
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - Candidate texts
+    /// * `timeout` - Timeout duration for this operation
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if none of the texts appear within the specified timeout.
+    /// Returns `ProcessExited` if the child process exits before any of them appear.
+    pub fn wait_for_any_timeout(&mut self, texts: &[&str], timeout: Duration) -> Result<usize> {
+        let description = format!(
+            "any of {}",
+            texts
+                .iter()
+                .map(|text| format!("'{}'", text))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let first_match = |state: &ScreenState| texts.iter().position(|text| state.contains(text));
 
-                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
-            }
+        self.wait_until(|state| first_match(state).is_some(), &description, timeout)?;
+        Ok(first_match(&self.state).unwrap_or_default())
+    }
 
-            iterations += 1;
-            std::thread::sleep(self.poll_interval);
-        }
+    /// Waits for the application to copy `expected` to the clipboard via OSC 52.
+    ///
+    /// Matches the most recent clipboard write exactly, whichever selection it
+    /// targeted. See [`ScreenState::clipboard`]. Uses the configured timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - Text the clipboard write should decode to
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if no matching clipboard write arrives within the
+    /// configured timeout.
+    /// Returns `ProcessExited` if the child process exits before the write arrives.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_text("y")?;
+    /// harness.wait_for_clipboard("copied line")?;
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_clipboard(&mut self, expected: &str) -> Result<()> {
+        let expected = expected.to_string();
+        let description = format!("clipboard '{}'", expected);
+        self.wait_for_with_context(
+            move |state| state.clipboard().as_deref() == Some(expected.as_str()),
+            &description,
+        )
     }
 
     /// Waits for the cursor to reach a specific position.
@@ -1241,6 +2074,38 @@ impl TuiTestHarness {
         self.wait_for_with_context(move |state| state.cursor_position() == pos, &description)
     }
 
+    /// Waits for the cursor to reach `(row, col)`.
+    ///
+    /// Like [`wait_for_cursor`](Self::wait_for_cursor), but a timeout reports
+    /// where the cursor was last seen. Useful for synchronizing on focus
+    /// movement in form-driven TUIs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::CursorTimeout`] with the last-seen position if
+    /// the cursor does not get there within the configured timeout, or
+    /// `ProcessExited` if the child process exits first.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::{KeyCode, TuiTestHarness};
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_key(KeyCode::Tab)?;
+    /// harness.wait_for_cursor_at(5, 10)?;
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_cursor_at(&mut self, row: u16, col: u16) -> Result<()> {
+        match self.wait_for_cursor((row, col)) {
+            Err(TermTestError::Timeout { timeout_ms }) => Err(TermTestError::CursorTimeout {
+                expected: (row, col),
+                last_seen: self.state.cursor_position(),
+                timeout_ms,
+            }),
+            other => other,
+        }
+    }
+
     /// Waits for the cursor to reach a specific position with a custom timeout.
     ///
     /// # Arguments
@@ -1265,60 +2130,51 @@ impl TuiTestHarness {
     /// ```
     pub fn wait_for_cursor_timeout(&mut self, pos: (u16, u16), timeout: Duration) -> Result<()> {
         let description = format!("cursor at ({}, {})", pos.0, pos.1);
+        self.wait_until(move |state| state.cursor_position() == pos, &description, timeout)
+    }
 
-        let start = Instant::now();
-        let mut iterations = 0;
-
-        loop {
-            // Update state - this may return ProcessExited
-            match self.update_state() {
-                Ok(()) => {
-                    if self.state.cursor_position() == pos {
-                        return Ok(());
-                    }
-                }
-                Err(TermTestError::ProcessExited) => {
-                    // Process exited - check condition one final time
-                    if self.state.cursor_position() == pos {
-                        return Ok(());
-                    }
+    /// Returns the current screen contents as a string.
+    pub fn screen_contents(&self) -> String {
+        self.state.contents()
+    }
 
-                    let current_state = self.state.debug_contents();
-                    let cursor = self.state.cursor_position();
+    /// Returns the screen's cell grid, indexed as `cells()[row][col]`.
+    ///
+    /// See [`ScreenState::cells`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::TuiTestHarness;
+    /// # let harness = TuiTestHarness::new(80, 24)?;
+    /// let bold = harness
+    ///     .cells()
+    ///     .iter()
+    ///     .flatten()
+    ///     .filter(|cell| cell.bold)
+    ///     .count();
+    /// println!("{bold} bold cells");
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn cells(&self) -> &[Vec<crate::screen::Cell>] {
+        self.state.cells()
+    }
 
-                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
-                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
-                    eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                    eprintln!("Final screen state:\n{}", current_state);
-                    eprintln!("==========================================\n");
-
-                    return Err(TermTestError::ProcessExited);
-                }
-                Err(e) => return Err(e),
-            }
-
-            let elapsed = start.elapsed();
-            if elapsed >= timeout {
-                let current_state = self.state.debug_contents();
-                let cursor = self.state.cursor_position();
-
-                eprintln!("\n=== Timeout waiting for: {} ===", description);
-                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
-                eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                eprintln!("Current screen state:\n{}", current_state);
-                eprintln!("==========================================\n");
-
-                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
-            }
-
-            iterations += 1;
-            std::thread::sleep(self.poll_interval);
-        }
-    }
-
-    /// Returns the current screen contents as a string.
-    pub fn screen_contents(&self) -> String {
-        self.state.contents()
+    /// Counts the non-overlapping occurrences of text on the screen.
+    ///
+    /// Matches do not span row boundaries. See [`ScreenState::find_all`] to
+    /// get the position of each match.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use terminal_testlib::TuiTestHarness;
+    /// # let harness = TuiTestHarness::new(80, 24)?;
+    /// assert!(harness.count_occurrences("ERROR") <= 1);
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn count_occurrences(&self, text: &str) -> usize {
+        self.state.find_all(text).len()
     }
 
     /// Returns the current cursor position as (row, col).
@@ -1402,8 +2258,11 @@ impl TuiTestHarness {
 
     /// Resizes the terminal.
     ///
-    /// Changes the terminal dimensions and resets the screen state.
-    /// This can be useful for testing responsive TUI layouts.
+    /// Changes the PTY dimensions, which sends `SIGWINCH` to the child, and
+    /// resizes the screen in place: content in the overlapping top-left area
+    /// is kept and the cursor is clamped, as with
+    /// [`ScreenState::resize`]. This can be useful for testing responsive TUI
+    /// layouts.
     ///
     /// # Arguments
     ///
@@ -1428,7 +2287,7 @@ impl TuiTestHarness {
     /// ```
     pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
         self.terminal.resize(width, height)?;
-        self.state = ScreenState::new(width, height);
+        self.state.resize(width, height);
         Ok(())
     }
 
@@ -1456,6 +2315,34 @@ impl TuiTestHarness {
         self.terminal.is_running()
     }
 
+    /// Returns the process ID of the spawned child, if one is running.
+    pub fn process_id(&self) -> Option<u32> {
+        self.terminal.process_id()
+    }
+
+    /// Returns the harness to a freshly created state for reuse by a pool.
+    ///
+    /// Kills any leftover child, clears the screen, and drops recorded events,
+    /// captured stderr, timing data and the
+    /// [`mark_start`](Self::mark_start) reference point. Size and
+    /// configuration are kept.
+    pub(crate) fn reset(&mut self) {
+        self.terminal.reset();
+        if let Some(pump) = &self.pump {
+            // The child has been reaped and the PTY emptied, so its remaining
+            // output is already queued
+            pump.drain();
+        }
+        let (width, height) = self.state.size();
+        self.state = ScreenState::new(width, height);
+        self.recording = false;
+        self.recorded_events.clear();
+        self.recording_start = None;
+        self.stderr_capture = None;
+        self.start_mark = None;
+        self.reset_timing();
+    }
+
     /// Waits for the child process to exit.
     ///
     /// Blocks until the spawned process terminates and returns its exit status.
@@ -1489,6 +2376,94 @@ impl TuiTestHarness {
         self.terminal.wait()
     }
 
+    /// Waits for the child process to exit, giving up after `timeout`.
+    ///
+    /// Unlike [`wait_exit`](Self::wait_exit), a hung child cannot block the
+    /// test forever. The process is polled every poll interval. When the
+    /// timeout expires the child's process group is killed, as it would be on
+    /// drop, unless the harness was built with
+    /// [`with_kill_on_drop(false)`](TuiTestHarnessBuilder::with_kill_on_drop).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the process to exit
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Timeout`] if the process is still running
+    /// after `timeout`, or [`TermTestError::NoProcessRunning`] if no process
+    /// was spawned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.send_text("q")?;
+    ///
+    /// let status = harness.wait_exit_timeout(Duration::from_secs(2))?;
+    /// assert!(status.success());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_exit_timeout(&mut self, timeout: Duration) -> Result<ExitStatus> {
+        let start = self.clock.now();
+
+        loop {
+            if let Some(status) = self.try_exit()? {
+                return Ok(status);
+            }
+
+            if self.clock.elapsed_since(start) >= timeout {
+                self.terminal.kill_after_timeout();
+                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
+            }
+
+            self.clock.sleep(self.poll_interval);
+        }
+    }
+
+    /// Checks whether the child process has exited without blocking.
+    ///
+    /// Unlike [`wait_exit`](Self::wait_exit), this returns immediately:
+    /// `Some(status)` if the process has exited, `None` if it is still running.
+    /// This lets a test drain output until the process finishes and then
+    /// collect its exit status cheaply.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::NoProcessRunning`] if no process was spawned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("true"))?;
+    ///
+    /// let status = loop {
+    ///     let _ = harness.update_state();
+    ///     if let Some(status) = harness.try_exit()? {
+    ///         break status;
+    ///     }
+    /// };
+    /// assert!(status.success());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_exit(&mut self) -> Result<Option<ExitStatus>> {
+        self.terminal.try_wait()
+    }
+
     // ========================================================================
     // Memory Profiling
     // ========================================================================
@@ -1667,6 +2642,172 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Asserts that a row reads exactly `expected`, ignoring trailing spaces.
+    ///
+    /// See [`ScreenState::assert_row_eq`] for the comparison rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row position (0-indexed)
+    /// * `expected` - Expected row text
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the row is out of bounds or differs,
+    /// showing both strings and the first differing column.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render a centered menu title ...
+    ///
+    /// harness.assert_row_eq(0, "                                  === Menu ===")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_row_eq(&self, row: u16, expected: &str) -> Result<()> {
+        self.state.assert_row_eq(row, expected)
+    }
+
+    /// Asserts that the whole screen reads exactly `expected`.
+    ///
+    /// See [`ScreenState::assert_screen_eq`] for the comparison rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - Expected screen text, one line per row
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if any row differs, showing each
+    /// differing row and its first differing column.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(20, 4)?;
+    /// // ... render a small dialog ...
+    ///
+    /// harness.assert_screen_eq(
+    ///     "┌──────────┐
+    /// │ Save?    │
+    /// └──────────┘",
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_screen_eq(&self, expected: &str) -> Result<()> {
+        self.state.assert_screen_eq(expected)
+    }
+
+    /// Asserts that a rectangular block of the screen reads exactly `expected`.
+    ///
+    /// See [`ScreenState::assert_block_eq`] for the comparison rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - Block to compare
+    /// * `expected` - Expected block text, one line per row
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if `expected` does not fit in `bounds`
+    /// or any cell differs, listing each differing cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::{regions::RegionBounds, TuiTestHarness};
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render a confirmation dialog ...
+    ///
+    /// harness.assert_block_eq(
+    ///     RegionBounds::new(10, 30, 10, 3),
+    ///     &["+--------+", "| Save?  |", "+--------+"],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ipc")]
+    pub fn assert_block_eq(
+        &self,
+        bounds: crate::regions::RegionBounds,
+        expected: &[&str],
+    ) -> Result<()> {
+        self.state.assert_block_eq(bounds, expected)
+    }
+
+    /// Asserts the underline shape of the cell at (`row`, `col`).
+    ///
+    /// See [`ScreenState::assert_cell_underline_style`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the cell is out of bounds or its
+    /// style differs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::{TuiTestHarness, UnderlineStyle};
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render an editor with a syntax error on row 3 ...
+    ///
+    /// harness.assert_cell_underline_style(3, 10, UnderlineStyle::Curly)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_cell_underline_style(
+        &self,
+        row: u16,
+        col: u16,
+        expected: UnderlineStyle,
+    ) -> Result<()> {
+        self.state.assert_cell_underline_style(row, col, expected)
+    }
+
+    /// Asserts the underline color of the cell at (`row`, `col`).
+    ///
+    /// See [`ScreenState::assert_cell_underline_color`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the cell is out of bounds or its
+    /// color differs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::{TuiTestHarness, UnderlineColor};
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render an editor with a syntax error on row 3 ...
+    ///
+    /// harness.assert_cell_underline_color(3, 10, Some(UnderlineColor::Rgb(255, 0, 0)))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_cell_underline_color(
+        &self,
+        row: u16,
+        col: u16,
+        expected: Option<UnderlineColor>,
+    ) -> Result<()> {
+        self.state.assert_cell_underline_color(row, col, expected)
+    }
+
     /// Asserts that text appears anywhere within a specified rectangular area.
     ///
     /// This searches for the text within the given bounds and succeeds if found
@@ -2190,6 +3331,60 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Asserts that no inline graphics have been rendered.
+    ///
+    /// The negative counterpart to the Sixel position checks, for tests that
+    /// confirm a text-only fallback was honored. Sixel, Kitty and iTerm2
+    /// images all count.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::GraphicsPresent`] if any graphics are present,
+    /// with how many of each protocol and where the first one (in reading
+    /// order) starts.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> terminal_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... run the app with graphics disabled ...
+    ///
+    /// harness.assert_no_graphics()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_no_graphics(&self) -> Result<()> {
+        if !self.state.has_any_graphics() {
+            return Ok(());
+        }
+
+        let sixels = self.state.sixel_regions();
+        let kitty = self.state.kitty_regions();
+        let iterm2 = self.state.iterm2_regions();
+        let (row, col, protocol) = sixels
+            .iter()
+            .map(|r| (r.start_row, r.start_col, "Sixel"))
+            .chain(kitty.iter().map(|r| (r.start_row, r.start_col, "Kitty")))
+            .chain(iterm2.iter().map(|r| (r.start_row, r.start_col, "iTerm2")))
+            .min()
+            .expect("has_any_graphics implies a region");
+
+        Err(TermTestError::GraphicsPresent(format!(
+            "Expected no graphics, found {} ({} Sixel, {} Kitty, {} iTerm2); \
+                first is a {} image at row {}, col {}",
+            sixels.len() + kitty.len() + iterm2.len(),
+            sixels.len(),
+            kitty.len(),
+            iterm2.len(),
+            protocol,
+            row,
+            col
+        )))
+    }
+
     // ========================================================================
     // Golden File Testing (Visual Regression)
     // ========================================================================
@@ -2654,6 +3849,15 @@ impl TuiTestHarness {
         }
     }
 
+    /// Screen dump for wait diagnostics, with a ruler if `TERMINAL_TESTLIB_RULER` is set.
+    fn diagnostic_contents(&self) -> String {
+        if std::env::var_os(RULER_VAR).is_some() {
+            self.state.contents_with_ruler()
+        } else {
+            self.state.debug_contents()
+        }
+    }
+
     /// Records a state change event if recording is active.
     fn record_state_change(&mut self) {
         if self.recording {
@@ -2977,6 +4181,15 @@ pub struct TuiTestHarnessBuilder {
     poll_interval: Duration,
     buffer_size: usize,
     terminal_profile: TerminalProfile,
+    check_command: bool,
+    env: Vec<(String, String)>,
+    cwd: Option<PathBuf>,
+    separate_stderr: bool,
+    background_pump: bool,
+    kill_on_drop: bool,
+    clock: Arc<dyn Clock>,
+    kitty_keyboard: bool,
+    newline: Newline,
 }
 
 impl Default for TuiTestHarnessBuilder {
@@ -2988,6 +4201,15 @@ impl Default for TuiTestHarnessBuilder {
             poll_interval: DEFAULT_POLL_INTERVAL,
             buffer_size: DEFAULT_BUFFER_SIZE,
             terminal_profile: TerminalProfile::default(),
+            check_command: true,
+            env: Vec::new(),
+            cwd: None,
+            separate_stderr: false,
+            background_pump: false,
+            kill_on_drop: true,
+            clock: Arc::new(SystemClock),
+            kitty_keyboard: true,
+            newline: Newline::default(),
         }
     }
 }
@@ -3005,101 +4227,726 @@ impl TuiTestHarnessBuilder {
         self
     }
 
-    /// Sets the timeout for wait operations.
-    ///
-    /// # Arguments
-    ///
-    /// * `timeout` - Timeout duration
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
-        self
+    /// Sets the timeout for wait operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Timeout duration
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the polling interval for wait operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Polling interval
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Sets the buffer size for reading PTY output.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Buffer size in bytes
+    pub fn with_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size;
+        self
+    }
+
+    /// Sets the terminal profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The terminal profile to use
+    pub fn with_terminal_profile(mut self, profile: TerminalProfile) -> Self {
+        self.terminal_profile = profile;
+        self
+    }
+
+    /// Enables or disables the pre-spawn check that the program exists.
+    ///
+    /// Enabled by default. See [`TuiTestHarness::with_command_check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to check the program before spawning
+    pub fn with_command_check(mut self, enabled: bool) -> Self {
+        self.check_command = enabled;
+        self
+    }
+
+    /// Sets an environment variable for spawned processes.
+    ///
+    /// See [`TuiTestHarness::with_env`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Variable name
+    /// * `value` - Variable value
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the working directory for spawned processes.
+    ///
+    /// See [`TuiTestHarness::with_cwd`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Working directory for the child process
+    pub fn with_cwd(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(path.into());
+        self
+    }
+
+    /// Routes the child's stderr to a separate capture instead of the PTY.
+    ///
+    /// See [`TuiTestHarness::with_separate_stderr`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to capture stderr separately
+    #[cfg(unix)]
+    pub fn with_separate_stderr(mut self, enabled: bool) -> Self {
+        self.separate_stderr = enabled;
+        self
+    }
+
+    /// Drains PTY output on a background thread instead of only when polled.
+    ///
+    /// See [`TuiTestHarness::with_background_pump`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to drain output on a background thread
+    pub fn with_background_pump(mut self, enabled: bool) -> Self {
+        self.background_pump = enabled;
+        self
+    }
+
+    /// Sets whether dropping the harness kills the child's process group.
+    ///
+    /// Enabled by default, so a harness dropped mid-test (for example by a
+    /// panic) does not leak the child or anything it started. Disable this when
+    /// a test intentionally leaves a daemon running; see
+    /// [`TestTerminal::with_kill_on_drop`] for how such a process must detach.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to kill the child's process group on drop
+    pub fn with_kill_on_drop(mut self, enabled: bool) -> Self {
+        self.kill_on_drop = enabled;
+        self
+    }
+
+    /// Sets the clock used to measure timeouts and sleep between polls.
+    ///
+    /// See [`TuiTestHarness::with_clock`].
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for wait operations
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Enables or disables Kitty keyboard protocol encoding for key input.
+    ///
+    /// See [`TuiTestHarness::with_kitty_keyboard`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to follow the application's Kitty keyboard flags
+    pub fn with_kitty_keyboard(mut self, enabled: bool) -> Self {
+        self.kitty_keyboard = enabled;
+        self
+    }
+
+    /// Sets the line ending `send_text` writes for `\n`.
+    ///
+    /// See [`TuiTestHarness::with_newline`].
+    ///
+    /// # Arguments
+    ///
+    /// * `newline` - Line ending to send in place of `\n`
+    pub fn with_newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Builds the test harness with the configured settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal creation fails.
+    pub fn build(self) -> Result<TuiTestHarness> {
+        let terminal = TestTerminal::new(self.width, self.height)?
+            .with_buffer_size(self.buffer_size)
+            .with_kill_on_drop(self.kill_on_drop);
+        let state = ScreenState::new(self.width, self.height);
+
+        Ok(TuiTestHarness {
+            terminal,
+            event_delay: Duration::ZERO,
+            state,
+            timeout: self.timeout,
+            poll_interval: self.poll_interval,
+            buffer_size: self.buffer_size,
+            recording: false,
+            recorded_events: Vec::new(),
+            recording_start: None,
+            verbose: false,
+            terminal_profile: self.terminal_profile,
+            timing_recorder: TimingRecorder::new(),
+            latency_profile: LatencyProfile::new(),
+            check_command: self.check_command,
+            env: self.env,
+            cwd: self.cwd,
+            separate_stderr: self.separate_stderr,
+            stderr_capture: None,
+            background_pump: self.background_pump,
+            pump: None,
+            clock: self.clock,
+            kitty_keyboard: self.kitty_keyboard,
+            newline: self.newline,
+            start_mark: None,
+        })
+    }
+}
+
+/// Verifies that the program of a command can be resolved before spawning.
+///
+/// Programs containing a path separator are checked as paths (relative paths are
+/// resolved against the command's working directory); bare names are searched for
+/// on the command's `PATH`. The default shell command (empty argv) is not checked.
+fn check_program_exists(cmd: &CommandBuilder) -> Result<()> {
+    let Some(program) = cmd.get_argv().first() else {
+        return Ok(());
+    };
+
+    let program_path = Path::new(program);
+    let found = if program_path.components().count() > 1 {
+        let resolved = match cmd.get_cwd() {
+            Some(cwd) if program_path.is_relative() => PathBuf::from(cwd).join(program_path),
+            _ => program_path.to_path_buf(),
+        };
+        resolved.is_file()
+    } else {
+        cmd.get_env("PATH")
+            .map(|paths| std::env::split_paths(paths).any(|dir| is_executable(&dir.join(program))))
+            .unwrap_or(false)
+    };
+
+    if found {
+        Ok(())
+    } else {
+        Err(TermTestError::CommandNotFound { program: program.to_string_lossy().into_owned() })
+    }
+}
+
+/// Returns true if `path` is a file that can be executed.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file() || path.with_extension("exe").is_file()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_harness() {
+        let harness = TuiTestHarness::new(80, 24);
+        assert!(harness.is_ok());
+        let harness = harness.unwrap();
+        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
+        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_timeout(Duration::from_secs(10));
+        assert_eq!(harness.timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_with_poll_interval() {
+        let harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(50));
+        assert_eq!(harness.poll_interval, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_spawn_missing_command_fails_fast() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        let result = harness.spawn(CommandBuilder::new("terminal-testlib-no-such-binary"));
+
+        match result {
+            Err(TermTestError::CommandNotFound { program }) => {
+                assert_eq!(program, "terminal-testlib-no-such-binary");
+            }
+            other => panic!("Expected CommandNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_spawn_missing_path_fails_fast() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        let result = harness.spawn(CommandBuilder::new("/nonexistent/dir/app"));
+        assert!(matches!(result, Err(TermTestError::CommandNotFound { .. })));
+    }
+
+    #[test]
+    fn test_builder_without_command_check() {
+        let harness = TuiTestHarness::builder()
+            .with_command_check(false)
+            .build()
+            .unwrap();
+        assert!(!harness.check_command);
+    }
+
+    #[test]
+    fn test_spawn_with_env() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(2))
+            .with_env("MYVAR", "harness-env-value");
+
+        let mut cmd = CommandBuilder::new("printenv");
+        cmd.arg("MYVAR");
+        harness.spawn(cmd)?;
+
+        match harness.wait_for_text("harness-env-value") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("harness-env-value"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn_defaults_term() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("printenv");
+        cmd.arg("TERM");
+        harness.spawn(cmd)?;
+
+        match harness.wait_for_text("xterm-256color") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("xterm-256color"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn_keeps_explicit_term() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("printenv");
+        cmd.arg("TERM");
+        cmd.env("TERM", "dumb");
+        harness.spawn(cmd)?;
+
+        match harness.wait_for_text("dumb") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        let contents = harness.screen_contents();
+        assert!(contents.contains("dumb"));
+        assert!(!contents.contains("xterm-256color"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_spawn_with_cwd() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("cwd-marker.txt");
+        File::create(&marker).unwrap();
+
+        let mut harness = TuiTestHarness::builder()
+            .with_timeout(Duration::from_secs(2))
+            .with_cwd(dir.path())
+            .build()?;
+        harness.spawn(CommandBuilder::new("ls"))?;
+
+        match harness.wait_for_text("cwd-marker.txt") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("cwd-marker.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_exit_still_running() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("5");
+        harness.spawn(cmd)?;
+
+        assert!(harness.try_exit()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_exit_after_exit() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "exit 3"]);
+        harness.spawn(cmd)?;
+
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = harness.try_exit()? {
+                break status;
+            }
+            assert!(start.elapsed() < Duration::from_secs(5), "process did not exit");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(status.exit_code(), 3);
+
+        // Subsequent calls keep reporting the exit status
+        assert!(harness.try_exit()?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_exit_timeout() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_poll_interval(Duration::from_millis(10))
+            .build()?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "exit 4"]);
+        harness.spawn(cmd)?;
+        let status = harness.wait_exit_timeout(Duration::from_secs(5))?;
+        assert_eq!(status.exit_code(), 4);
+
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("10");
+        harness.spawn(cmd)?;
+        let pid = harness.process_id().expect("child is running");
+
+        let start = Instant::now();
+        let result = harness.wait_exit_timeout(Duration::from_millis(200));
+        assert!(matches!(result, Err(TermTestError::Timeout { timeout_ms: 200 })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The hung child was killed and reaped
+        assert!(!harness.is_running());
+        assert_eq!(harness.process_id(), None);
+        #[cfg(target_os = "linux")]
+        assert!(process_gone(pid));
+        #[cfg(not(target_os = "linux"))]
+        let _ = pid;
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_exit_timeout_without_process() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        assert!(matches!(
+            harness.wait_exit_timeout(Duration::from_millis(10)),
+            Err(TermTestError::NoProcessRunning)
+        ));
+    }
+
+    #[test]
+    fn test_try_exit_without_process() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        assert!(matches!(harness.try_exit(), Err(TermTestError::NoProcessRunning)));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_separate_stderr_capture() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_timeout(Duration::from_secs(2))
+            .with_separate_stderr(true)
+            .build()?;
+
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "echo on-screen; echo diagnostics >&2"]);
+        harness.spawn(cmd)?;
+        harness.wait_exit()?;
+        let _ = harness.update_state();
+
+        let contents = harness.screen_contents();
+        assert!(contents.contains("on-screen"));
+        assert!(!contents.contains("diagnostics"));
+
+        assert_eq!(harness.stderr_contents()?.trim(), "diagnostics");
+        // Already drained
+        assert_eq!(harness.stderr_contents()?, "");
+
+        // The wrapper unlinked the capture file once stderr was redirected
+        let capture = harness.stderr_capture.as_ref().unwrap();
+        assert!(!capture.path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stderr_contents_without_capture() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        assert_eq!(harness.stderr_contents()?, "");
+        Ok(())
     }
 
-    /// Sets the polling interval for wait operations.
-    ///
-    /// # Arguments
-    ///
-    /// * `interval` - Polling interval
-    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
-        self.poll_interval = interval;
-        self
+    /// Returns the bytes of every recorded input event, in order.
+    fn recorded_inputs(harness: &TuiTestHarness) -> Vec<Vec<u8>> {
+        harness
+            .recorded_events
+            .iter()
+            .filter_map(|e| match &e.event {
+                RecordedEvent::Input(bytes) => Some(bytes.clone()),
+                _ => None,
+            })
+            .collect()
     }
 
-    /// Sets the buffer size for reading PTY output.
-    ///
-    /// # Arguments
-    ///
-    /// * `size` - Buffer size in bytes
-    pub fn with_buffer_size(mut self, size: usize) -> Self {
-        self.buffer_size = size;
-        self
+    #[test]
+    fn test_type_text_with_delay() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.spawn(CommandBuilder::new("cat"))?;
+        harness.start_recording();
+
+        let start = Instant::now();
+        harness.type_text_with_delay("héy", Duration::from_millis(20))?;
+        assert!(start.elapsed() >= Duration::from_millis(60));
+
+        // One write per character, multi-byte characters kept whole
+        let inputs = recorded_inputs(&harness);
+        assert_eq!(inputs, vec![b"h".to_vec(), "é".as_bytes().to_vec(), b"y".to_vec()]);
+        Ok(())
     }
 
-    /// Sets the terminal profile.
-    ///
-    /// # Arguments
-    ///
-    /// * `profile` - The terminal profile to use
-    pub fn with_terminal_profile(mut self, profile: TerminalProfile) -> Self {
-        self.terminal_profile = profile;
-        self
+    #[test]
+    fn test_assert_text_within_budget() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_background_pump(true)
+            .with_poll_interval(Duration::from_millis(5));
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("fast");
+        harness.spawn(cmd)?;
+        let elapsed = harness.assert_text_within("fast", Duration::from_secs(5))?;
+        assert!(elapsed < Duration::from_secs(5));
+
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_background_pump(true)
+            .with_poll_interval(Duration::from_millis(5));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "sleep 0.5; echo slow"]);
+        harness.spawn(cmd)?;
+        match harness.assert_text_within("slow", Duration::from_millis(100)) {
+            Err(TermTestError::BudgetExceeded { text, budget_ms, elapsed_ms }) => {
+                assert_eq!(text, "slow");
+                assert_eq!(budget_ms, 100);
+                assert!(elapsed_ms >= 500, "elapsed {}ms", elapsed_ms);
+            }
+            other => panic!("Expected BudgetExceeded, got {:?}", other),
+        }
+
+        // Marking a new start makes the budget relative to it: the text
+        // arrives about 1s after spawn but only about 0.2s after the mark
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_background_pump(true)
+            .with_poll_interval(Duration::from_millis(5));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "sleep 1; echo marked; sleep 1"]);
+        harness.spawn(cmd)?;
+        std::thread::sleep(Duration::from_millis(800));
+        harness.mark_start();
+        let elapsed = harness.assert_text_within("marked", Duration::from_millis(800))?;
+        assert!(elapsed < Duration::from_millis(800), "elapsed {:?}", elapsed);
+        Ok(())
     }
 
-    /// Builds the test harness with the configured settings.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if terminal creation fails.
-    pub fn build(self) -> Result<TuiTestHarness> {
-        let terminal = TestTerminal::new(self.width, self.height)?;
-        let state = ScreenState::new(self.width, self.height);
+    #[test]
+    fn test_send_text_newline_translation() -> Result<()> {
+        let cases = [
+            (Newline::Cr, b"ls -l\rq\r".to_vec()),
+            (Newline::Crlf, b"ls -l\r\nq\r\n".to_vec()),
+            (Newline::Lf, b"ls -l\nq\n".to_vec()),
+        ];
+        for (newline, expected) in cases {
+            let mut harness = TuiTestHarness::builder().with_newline(newline).build()?;
+            harness.spawn(CommandBuilder::new("cat"))?;
+            harness.start_recording();
+            harness.send_text("ls -l\nq\n")?;
+
+            let inputs = recorded_inputs(&harness);
+            assert_eq!(inputs, vec![expected], "{:?}", newline);
+        }
 
-        Ok(TuiTestHarness {
-            terminal,
-            event_delay: Duration::ZERO,
-            state,
-            timeout: self.timeout,
-            poll_interval: self.poll_interval,
-            buffer_size: self.buffer_size,
-            recording: false,
-            recorded_events: Vec::new(),
-            recording_start: None,
-            verbose: false,
-            terminal_profile: self.terminal_profile,
-            timing_recorder: TimingRecorder::new(),
-            latency_profile: LatencyProfile::new(),
-        })
+        // Existing carriage returns are left alone
+        assert_eq!(Newline::Cr.apply("a\r\nb"), b"a\r\rb");
+        assert_eq!(Newline::default(), Newline::Cr);
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_terminal_profile_drives_term_and_key_bytes() -> Result<()> {
+        let mut sent = Vec::new();
+        for profile in [TerminalProfile::Linux, TerminalProfile::Xterm] {
+            let mut harness = TuiTestHarness::new(40, 5)?
+                .with_terminal_profile(profile)
+                .with_background_pump(true);
+            let mut cmd = CommandBuilder::new("sh");
+            cmd.args(["-c", "echo \"TERM is $TERM\"; cat >/dev/null"]);
+            harness.spawn(cmd)?;
+            harness.wait_for_text(&format!("TERM is {}", profile.term_name()))?;
+
+            harness.start_recording();
+            harness.send_key(KeyCode::Home)?;
+            let inputs = recorded_inputs(&harness);
+            sent.push(inputs);
+        }
+
+        assert_eq!(sent[0], vec![b"\x1b[1~".to_vec()]);
+        assert_eq!(sent[1], vec![b"\x1b[H".to_vec()]);
+        assert_eq!(TuiTestHarness::new(40, 5)?.terminal_profile().term_name(), "xterm-256color");
+        Ok(())
+    }
 
     #[test]
-    fn test_create_harness() {
-        let harness = TuiTestHarness::new(80, 24);
-        assert!(harness.is_ok());
-        let harness = harness.unwrap();
-        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
-        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
-        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+    fn test_paste_bracketed_only_when_enabled() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.spawn(CommandBuilder::new("cat"))?;
+        harness.start_recording();
+
+        // Mode not enabled: raw text
+        harness.paste("one")?;
+
+        // Application enables bracketed paste
+        harness.state.feed(b"\x1b[?2004h");
+        harness.paste("two")?;
+
+        // Application disables it again
+        harness.state.feed(b"\x1b[?2004l");
+        harness.paste("three")?;
+
+        let inputs = recorded_inputs(&harness);
+        assert_eq!(
+            inputs,
+            vec![
+                b"one".to_vec(),
+                b"\x1b[200~two\x1b[201~".to_vec(),
+                b"three".to_vec(),
+            ]
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_with_timeout() {
-        let harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_timeout(Duration::from_secs(10));
-        assert_eq!(harness.timeout, Duration::from_secs(10));
+    fn test_send_mouse_uses_enabled_protocol() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.spawn(CommandBuilder::new("cat"))?;
+        harness.start_recording();
+
+        let click = MouseEvent::press(10, 5, MouseButton::Left);
+        assert!(matches!(harness.send_mouse(click), Err(TermTestError::MouseReportingDisabled)));
+
+        // Application enables SGR mouse reporting
+        harness.state.feed(b"\x1b[?1000;1006h");
+        harness.send_mouse(click)?;
+        harness.send_mouse(MouseEvent::release(10, 5, MouseButton::Left))?;
+
+        // Falls back to the legacy encoding without ?1006
+        harness.state.feed(b"\x1b[?1006l");
+        harness.send_mouse(click)?;
+
+        let inputs = recorded_inputs(&harness);
+        assert_eq!(
+            inputs,
+            vec![
+                b"\x1b[<0;11;6M".to_vec(),
+                b"\x1b[<0;11;6m".to_vec(),
+                b"\x1b[M +&".to_vec(),
+            ]
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_with_poll_interval() {
-        let harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_poll_interval(Duration::from_millis(50));
-        assert_eq!(harness.poll_interval, Duration::from_millis(50));
+    fn test_wait_for_any_returns_matched_index() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_background_pump(true)
+            .build()?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "sleep 0.2; echo 'build FAILED'; sleep 5"]);
+        harness.spawn(cmd)?;
+
+        let index = harness.wait_for_any(&["build ok", "build FAILED"])?;
+        assert_eq!(index, 1);
+
+        let err = harness
+            .wait_for_any_timeout(&["never", "also never"], Duration::from_millis(100))
+            .unwrap_err();
+        assert!(matches!(err, TermTestError::Timeout { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_send_key_follows_kitty_keyboard_flags() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.spawn(CommandBuilder::new("cat"))?;
+        harness.start_recording();
+
+        harness.send_key_with_modifiers(KeyCode::Char('a'), Modifiers::CTRL | Modifiers::ALT)?;
+
+        // Application pushes the disambiguate flag
+        harness.state.feed(b"\x1b[>1u");
+        harness.send_key_with_modifiers(KeyCode::Char('a'), Modifiers::CTRL | Modifiers::ALT)?;
+        harness.send_key(KeyCode::Esc)?;
+
+        // ...and pops it again
+        harness.state.feed(b"\x1b[<u");
+        harness.send_key(KeyCode::Esc)?;
+
+        let inputs = recorded_inputs(&harness);
+        assert_eq!(
+            inputs,
+            vec![
+                vec![0x01],
+                b"\x1b[97;7u".to_vec(),
+                b"\x1b[27u".to_vec(),
+                b"\x1b".to_vec(),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_kitty_keyboard_can_be_disabled() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_kitty_keyboard(false);
+        harness.spawn(CommandBuilder::new("cat"))?;
+        harness.start_recording();
+
+        harness.state.feed(b"\x1b[>1u");
+        harness.send_key(KeyCode::Esc)?;
+
+        assert_eq!(recorded_inputs(&harness), vec![b"\x1b".to_vec()]);
+        Ok(())
     }
 
     #[test]
@@ -3212,6 +5059,30 @@ mod tests {
         assert!(contents.contains("Test"));
     }
 
+    #[test]
+    fn test_resize_to_zero_width() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        let result = harness.resize(0, 24);
+        assert!(matches!(result, Err(TermTestError::InvalidDimensions { width: 0, height: 24 })));
+
+        // The screen keeps its previous size and remains usable
+        harness.state_mut().feed(b"\x1b[5;5HHello");
+        assert_eq!(harness.state().size(), (80, 24));
+        assert_eq!(harness.count_occurrences("Hello"), 1);
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        assert_eq!(harness.count_occurrences("ERROR"), 0);
+
+        harness.state_mut().feed(b"ERROR: disk full");
+        assert_eq!(harness.count_occurrences("ERROR"), 1);
+
+        harness.state_mut().feed(b"\r\nERROR: retry failed (ERROR)");
+        assert_eq!(harness.count_occurrences("ERROR"), 3);
+    }
+
     #[test]
     fn test_cursor_position_tracking() {
         // Test cursor position tracking
@@ -3247,6 +5118,19 @@ mod tests {
         assert_eq!(harness.state.size(), (100, 30));
     }
 
+    #[test]
+    fn test_resize_preserves_content() -> Result<()> {
+        let mut harness = TuiTestHarness::new(20, 4)?;
+        harness.state_mut().feed(b"Header\r\n\x1b[4;15Hfooter");
+
+        harness.resize(40, 10)?;
+        assert_eq!(harness.state().size(), (40, 10));
+        assert_eq!(harness.state().row_text(0), "Header");
+        assert_eq!(harness.state().row_text(3), "              footer");
+        assert_eq!(harness.cursor_position(), (3, 19));
+        Ok(())
+    }
+
     #[test]
     fn test_is_running_no_process() {
         let mut harness = TuiTestHarness::new(80, 24).unwrap();
@@ -3400,6 +5284,254 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_background_pump_drains_chatty_child() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_background_pump(true);
+        // ~200KB, far more than the PTY buffer holds
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", "yes chatty-output | head -n 10000; echo all-written"]);
+        harness.spawn(cmd)?;
+
+        // Without polling, the child can only finish if something drains the PTY
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while harness.is_running() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(!harness.is_running(), "child blocked on a full PTY buffer");
+
+        assert!(matches!(harness.update_state(), Err(TermTestError::ProcessExited)));
+        assert!(harness.screen_contents().contains("all-written"));
+        Ok(())
+    }
+
+    /// Whether a process has exited; zombies count as exited.
+    #[cfg(target_os = "linux")]
+    fn process_gone(pid: u32) -> bool {
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+            return true;
+        };
+        // The state follows the parenthesised command name
+        stat.rsplit(')')
+            .next()
+            .is_some_and(|rest| rest.trim_start().starts_with('Z'))
+    }
+
+    /// Polls until `path` holds a non-empty line and returns it.
+    #[cfg(target_os = "linux")]
+    fn wait_for_file_line(path: &Path) -> String {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let contents = std::fs::read_to_string(path).unwrap_or_default();
+            if contents.ends_with('\n') {
+                return contents.trim().to_string();
+            }
+            assert!(Instant::now() < deadline, "{} was never written", path.display());
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_drop_kills_process_group() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let pid_file = dir.path().join("background.pid");
+
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sh");
+        let script = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+        cmd.args(["-c", &script]);
+        harness.spawn(cmd)?;
+
+        let child = harness.process_id().expect("child is running");
+        let background: u32 = wait_for_file_line(&pid_file).parse().unwrap();
+        drop(harness);
+
+        // The child is reaped on drop; the orphaned sleep is reaped by init
+        assert!(process_gone(child));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !process_gone(background) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(process_gone(background), "background sleep outlived the harness");
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_drop_after_reap_leaves_process_group_alone() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let pid_file = dir.path().join("background.pid");
+
+        let mut terminal = TestTerminal::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sh");
+        // The sleep stays in the child's group, and ignores the SIGHUP sent
+        // when the child exits
+        let script = format!("trap '' HUP; sleep 30 & echo $! > '{}'", pid_file.display());
+        cmd.args(["-c", &script]);
+        terminal.spawn(cmd)?;
+
+        let background: u32 = wait_for_file_line(&pid_file).parse().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while terminal.try_wait()?.is_none() {
+            assert!(Instant::now() < deadline, "child never exited");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(terminal);
+
+        // The reaped child's group ID may already belong to someone else, so
+        // drop must not signal it
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!process_gone(background));
+
+        unsafe { libc::kill(background as libc::pid_t, libc::SIGKILL) };
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_drop_without_kill_on_drop_leaves_child_running() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ready = dir.path().join("ready");
+
+        let mut harness = TuiTestHarness::builder().with_kill_on_drop(false).build()?;
+        let mut cmd = CommandBuilder::new("sh");
+        // Closing the PTY sends SIGHUP, so a detached process has to ignore it
+        let script = format!("trap '' HUP; echo ready > '{}'; exec sleep 30", ready.display());
+        cmd.args(["-c", &script]);
+        harness.spawn(cmd)?;
+
+        let child = harness.process_id().expect("child is running");
+        wait_for_file_line(&ready);
+        drop(harness);
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!process_gone(child));
+
+        unsafe {
+            libc::kill(child as libc::pid_t, libc::SIGKILL);
+            libc::waitpid(child as libc::pid_t, std::ptr::null_mut(), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_screen_eq() -> Result<()> {
+        let mut harness = TuiTestHarness::new(12, 4)?;
+        harness.state_mut().feed(b"\x1b[2;3H[ OK ]");
+
+        harness.assert_screen_eq("\n  [ OK ]")?;
+        let message = harness
+            .assert_screen_eq("\n  [FAIL]")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Screen mismatch in 1 row(s)"));
+        assert!(message.contains("Row 1 mismatch at column 3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_row_eq() -> Result<()> {
+        let mut harness = TuiTestHarness::new(20, 4)?;
+        harness.state_mut().feed(b"\x1b[2;5H[ OK ]");
+
+        harness.assert_row_eq(1, "    [ OK ]")?;
+        let message = harness
+            .assert_row_eq(1, "    [FAIL]")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Row 1 mismatch at column 5"));
+        assert!(message.contains("Found:    \"    [ OK ]\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_cell_underline_curly_red() -> Result<()> {
+        let mut harness = TuiTestHarness::new(20, 4)?;
+        harness
+            .state_mut()
+            .feed(b"let \x1b[4:3;58:2::255:0:0mfoo\x1b[0m = 1;");
+
+        let red = UnderlineColor::Rgb(255, 0, 0);
+        harness.assert_cell_underline_style(0, 4, UnderlineStyle::Curly)?;
+        harness.assert_cell_underline_color(0, 4, Some(red))?;
+        harness.assert_cell_underline_style(0, 7, UnderlineStyle::None)?;
+
+        let message = harness
+            .assert_cell_underline_color(0, 0, Some(red))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Expected: Some(Rgb(255, 0, 0))"), "{}", message);
+        assert!(message.contains("Found:    None"), "{}", message);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_clipboard() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(200));
+        harness.spawn(CommandBuilder::new("cat"))?;
+
+        assert!(matches!(
+            harness.wait_for_clipboard("yanked"),
+            Err(TermTestError::Timeout { .. })
+        ));
+
+        // "eWFua2Vk" is base64 for "yanked"
+        harness.state_mut().feed(b"\x1b]52;c;eWFua2Vk\x07");
+        harness.wait_for_clipboard("yanked")?;
+        assert!(harness.wait_for_clipboard("yank").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_timeout_overrides_per_call() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(100));
+        harness.spawn(CommandBuilder::new("cat"))?;
+
+        // A short override is reported as its own timeout
+        let result =
+            harness.wait_for_timeout(|state| state.contains("ready"), Duration::from_millis(20));
+        assert!(matches!(result, Err(TermTestError::Timeout { timeout_ms: 20 })));
+
+        // A condition that needs longer than the configured timeout still passes with an override
+        let start = Instant::now();
+        assert!(harness
+            .wait_for(|_| start.elapsed() >= Duration::from_millis(300))
+            .is_err());
+        let start = Instant::now();
+        harness.wait_for_timeout(
+            |_| start.elapsed() >= Duration::from_millis(300),
+            Duration::from_secs(5),
+        )?;
+
+        harness.state_mut().feed(b"ready");
+        harness.wait_for_text_timeout("ready", Duration::from_secs(5))?;
+
+        // Neither call changed the harness-wide timeout
+        assert_eq!(harness.timeout, Duration::from_millis(100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_cursor_at_reports_last_seen() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(200));
+        harness.spawn(CommandBuilder::new("cat"))?;
+
+        harness.state_mut().feed(b"\x1b[3;5H");
+        harness.wait_for_cursor_at(2, 4)?;
+
+        match harness.wait_for_cursor_at(10, 10) {
+            Err(TermTestError::CursorTimeout { expected, last_seen, timeout_ms }) => {
+                assert_eq!(expected, (10, 10));
+                assert_eq!(last_seen, (2, 4));
+                assert_eq!(timeout_ms, 200);
+            }
+            other => panic!("Expected CursorTimeout, got {:?}", other),
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_wait_for_cursor_with_custom_timeout() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
@@ -3800,6 +5932,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_assert_no_graphics() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.state_mut().feed(b"text-only fallback");
+        harness.assert_no_graphics()?;
+
+        harness.state_mut().feed(b"\x1b[10;20H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+        harness.state_mut().feed(b"\x1b[3;5H");
+        harness.state_mut().feed(b"\x1b_Ga=T,s=10,v=10;AAAA\x1b\\");
+
+        let err = harness.assert_no_graphics().unwrap_err().to_string();
+        assert!(err.contains("found 2 (1 Sixel, 1 Kitty, 0 iTerm2)"), "{}", err);
+        assert!(err.contains("Kitty image at row 2, col 4"), "{}", err);
+        Ok(())
+    }
+
     #[cfg(feature = "sixel")]
     #[test]
     fn test_empty_sixel_regions() -> Result<()> {
@@ -4362,6 +6511,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_wait_for_times_out_on_fake_clock() -> Result<()> {
+        let clock = crate::FakeClock::new();
+        let mut harness = TuiTestHarness::builder()
+            .with_size(40, 5)
+            .with_timeout(Duration::from_secs(600))
+            .with_poll_interval(Duration::from_secs(10))
+            // Polling the pump does not block on the PTY
+            .with_background_pump(true)
+            .with_clock(clock.clone())
+            .build()?;
+        harness.spawn(CommandBuilder::new("cat"))?;
+
+        let real = Instant::now();
+        let result = harness.wait_for(|state| state.contains("never printed"));
+
+        assert!(matches!(result, Err(TermTestError::Timeout { timeout_ms: 600_000 })));
+        assert!(clock.elapsed() >= Duration::from_secs(600));
+        assert!(real.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+
     #[test]
     fn test_press_key_repeat() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
@@ -4438,4 +6609,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_send_key_without_encoding() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        match harness.send_key(KeyCode::F(0)) {
+            Err(TermTestError::UnsupportedKey(key)) => assert_eq!(key, "F(0)"),
+            other => panic!("Expected UnsupportedKey, got {:?}", other),
+        }
+        assert_eq!(harness.timing_recorder.event_count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reset_clears_timing() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.send_key(KeyCode::Char('a'))?;
+        harness.mark_start();
+        assert!(harness.timing_recorder.event_count() > 0);
+        assert!(harness.latency_profile.input_to_render().is_some());
+
+        harness.reset();
+
+        assert_eq!(harness.start_mark, None);
+        assert_eq!(harness.timing_recorder.event_count(), 0);
+        assert_eq!(harness.latency_profile.input_to_render(), None);
+        Ok(())
+    }
 }
@@ -87,6 +87,8 @@
 //! - [`DaemonTestHarness`]: Main test harness combining IPC + shared memory
 //! - [`DaemonIpcClient`]: Unix socket client for sending control messages
 //! - [`DaemonSharedMemory`]: Shared memory reader for terminal state
+//! - [`MockSharedMemory`]: In-memory stand-in for shared memory in unit tests
+//! - [`ShmSnapshot`] / [`CellDiff`]: Capture the grid and diff it after an update
 //! - [`DaemonTestExt`]: Extension trait for TuiTestHarness integration
 //! - [`ControlMessage`]: IPC message types for daemon communication
 //!
@@ -194,6 +196,7 @@
 //! ```
 
 use std::{
+    fmt,
     io::Write,
     os::unix::net::UnixStream,
     path::{Path, PathBuf},
@@ -202,11 +205,19 @@ use std::{
 
 use thiserror::Error;
 
+use crate::{
+    clock::{Clock, SystemClock},
+    regions::RegionBounds,
+};
+
 // Default paths - can be overridden via config
 const DEFAULT_SOCKET_PATH: &str = "/tmp/term-daemon.sock";
 const DEFAULT_SHM_PATH: &str = "/term_shm_v1";
 const DEFAULT_DAEMON_COMMAND: &str = "term-daemon";
 
+/// How often [`DaemonSharedMemory::wait_for_update`] checks the sequence number.
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
 /// Cell attribute structure for colors and styling.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -271,10 +282,105 @@ pub enum IpcError {
     #[error("Timeout after {0:?} waiting for condition")]
     Timeout(Duration),
 
+    /// Timeout waiting for the cursor to reach a position.
+    #[error(
+        "Timeout after {timeout:?} waiting for cursor at {expected:?}, last seen at {last_seen:?}"
+    )]
+    CursorTimeout {
+        /// Position waited for as (row, col).
+        expected: (u16, u16),
+        /// Cursor position at the final check as (row, col).
+        last_seen: (u16, u16),
+        /// How long the wait lasted.
+        timeout: Duration,
+    },
+
+    /// Timeout waiting for one step of a text sequence.
+    #[error(
+        "Timeout after {timeout:?} waiting for step {step} ('{text}'); {step} of {total} steps succeeded"
+    )]
+    SequenceTimeout {
+        /// 0-based index of the step that timed out.
+        step: usize,
+        /// Text the step was waiting for.
+        text: String,
+        /// Number of steps in the sequence.
+        total: usize,
+        /// Timeout that expired.
+        timeout: Duration,
+    },
+
+    /// Timeout waiting for a region's contents.
+    #[error("Timeout after {timeout:?} waiting for region '{region}'; last seen:\n{last_seen}")]
+    RegionTimeout {
+        /// Name of the region waited on.
+        region: String,
+        /// Region text at the final check.
+        last_seen: String,
+        /// Timeout that expired.
+        timeout: Duration,
+    },
+
+    /// The terminal took a new size but no frame followed it.
+    ///
+    /// The app did not redraw, so it most likely never handled `SIGWINCH`.
+    #[error("Terminal resized to {cols}x{rows}, but the app did not redraw within {timeout:?}")]
+    ResizeNotRedrawn {
+        /// Requested width.
+        cols: u16,
+        /// Requested height.
+        rows: u16,
+        /// Timeout that expired.
+        timeout: Duration,
+    },
+
     /// Invalid shared memory format or data.
     #[error("Invalid shared memory data: {0}")]
     InvalidData(String),
 
+    /// A cell position lies outside the grid.
+    #[error("Invalid shared memory data: Position ({row}, {col}) out of bounds ({rows}x{cols})")]
+    CellOutOfBounds {
+        /// Requested row.
+        row: u16,
+        /// Requested column.
+        col: u16,
+        /// Number of rows in the grid.
+        rows: u16,
+        /// Number of columns in the grid.
+        cols: u16,
+    },
+
+    /// A cell's color differs from the expected RGBA value.
+    #[error(
+        "Invalid shared memory data: Cell ({row}, {col}) {layer} color mismatch: expected 0x{expected:08X}, got 0x{actual:08X}"
+    )]
+    ColorMismatch {
+        /// Row of the cell.
+        row: u16,
+        /// Column of the cell.
+        col: u16,
+        /// Whether the foreground or background was checked.
+        layer: ColorLayer,
+        /// Expected color as `0xRRGGBBAA`.
+        expected: u32,
+        /// Actual color as `0xRRGGBBAA`.
+        actual: u32,
+    },
+
+    /// No region is registered under the given name.
+    #[error("Invalid shared memory data: Region '{0}' not found")]
+    RegionNotFound(String),
+
+    /// A command finished with a different exit code than expected.
+    #[error("Command finished with {}, expected exit code {expected}", exit_code_label(*.actual))]
+    ExitCodeMismatch {
+        /// Exit code the command was expected to finish with.
+        expected: i32,
+        /// Exit code from the D marker, or `None` if the shell did not report one.
+        actual: Option<i32>,
+    },
+
     /// Daemon process failed to spawn.
     #[error("Failed to spawn daemon: {0}")]
     SpawnFailed(#[source] std::io::Error),
@@ -288,9 +394,35 @@ pub enum IpcError {
     TestingDisabled,
 }
 
+/// Describe an OSC 133 exit code for [`IpcError::ExitCodeMismatch`].
+fn exit_code_label(code: Option<i32>) -> String {
+    match code {
+        Some(code) => format!("exit code {}", code),
+        None => "no exit code".to_string(),
+    }
+}
+
 /// Result type for IPC operations.
 pub type IpcResult<T> = std::result::Result<T, IpcError>;
 
+/// Which color of a cell an [`IpcError::ColorMismatch`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorLayer {
+    /// Text (foreground) color.
+    Foreground,
+    /// Cell background color.
+    Background,
+}
+
+impl fmt::Display for ColorLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorLayer::Foreground => write!(f, "foreground"),
+            ColorLayer::Background => write!(f, "background"),
+        }
+    }
+}
+
 /// Control messages sent to the terminal daemon via IPC.
 ///
 /// These messages provide a generic interface for controlling terminal
@@ -561,6 +693,19 @@ impl DaemonIpcClient {
 /// 28      4       attrs_offset
 /// 32      4       attrs_size
 /// ```
+///
+/// The layout describes the visible grid only. It has no scrollback offset
+/// or size, so lines that have scrolled off the screen can't be read through
+/// shared memory; assert on them before they scroll, or resize the terminal
+/// so they stay visible.
+///
+/// There are no selection fields either. A mouse selection made in the daemon
+/// isn't visible through this header; check the highlighted cells' attributes
+/// with [`DaemonSharedMemory::cells_in_range`] instead.
+///
+/// Only the cursor's position is shared. Its shape and blink state aren't
+/// part of the layout, so they can't be asserted from a split-architecture
+/// test.
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct ShmHeader {
@@ -661,49 +806,11 @@ impl DaemonSharedMemory {
         expected_magic: u32,
         expected_version: u32,
     ) -> IpcResult<Self> {
-        use std::ffi::CString;
-
-        let path_cstr = CString::new(shm_path)
-            .map_err(|_| IpcError::InvalidData("Invalid shm path".to_string()))?;
-
-        // Open the shared memory object
-        let fd = unsafe { libc::shm_open(path_cstr.as_ptr(), libc::O_RDONLY, 0o644) };
-
-        if fd < 0 {
-            return Err(IpcError::SharedMemoryNotFound(shm_path.to_string()));
-        }
-
-        // Get the size
-        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
-        if unsafe { libc::fstat(fd, &mut stat) } < 0 {
-            unsafe { libc::close(fd) };
-            return Err(IpcError::MmapFailed("fstat failed".to_string()));
-        }
-
-        let size = stat.st_size as usize;
-        if size < std::mem::size_of::<ShmHeader>() {
-            unsafe { libc::close(fd) };
-            return Err(IpcError::InvalidData(
-                "Shared memory too small for header".to_string(),
-            ));
-        }
-
-        // Map the memory
-        let mmap = unsafe {
-            libc::mmap(
-                std::ptr::null_mut(),
-                size,
-                libc::PROT_READ,
-                libc::MAP_SHARED,
-                fd,
-                0,
-            )
-        };
-
-        if mmap == libc::MAP_FAILED {
-            unsafe { libc::close(fd) };
-            return Err(IpcError::MmapFailed("mmap failed".to_string()));
-        }
+        let (fd, mmap, size) = map_shm(
+            shm_path,
+            std::mem::size_of::<ShmHeader>(),
+            "Shared memory too small for header",
+        )?;
 
         // Read and validate header
         let header: ShmHeader = unsafe { std::ptr::read(mmap as *const ShmHeader) };
@@ -711,7 +818,7 @@ impl DaemonSharedMemory {
 
         Ok(Self {
             shm_fd: fd,
-            mmap: mmap as *const u8,
+            mmap,
             size,
             header,
             expected_magic,
@@ -758,6 +865,35 @@ impl DaemonSharedMemory {
         }
     }
 
+    /// Block until the writer bumps the sequence number, then refresh.
+    ///
+    /// Compares against the sequence number from the last
+    /// [`refresh`](Self::refresh), so an update that landed since then
+    /// returns immediately. The header protocol has no futex or eventfd to
+    /// wait on, so this watches the mapped sequence number at a 1ms interval,
+    /// which wakes far sooner than a typical frame-polling loop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::Timeout`] if no update arrives in time, or a
+    /// validation error from the refreshed header.
+    pub fn wait_for_update(&mut self, timeout: Duration) -> IpcResult<()> {
+        let start = Instant::now();
+        let initial_seq = self.header.sequence_number;
+
+        loop {
+            if self.raw_sequence_number() != initial_seq {
+                return self.refresh();
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(IpcError::Timeout(timeout));
+            }
+
+            std::thread::sleep(UPDATE_POLL_INTERVAL);
+        }
+    }
+
     /// Read the terminal grid as a string.
     ///
     /// Returns the grid content with newlines between rows.
@@ -801,10 +937,12 @@ impl DaemonSharedMemory {
     #[allow(unsafe_code)]
     pub fn cell_at(&self, row: u16, col: u16) -> IpcResult<char> {
         if row >= self.header.rows || col >= self.header.cols {
-            return Err(IpcError::InvalidData(format!(
-                "Position ({}, {}) out of bounds ({}x{})",
-                row, col, self.header.rows, self.header.cols
-            )));
+            return Err(IpcError::CellOutOfBounds {
+                row,
+                col,
+                rows: self.header.rows,
+                cols: self.header.cols,
+            });
         }
 
         let offset = self.header.grid_offset as usize;
@@ -826,10 +964,12 @@ impl DaemonSharedMemory {
     #[allow(unsafe_code)]
     pub fn cell_attrs_at(&self, row: u16, col: u16) -> IpcResult<CellAttributes> {
         if row >= self.header.rows || col >= self.header.cols {
-            return Err(IpcError::InvalidData(format!(
-                "Position ({}, {}) out of bounds ({}x{})",
-                row, col, self.header.rows, self.header.cols
-            )));
+            return Err(IpcError::CellOutOfBounds {
+                row,
+                col,
+                rows: self.header.rows,
+                cols: self.header.cols,
+            });
         }
 
         if self.header.attrs_offset == 0 || self.header.attrs_size == 0 {
@@ -880,6 +1020,74 @@ impl DaemonSharedMemory {
 
         Ok(attrs)
     }
+
+    /// Read the characters and attributes of every cell in a rectangle.
+    ///
+    /// Cells are returned in row-major order. The header is read once and
+    /// each cell straight from the mapped buffer, which is much cheaper than
+    /// calling [`cell_at`](Self::cell_at) and
+    /// [`cell_attrs_at`](Self::cell_attrs_at) per cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rectangle extends past the grid or the mapped
+    /// buffer.
+    pub fn cells_in_range(&self, bounds: RegionBounds) -> IpcResult<Vec<(char, CellAttributes)>> {
+        CellGrid::cells_in_range(self, bounds)
+    }
+
+    /// Capture the current grid and sequence number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid can't be read.
+    pub fn snapshot(&self) -> IpcResult<ShmSnapshot> {
+        CellGrid::snapshot(self)
+    }
+
+    /// List the cells that changed since `previous` was taken.
+    ///
+    /// Short-circuits to an empty list when the sequence number hasn't moved.
+    /// Call [`refresh`](Self::refresh) first so the sequence number is current.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid can't be read.
+    pub fn diff_since(&self, previous: &ShmSnapshot) -> IpcResult<Vec<CellDiff>> {
+        CellGrid::diff_since(self, previous)
+    }
+}
+
+#[cfg(target_family = "unix")]
+impl CellGrid for DaemonSharedMemory {
+    fn grid_dimensions(&self) -> (u16, u16) {
+        self.dimensions()
+    }
+
+    fn grid_sequence_number(&self) -> u32 {
+        self.sequence_number()
+    }
+
+    #[allow(unsafe_code)]
+    fn cell(&self, index: usize) -> IpcResult<(char, CellAttributes)> {
+        let grid_offset = self.header.grid_offset as usize;
+        if grid_offset + index >= self.size {
+            return Err(IpcError::InvalidData("Grid extends beyond shared memory".to_string()));
+        }
+        let byte = unsafe { *self.mmap.add(grid_offset + index) };
+
+        if self.header.attrs_offset == 0 || self.header.attrs_size == 0 {
+            return Ok((byte as char, CellAttributes::default()));
+        }
+
+        let attr_size = std::mem::size_of::<CellAttributes>();
+        let offset = self.header.attrs_offset as usize + index * attr_size;
+        if offset + attr_size > self.size {
+            return Err(IpcError::InvalidData("Cell attributes index out of bounds".to_string()));
+        }
+        let ptr = unsafe { self.mmap.add(offset) as *const CellAttributes };
+        Ok((byte as char, unsafe { std::ptr::read_unaligned(ptr) }))
+    }
 }
 
 #[cfg(target_family = "unix")]
@@ -898,6 +1106,658 @@ impl Drop for DaemonSharedMemory {
 #[allow(unsafe_code)]
 unsafe impl Send for DaemonSharedMemory {}
 
+/// Open a shared memory object read-only and map all of it.
+///
+/// Returns the descriptor, the mapping and its size. Objects smaller than
+/// `min_size` are rejected with `too_small` as the message.
+#[cfg(target_family = "unix")]
+#[allow(unsafe_code)]
+fn map_shm(
+    shm_path: &str,
+    min_size: usize,
+    too_small: &str,
+) -> IpcResult<(std::os::fd::RawFd, *const u8, usize)> {
+    use std::ffi::CString;
+
+    let path_cstr = CString::new(shm_path)
+        .map_err(|_| IpcError::InvalidData("Invalid shm path".to_string()))?;
+
+    // Open the shared memory object
+    let fd = unsafe { libc::shm_open(path_cstr.as_ptr(), libc::O_RDONLY, 0o644) };
+
+    if fd < 0 {
+        return Err(IpcError::SharedMemoryNotFound(shm_path.to_string()));
+    }
+
+    // Get the size
+    let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+    if unsafe { libc::fstat(fd, &mut stat) } < 0 {
+        unsafe { libc::close(fd) };
+        return Err(IpcError::MmapFailed("fstat failed".to_string()));
+    }
+
+    let size = stat.st_size as usize;
+    if size < min_size {
+        unsafe { libc::close(fd) };
+        return Err(IpcError::InvalidData(too_small.to_string()));
+    }
+
+    // Map the memory
+    let mmap =
+        unsafe { libc::mmap(std::ptr::null_mut(), size, libc::PROT_READ, libc::MAP_SHARED, fd, 0) };
+
+    if mmap == libc::MAP_FAILED {
+        unsafe { libc::close(fd) };
+        return Err(IpcError::MmapFailed("mmap failed".to_string()));
+    }
+
+    Ok((fd, mmap as *const u8, size))
+}
+
+/// Reader for a daemon's raw output log.
+///
+/// The grid segment read by [`DaemonSharedMemory`] only holds rendered
+/// cells, so escape sequences such as OSC 133 zone markers are gone by the
+/// time a test looks. A daemon can publish the bytes its PTY produced in a
+/// second segment, a ring buffer of the most recent output:
+///
+/// ```text
+/// Offset  Size      Field
+/// 0       4         magic
+/// 4       4         version
+/// 8       8         bytes_written (total since start, never wraps)
+/// 16      4         capacity
+/// 20      4         reserved
+/// 24      capacity  ring data
+/// ```
+///
+/// Byte `n` of the stream is stored at `24 + n % capacity` until it is
+/// overwritten. The writer copies the bytes in before advancing
+/// `bytes_written`.
+#[cfg(target_family = "unix")]
+pub struct DaemonOutputLog {
+    shm_fd: std::os::fd::RawFd,
+    mmap: *const u8,
+    size: usize,
+    capacity: usize,
+}
+
+#[cfg(target_family = "unix")]
+impl std::fmt::Debug for DaemonOutputLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DaemonOutputLog")
+            .field("size", &self.size)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+#[cfg(target_family = "unix")]
+impl DaemonOutputLog {
+    /// Size of the fixed header before the ring data.
+    pub const HEADER_SIZE: usize = 24;
+
+    /// Open and map an output log with default validation.
+    pub fn open(shm_path: &str) -> IpcResult<Self> {
+        Self::open_with_validation(shm_path, ShmHeader::DEFAULT_MAGIC, ShmHeader::DEFAULT_VERSION)
+    }
+
+    /// Open and map an output log with custom magic/version validation.
+    #[allow(unsafe_code)]
+    pub fn open_with_validation(
+        shm_path: &str,
+        expected_magic: u32,
+        expected_version: u32,
+    ) -> IpcResult<Self> {
+        let (fd, mmap, size) =
+            map_shm(shm_path, Self::HEADER_SIZE, "Shared memory too small for output log header")?;
+        // Unmapped by Drop if validation fails
+        let mut log = Self { shm_fd: fd, mmap, size, capacity: 0 };
+
+        let (magic, version, capacity) = unsafe {
+            (
+                std::ptr::read(mmap as *const u32),
+                std::ptr::read(mmap.add(4) as *const u32),
+                std::ptr::read(mmap.add(16) as *const u32) as usize,
+            )
+        };
+        if magic != expected_magic {
+            return Err(IpcError::InvalidData(format!(
+                "Invalid magic: expected 0x{:08X}, got 0x{:08X}",
+                expected_magic, magic
+            )));
+        }
+        if version != expected_version {
+            return Err(IpcError::InvalidData(format!(
+                "Unsupported version: expected {}, got {}",
+                expected_version, version
+            )));
+        }
+        if capacity == 0 || Self::HEADER_SIZE + capacity > size {
+            return Err(IpcError::InvalidData(format!(
+                "Output log capacity {} does not fit in a {} byte segment",
+                capacity, size
+            )));
+        }
+
+        log.capacity = capacity;
+        Ok(log)
+    }
+
+    /// Get the ring buffer capacity in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the total number of bytes the daemon has written.
+    #[allow(unsafe_code)]
+    pub fn bytes_written(&self) -> u64 {
+        unsafe { std::ptr::read_volatile(self.mmap.add(8) as *const u64) }
+    }
+
+    /// Read the output still held in the ring, oldest byte first.
+    ///
+    /// Once more than [`capacity`](Self::capacity) bytes have been written
+    /// only the most recent ones remain. Bytes the writer overwrote while
+    /// they were being copied are dropped from the front, so the result is
+    /// always a contiguous tail of the stream.
+    #[allow(unsafe_code)]
+    pub fn read(&self) -> IpcResult<Vec<u8>> {
+        let end = self.bytes_written();
+        let start = end.saturating_sub(self.capacity as u64);
+        let ring = unsafe { self.mmap.add(Self::HEADER_SIZE) };
+
+        let mut bytes = Vec::with_capacity((end - start) as usize);
+        for n in start..end {
+            let offset = (n % self.capacity as u64) as usize;
+            bytes.push(unsafe { std::ptr::read_volatile(ring.add(offset)) });
+        }
+
+        // Anything before `written - capacity` may have been overwritten mid-copy
+        let written = self.bytes_written();
+        let torn = written
+            .saturating_sub(self.capacity as u64)
+            .saturating_sub(start);
+        bytes.drain(..(torn as usize).min(bytes.len()));
+        Ok(bytes)
+    }
+}
+
+#[cfg(target_family = "unix")]
+impl Drop for DaemonOutputLog {
+    #[allow(unsafe_code)]
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.mmap as *mut libc::c_void, self.size);
+            libc::close(self.shm_fd);
+        }
+    }
+}
+
+// SAFETY: The log is mapped read-only; torn reads are detected via bytes_written
+#[cfg(target_family = "unix")]
+#[allow(unsafe_code)]
+unsafe impl Send for DaemonOutputLog {}
+
+/// Cell reads shared by [`DaemonSharedMemory`] and [`MockSharedMemory`].
+///
+/// Implementors provide the grid size, the sequence number and one cell by
+/// row-major index; rectangle reads, snapshots and diffs are built on those.
+trait CellGrid {
+    /// Grid dimensions (cols, rows).
+    fn grid_dimensions(&self) -> (u16, u16);
+
+    /// Sequence number of the current frame.
+    fn grid_sequence_number(&self) -> u32;
+
+    /// Read the cell at a row-major index inside the grid.
+    fn cell(&self, index: usize) -> IpcResult<(char, CellAttributes)>;
+
+    /// Read every cell in a rectangle, in row-major order.
+    fn cells_in_range(&self, bounds: RegionBounds) -> IpcResult<Vec<(char, CellAttributes)>> {
+        let (cols, rows) = self.grid_dimensions();
+        if bounds.row as usize + bounds.height as usize > rows as usize
+            || bounds.col as usize + bounds.width as usize > cols as usize
+        {
+            return Err(IpcError::InvalidData(format!(
+                "Range ({}, {}) {}x{} out of bounds ({}x{})",
+                bounds.row, bounds.col, bounds.width, bounds.height, rows, cols
+            )));
+        }
+
+        let cols = cols as usize;
+        let mut cells = Vec::with_capacity(bounds.width as usize * bounds.height as usize);
+        for row in bounds.row as usize..(bounds.row + bounds.height) as usize {
+            let start = row * cols + bounds.col as usize;
+            for index in start..start + bounds.width as usize {
+                cells.push(self.cell(index)?);
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Capture the whole grid with its sequence number.
+    fn snapshot(&self) -> IpcResult<ShmSnapshot> {
+        let (cols, rows) = self.grid_dimensions();
+        Ok(ShmSnapshot {
+            sequence_number: self.grid_sequence_number(),
+            cols,
+            rows,
+            cells: self.cells_in_range(RegionBounds::new(0, 0, cols, rows))?,
+        })
+    }
+
+    /// Cells changed since `previous`, skipping the read if the sequence
+    /// number hasn't moved.
+    fn diff_since(&self, previous: &ShmSnapshot) -> IpcResult<Vec<CellDiff>> {
+        if self.grid_sequence_number() == previous.sequence_number {
+            return Ok(Vec::new());
+        }
+        Ok(previous.diff(&self.snapshot()?))
+    }
+}
+
+/// Point-in-time copy of the grid, taken with
+/// [`DaemonSharedMemory::snapshot`].
+///
+/// Pass it to [`DaemonSharedMemory::diff_since`] after an update to find which
+/// cells changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShmSnapshot {
+    sequence_number: u32,
+    cols: u16,
+    rows: u16,
+    cells: Vec<(char, CellAttributes)>,
+}
+
+impl ShmSnapshot {
+    /// Sequence number at the time of the snapshot.
+    pub fn sequence_number(&self) -> u32 {
+        self.sequence_number
+    }
+
+    /// Grid dimensions (cols, rows) at the time of the snapshot.
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.cols, self.rows)
+    }
+
+    /// Cells that differ between this snapshot and a newer one.
+    ///
+    /// Returns an empty list without comparing cells when both snapshots
+    /// carry the same sequence number. If the grid was resized, cells that
+    /// exist on only one side are compared against a blank cell.
+    pub fn diff(&self, newer: &ShmSnapshot) -> Vec<CellDiff> {
+        if self.sequence_number == newer.sequence_number {
+            return Vec::new();
+        }
+
+        let rows = self.rows.max(newer.rows);
+        let cols = self.cols.max(newer.cols);
+        let mut diffs = Vec::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                let (old_char, old_attrs) = self.cell(row, col);
+                let (new_char, new_attrs) = newer.cell(row, col);
+                if old_char != new_char || old_attrs != new_attrs {
+                    diffs.push(CellDiff {
+                        row,
+                        col,
+                        old_char,
+                        new_char,
+                        old_attrs,
+                        new_attrs,
+                    });
+                }
+            }
+        }
+        diffs
+    }
+
+    fn cell(&self, row: u16, col: u16) -> (char, CellAttributes) {
+        if row < self.rows && col < self.cols {
+            self.cells[row as usize * self.cols as usize + col as usize]
+        } else {
+            (' ', CellAttributes::default())
+        }
+    }
+}
+
+/// A cell that changed between two [`ShmSnapshot`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff {
+    /// Row of the cell (0-indexed).
+    pub row: u16,
+    /// Column of the cell (0-indexed).
+    pub col: u16,
+    /// Character before the change.
+    pub old_char: char,
+    /// Character after the change.
+    pub new_char: char,
+    /// Attributes before the change.
+    pub old_attrs: CellAttributes,
+    /// Attributes after the change.
+    pub new_attrs: CellAttributes,
+}
+
+/// In-memory stand-in for [`DaemonSharedMemory`].
+///
+/// Exposes the same read surface from a test-provided grid and attribute
+/// buffer, so harness code and extension traits can be exercised without a
+/// running daemon. Mutating methods bump the sequence number, mimicking a
+/// daemon publishing a new frame.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "ipc")]
+/// # {
+/// use terminal_testlib::ipc::{CellAttributes, MockSharedMemory};
+///
+/// let mut shm = MockSharedMemory::new(20, 3).with_grid("hello\nworld");
+/// assert_eq!(shm.cell_at(1, 0).unwrap(), 'w');
+///
+/// let red = CellAttributes { fg: 0xFF0000FF, ..Default::default() };
+/// shm.set_cell_attrs(0, 0, red);
+/// assert_eq!(shm.cell_attrs_at(0, 0).unwrap().fg, 0xFF0000FF);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockSharedMemory {
+    cols: u16,
+    rows: u16,
+    cells: Vec<char>,
+    attrs: Vec<CellAttributes>,
+    cursor: (u16, u16),
+    sequence_number: u32,
+    refreshed_sequence: u32,
+    /// Raw bytes the mocked terminal has received, including escape sequences.
+    raw_output: Vec<u8>,
+    /// Whether [`resize`](Self::resize) publishes a new frame.
+    redraw_on_resize: bool,
+}
+
+impl MockSharedMemory {
+    /// Create a blank grid of the given size with default attributes.
+    pub fn new(cols: u16, rows: u16) -> Self {
+        let len = cols as usize * rows as usize;
+        Self {
+            cols,
+            rows,
+            cells: vec![' '; len],
+            attrs: vec![CellAttributes::default(); len],
+            cursor: (0, 0),
+            sequence_number: 0,
+            refreshed_sequence: 0,
+            raw_output: Vec::new(),
+            redraw_on_resize: false,
+        }
+    }
+
+    /// Set the grid text, one line per row.
+    ///
+    /// Lines are truncated to the grid width and missing cells are blank.
+    pub fn with_grid(mut self, text: &str) -> Self {
+        self.set_grid(text);
+        self
+    }
+
+    /// Set the attribute buffer in row-major order.
+    ///
+    /// Missing entries are filled with default attributes and extra entries
+    /// are ignored.
+    pub fn with_attrs(mut self, attrs: Vec<CellAttributes>) -> Self {
+        self.set_attrs(attrs);
+        self
+    }
+
+    /// Seed the raw output stream, e.g. with OSC 133 shell integration markers.
+    pub fn with_raw_output(mut self, bytes: &[u8]) -> Self {
+        self.push_raw_output(bytes);
+        self
+    }
+
+    /// Set the cursor position (row, col).
+    pub fn with_cursor(mut self, row: u16, col: u16) -> Self {
+        self.set_cursor(row, col);
+        self
+    }
+
+    /// Make [`resize`](Self::resize) bump the sequence number, standing in
+    /// for an app that redraws on `SIGWINCH`.
+    pub fn with_redraw_on_resize(mut self, redraw: bool) -> Self {
+        self.redraw_on_resize = redraw;
+        self
+    }
+
+    /// Replace the grid text, one line per row.
+    pub fn set_grid(&mut self, text: &str) {
+        self.cells.fill(' ');
+        let cols = self.cols as usize;
+        for (row, line) in text.lines().take(self.rows as usize).enumerate() {
+            for (col, c) in line.chars().take(cols).enumerate() {
+                self.cells[row * cols + col] = c;
+            }
+        }
+        self.bump_sequence();
+    }
+
+    /// Replace the attribute buffer in row-major order.
+    pub fn set_attrs(&mut self, mut attrs: Vec<CellAttributes>) {
+        attrs.resize(self.cells.len(), CellAttributes::default());
+        self.attrs = attrs;
+        self.bump_sequence();
+    }
+
+    /// Set the attributes of a single cell.
+    ///
+    /// Positions outside the grid are ignored.
+    pub fn set_cell_attrs(&mut self, row: u16, col: u16, attrs: CellAttributes) {
+        if let Some(index) = self.index(row, col) {
+            self.attrs[index] = attrs;
+            self.bump_sequence();
+        }
+    }
+
+    /// Move the cursor.
+    pub fn set_cursor(&mut self, row: u16, col: u16) {
+        self.cursor = (row, col);
+        self.bump_sequence();
+    }
+
+    /// Change the grid size, keeping the cells that still fit.
+    ///
+    /// This is the daemon storing new dimensions, so unlike the other setters
+    /// it leaves the sequence number alone unless
+    /// [`with_redraw_on_resize`](Self::with_redraw_on_resize) is set.
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        let len = cols as usize * rows as usize;
+        let mut cells = vec![' '; len];
+        let mut attrs = vec![CellAttributes::default(); len];
+        for row in 0..rows.min(self.rows) {
+            for col in 0..cols.min(self.cols) {
+                let from = row as usize * self.cols as usize + col as usize;
+                let to = row as usize * cols as usize + col as usize;
+                cells[to] = self.cells[from];
+                attrs[to] = self.attrs[from];
+            }
+        }
+
+        self.cols = cols;
+        self.rows = rows;
+        self.cells = cells;
+        self.attrs = attrs;
+        self.cursor = (
+            self.cursor.0.min(rows.saturating_sub(1)),
+            self.cursor.1.min(cols.saturating_sub(1)),
+        );
+        if self.redraw_on_resize {
+            self.bump_sequence();
+        }
+    }
+
+    /// Append bytes to the raw output stream.
+    ///
+    /// The grid is not updated; set it separately with [`set_grid`](Self::set_grid).
+    pub fn push_raw_output(&mut self, bytes: &[u8]) {
+        self.raw_output.extend_from_slice(bytes);
+        self.bump_sequence();
+    }
+
+    /// Get every byte pushed to the raw output stream so far.
+    pub fn raw_output(&self) -> &[u8] {
+        &self.raw_output
+    }
+
+    /// Advance the sequence number, as the daemon does after each update.
+    pub fn bump_sequence(&mut self) {
+        self.sequence_number = self.sequence_number.wrapping_add(2);
+    }
+
+    /// Refresh the header. Reads are always current; this only records the
+    /// sequence number for [`wait_for_update`](Self::wait_for_update).
+    pub fn refresh(&mut self) -> IpcResult<()> {
+        self.refreshed_sequence = self.sequence_number;
+        Ok(())
+    }
+
+    /// Return once the sequence number differs from the last refresh.
+    ///
+    /// Nothing can change the mock while this call holds it, so it either
+    /// returns immediately or sleeps out the timeout.
+    pub fn wait_for_update(&mut self, timeout: Duration) -> IpcResult<()> {
+        self.wait_for_update_on(&SystemClock, timeout)
+    }
+
+    /// Like [`wait_for_update`](Self::wait_for_update), sleeping out the
+    /// timeout on `clock`.
+    pub(crate) fn wait_for_update_on(
+        &mut self,
+        clock: &dyn Clock,
+        timeout: Duration,
+    ) -> IpcResult<()> {
+        if self.sequence_number != self.refreshed_sequence {
+            return self.refresh();
+        }
+
+        clock.sleep(timeout);
+        Err(IpcError::Timeout(timeout))
+    }
+
+    /// Get the terminal dimensions (cols, rows).
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.cols, self.rows)
+    }
+
+    /// Get the cursor position (row, col).
+    pub fn cursor_position(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    /// Get the sequence number for change detection.
+    pub fn sequence_number(&self) -> u32 {
+        self.sequence_number
+    }
+
+    /// Read the terminal grid as a string.
+    ///
+    /// Returns the grid content with newlines between rows.
+    pub fn grid_contents(&self) -> IpcResult<String> {
+        let cols = (self.cols as usize).max(1);
+        let rows: Vec<String> = self
+            .cells
+            .chunks(cols)
+            .map(|row| row.iter().collect())
+            .collect();
+        Ok(rows.join("\n"))
+    }
+
+    /// Check if the grid contains the given text.
+    pub fn contains(&self, text: &str) -> IpcResult<bool> {
+        let grid = self.grid_contents()?;
+        Ok(grid.contains(text))
+    }
+
+    /// Get a specific cell character at (row, col).
+    pub fn cell_at(&self, row: u16, col: u16) -> IpcResult<char> {
+        let index = self.checked_index(row, col)?;
+        Ok(self.cells[index])
+    }
+
+    /// Get cell attributes at (row, col).
+    pub fn cell_attrs_at(&self, row: u16, col: u16) -> IpcResult<CellAttributes> {
+        let index = self.checked_index(row, col)?;
+        Ok(self.attrs[index])
+    }
+
+    /// Get all cell attributes for a specific row.
+    pub fn row_attrs(&self, row: u16) -> IpcResult<Vec<CellAttributes>> {
+        if row >= self.rows {
+            return Err(IpcError::InvalidData(format!(
+                "Row {} out of bounds (max {})",
+                row, self.rows
+            )));
+        }
+
+        let start = row as usize * self.cols as usize;
+        Ok(self.attrs[start..start + self.cols as usize].to_vec())
+    }
+
+    /// Read the characters and attributes of every cell in a rectangle.
+    ///
+    /// Cells are returned in row-major order.
+    pub fn cells_in_range(&self, bounds: RegionBounds) -> IpcResult<Vec<(char, CellAttributes)>> {
+        CellGrid::cells_in_range(self, bounds)
+    }
+
+    /// Capture the current grid and sequence number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid can't be read.
+    pub fn snapshot(&self) -> IpcResult<ShmSnapshot> {
+        CellGrid::snapshot(self)
+    }
+
+    /// List the cells that changed since `previous` was taken.
+    ///
+    /// Short-circuits to an empty list when the sequence number hasn't moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid can't be read.
+    pub fn diff_since(&self, previous: &ShmSnapshot) -> IpcResult<Vec<CellDiff>> {
+        CellGrid::diff_since(self, previous)
+    }
+
+    fn index(&self, row: u16, col: u16) -> Option<usize> {
+        (row < self.rows && col < self.cols)
+            .then(|| row as usize * self.cols as usize + col as usize)
+    }
+
+    fn checked_index(&self, row: u16, col: u16) -> IpcResult<usize> {
+        self.index(row, col).ok_or(IpcError::CellOutOfBounds {
+            row,
+            col,
+            rows: self.rows,
+            cols: self.cols,
+        })
+    }
+}
+
+impl CellGrid for MockSharedMemory {
+    fn grid_dimensions(&self) -> (u16, u16) {
+        self.dimensions()
+    }
+
+    fn grid_sequence_number(&self) -> u32 {
+        self.sequence_number()
+    }
+
+    fn cell(&self, index: usize) -> IpcResult<(char, CellAttributes)> {
+        Ok((self.cells[index], self.attrs[index]))
+    }
+}
+
 /// Main test harness for split-process terminal daemon testing.
 ///
 /// Combines IPC communication and shared memory reading into a single
@@ -1002,7 +1862,6 @@ impl DaemonTestHarness {
     /// Wait until the grid contains the specified text.
     pub fn wait_for_text(&mut self, text: &str, timeout: Duration) -> IpcResult<()> {
         let start = Instant::now();
-        let poll_interval = Duration::from_millis(50);
 
         loop {
             self.shm.refresh()?;
@@ -1011,18 +1870,13 @@ impl DaemonTestHarness {
                 return Ok(());
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
-
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 
     /// Wait until the grid does NOT contain the specified text.
     pub fn wait_for_text_absent(&mut self, text: &str, timeout: Duration) -> IpcResult<()> {
         let start = Instant::now();
-        let poll_interval = Duration::from_millis(50);
 
         loop {
             self.shm.refresh()?;
@@ -1031,11 +1885,7 @@ impl DaemonTestHarness {
                 return Ok(());
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
-
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 
@@ -1056,22 +1906,19 @@ impl DaemonTestHarness {
 
     /// Wait for the sequence number to change, indicating a state update.
     pub fn wait_for_update(&mut self, timeout: Duration) -> IpcResult<()> {
-        let start = Instant::now();
-        let poll_interval = Duration::from_millis(10);
-        let initial_seq = self.shm.sequence_number();
-
-        loop {
-            self.shm.refresh()?;
-
-            if self.shm.sequence_number() != initial_seq {
-                return Ok(());
-            }
+        self.shm.wait_for_update(timeout)
+    }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
+    /// Sleep until the next frame, or fail once `timeout` has elapsed since `start`.
+    fn wait_for_frame(&mut self, start: Instant, timeout: Duration) -> IpcResult<()> {
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return Err(IpcError::Timeout(timeout));
+        }
 
-            std::thread::sleep(poll_interval);
+        match self.shm.wait_for_update(remaining) {
+            Err(IpcError::Timeout(_)) => Err(IpcError::Timeout(timeout)),
+            other => other,
         }
     }
 
@@ -1170,6 +2017,120 @@ impl<'a> TerminalStateReader<'a> {
     }
 }
 
+/// Shared memory fixtures for tests that read a real mapped segment.
+///
+/// Used by this crate's unit and integration tests; not part of the public
+/// API.
+#[doc(hidden)]
+#[cfg(target_family = "unix")]
+pub mod test_support {
+    use std::{fs::File, io::Write};
+
+    use super::{CellAttributes, ShmHeader};
+
+    /// POSIX shared memory segment, unlinked on drop.
+    #[derive(Debug)]
+    pub struct ShmSegment {
+        /// Name to pass to `shm_open`.
+        pub name: String,
+        file: File,
+    }
+
+    impl ShmSegment {
+        /// Create a grid segment laid out as [`ShmHeader`] describes.
+        ///
+        /// Cell `i`, in row-major order, holds `chars(i)` and `attrs(i)`. The
+        /// sequence number starts at 2.
+        pub fn grid(
+            tag: &str,
+            magic: u32,
+            cols: u16,
+            rows: u16,
+            chars: impl Fn(usize) -> u8,
+            attrs: impl Fn(usize) -> CellAttributes,
+        ) -> Self {
+            let cells = cols as usize * rows as usize;
+            let grid_offset = std::mem::size_of::<ShmHeader>();
+            let attrs_offset = (grid_offset + cells + 3) & !3;
+            let attrs_size = cells * std::mem::size_of::<CellAttributes>();
+
+            let mut buf = Vec::new();
+            for field in [magic, ShmHeader::DEFAULT_VERSION] {
+                buf.extend_from_slice(&field.to_ne_bytes());
+            }
+            for field in [cols, rows, 0, 0] {
+                buf.extend_from_slice(&field.to_ne_bytes());
+            }
+            let offsets = [2, grid_offset, cells, attrs_offset, attrs_size];
+            for field in offsets {
+                buf.extend_from_slice(&(field as u32).to_ne_bytes());
+            }
+            buf.extend((0..cells).map(&chars));
+            buf.resize(attrs_offset, 0);
+            for attrs in (0..cells).map(&attrs) {
+                buf.extend_from_slice(&attrs.fg.to_ne_bytes());
+                buf.extend_from_slice(&attrs.bg.to_ne_bytes());
+                buf.extend_from_slice(&attrs.flags.to_ne_bytes());
+                buf.extend_from_slice(&attrs.reserved.to_ne_bytes());
+            }
+
+            Self::create(tag, &buf)
+        }
+
+        /// Create an output log segment laid out as [`DaemonOutputLog`]
+        /// describes, as if the daemon had written `bytes` into a ring of
+        /// `capacity` bytes.
+        ///
+        /// [`DaemonOutputLog`]: super::DaemonOutputLog
+        pub fn output_log(tag: &str, magic: u32, capacity: usize, bytes: &[u8]) -> Self {
+            let mut buf = Vec::new();
+            buf.extend_from_slice(&magic.to_ne_bytes());
+            buf.extend_from_slice(&ShmHeader::DEFAULT_VERSION.to_ne_bytes());
+            buf.extend_from_slice(&(bytes.len() as u64).to_ne_bytes());
+            buf.extend_from_slice(&(capacity as u32).to_ne_bytes());
+            buf.extend_from_slice(&0u32.to_ne_bytes());
+
+            let mut ring = vec![0; capacity];
+            for (n, &byte) in bytes.iter().enumerate() {
+                ring[n % capacity] = byte;
+            }
+            buf.extend_from_slice(&ring);
+
+            Self::create(tag, &buf)
+        }
+
+        /// Overwrite the header's sequence number, as the daemon would.
+        pub fn set_sequence(&self, seq: u32) {
+            use std::os::unix::fs::FileExt;
+            self.file.write_at(&seq.to_ne_bytes(), 16).unwrap();
+        }
+
+        #[allow(unsafe_code)]
+        fn create(tag: &str, contents: &[u8]) -> Self {
+            use std::{ffi::CString, os::fd::FromRawFd};
+
+            let name = format!("/rtl_test_{}_{}", std::process::id(), tag);
+            let path = CString::new(name.clone()).unwrap();
+            let fd = unsafe {
+                libc::shm_open(path.as_ptr(), libc::O_CREAT | libc::O_EXCL | libc::O_RDWR, 0o600)
+            };
+            assert!(fd >= 0, "shm_open failed for {}", name);
+            let mut file = unsafe { File::from_raw_fd(fd) };
+            file.write_all(contents).unwrap();
+
+            Self { name, file }
+        }
+    }
+
+    impl Drop for ShmSegment {
+        #[allow(unsafe_code)]
+        fn drop(&mut self) {
+            let path = std::ffi::CString::new(self.name.clone()).unwrap();
+            unsafe { libc::shm_unlink(path.as_ptr()) };
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1416,4 +2377,283 @@ mod tests {
         let attrs2 = attrs1;
         assert_eq!(attrs1, attrs2);
     }
+
+    #[test]
+    fn test_mock_shared_memory_grid() {
+        let shm = MockSharedMemory::new(5, 3).with_grid("hello world\nab");
+
+        assert_eq!(shm.dimensions(), (5, 3));
+        assert_eq!(shm.grid_contents().unwrap(), "hello\nab   \n     ");
+        assert_eq!(shm.cell_at(1, 1).unwrap(), 'b');
+        assert!(shm.contains("hello").unwrap());
+        assert!(!shm.contains("world").unwrap());
+        assert!(shm.cell_at(3, 0).is_err());
+    }
+
+    #[test]
+    fn test_mock_shared_memory_attrs() {
+        let bold = CellAttributes {
+            flags: CellFlags::BOLD.bits(),
+            ..Default::default()
+        };
+        let mut shm = MockSharedMemory::new(2, 2).with_attrs(vec![bold]);
+
+        assert_eq!(shm.cell_attrs_at(0, 0).unwrap(), bold);
+        assert_eq!(shm.cell_attrs_at(1, 1).unwrap(), CellAttributes::default());
+
+        shm.set_cell_attrs(1, 0, bold);
+        assert_eq!(shm.row_attrs(1).unwrap(), vec![bold, CellAttributes::default()]);
+        assert!(shm.row_attrs(2).is_err());
+    }
+
+    #[test]
+    fn test_mock_shared_memory_sequence() {
+        let mut shm = MockSharedMemory::new(10, 2).with_cursor(1, 4);
+        assert_eq!(shm.cursor_position(), (1, 4));
+
+        let seq = shm.sequence_number();
+        shm.set_grid("next frame");
+        assert_ne!(shm.sequence_number(), seq);
+        // Stays even, like a settled seqlock
+        assert_eq!(shm.sequence_number() % 2, 0);
+    }
+
+    #[test]
+    fn test_mock_resize_keeps_overlap() {
+        let mut shm = MockSharedMemory::new(5, 2)
+            .with_grid("hello\nworld")
+            .with_cursor(1, 4);
+        let seq = shm.sequence_number();
+
+        shm.resize(3, 3);
+        assert_eq!(shm.dimensions(), (3, 3));
+        assert_eq!(shm.grid_contents().unwrap(), "hel\nwor\n   ");
+        assert_eq!(shm.cursor_position(), (1, 2));
+        // Storing the size is not a frame
+        assert_eq!(shm.sequence_number(), seq);
+
+        let mut shm = shm.with_redraw_on_resize(true);
+        shm.resize(6, 1);
+        assert_eq!(shm.grid_contents().unwrap(), "hel   ");
+        assert_ne!(shm.sequence_number(), seq);
+    }
+
+    /// Grid segment with letters cycling through the cells and varied attributes.
+    #[cfg(target_family = "unix")]
+    fn test_segment(tag: &str, cols: u16, rows: u16) -> test_support::ShmSegment {
+        test_support::ShmSegment::grid(
+            tag,
+            ShmHeader::DEFAULT_MAGIC,
+            cols,
+            rows,
+            |i| b'a' + (i % 26) as u8,
+            |i| {
+                let i = i as u32;
+                CellAttributes {
+                    fg: 0xFF00_00FF | (i % 7) << 8,
+                    bg: 0x0000_00FF | (i % 5) << 16,
+                    flags: (i % 3) as u16,
+                    reserved: 0,
+                }
+            },
+        )
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_cells_in_range_matches_per_cell_reads() {
+        let segment = test_segment("cells_in_range", 120, 40);
+        let shm = DaemonSharedMemory::open(&segment.name).unwrap();
+        let bounds = RegionBounds::new(3, 7, 100, 30);
+
+        let mut per_cell = Vec::new();
+        for row in bounds.row..bounds.row + bounds.height {
+            for col in bounds.col..bounds.col + bounds.width {
+                let c = shm.cell_at(row, col).unwrap();
+                per_cell.push((c, shm.cell_attrs_at(row, col).unwrap()));
+            }
+        }
+
+        let bulk = shm.cells_in_range(bounds).unwrap();
+        assert_eq!(bulk.len(), 3000);
+        assert_eq!(bulk, per_cell);
+
+        // The whole grid and an empty range
+        let whole = RegionBounds::new(0, 0, 120, 40);
+        let all = shm.cells_in_range(whole).unwrap();
+        assert_eq!(all.len(), 4800);
+        let empty = shm.cells_in_range(RegionBounds::new(5, 5, 0, 3)).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_output_log_reads_recent_bytes() {
+        let magic = ShmHeader::DEFAULT_MAGIC;
+        let short = test_support::ShmSegment::output_log("log_short", magic, 16, b"hello");
+        let log = DaemonOutputLog::open(&short.name).unwrap();
+        assert_eq!(log.capacity(), 16);
+        assert_eq!(log.bytes_written(), 5);
+        assert_eq!(log.read().unwrap(), b"hello");
+
+        // Once the ring wraps only the last `capacity` bytes remain
+        let wrapped = test_support::ShmSegment::output_log("log_wrapped", magic, 8, b"hello world");
+        let log = DaemonOutputLog::open(&wrapped.name).unwrap();
+        assert_eq!(log.read().unwrap(), b"lo world");
+
+        let other = test_support::ShmSegment::output_log("log_magic", 0x1234, 8, b"");
+        assert!(matches!(DaemonOutputLog::open(&other.name), Err(IpcError::InvalidData(_))));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_cells_in_range_out_of_bounds() {
+        let segment = test_segment("cells_in_range_oob", 10, 4);
+        let shm = DaemonSharedMemory::open(&segment.name).unwrap();
+
+        assert!(shm.cells_in_range(RegionBounds::new(0, 5, 6, 1)).is_err());
+        assert!(shm.cells_in_range(RegionBounds::new(3, 0, 10, 2)).is_err());
+        assert!(shm.cells_in_range(RegionBounds::new(3, 0, 10, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_structured_error_variants() {
+        let shm = MockSharedMemory::new(10, 4);
+        match shm.cell_attrs_at(4, 0) {
+            Err(IpcError::CellOutOfBounds { row, col, rows, cols }) => {
+                assert_eq!((row, col, rows, cols), (4, 0, 4, 10));
+            }
+            other => panic!("Expected CellOutOfBounds, got {:?}", other),
+        }
+        assert!(matches!(shm.cell_at(0, 10), Err(IpcError::CellOutOfBounds { col: 10, .. })));
+
+        // Messages read the same as the InvalidData strings they replace
+        let err = IpcError::CellOutOfBounds { row: 4, col: 0, rows: 4, cols: 10 };
+        assert_eq!(
+            err.to_string(),
+            "Invalid shared memory data: Position (4, 0) out of bounds (4x10)"
+        );
+        let err = IpcError::ColorMismatch {
+            row: 1,
+            col: 2,
+            layer: ColorLayer::Background,
+            expected: 0x000000FF,
+            actual: 0xFFFFFFFF,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid shared memory data: Cell (1, 2) background color mismatch: expected 0x000000FF, got 0xFFFFFFFF"
+        );
+        let err = IpcError::RegionNotFound("sidebar".to_string());
+        assert_eq!(err.to_string(), "Invalid shared memory data: Region 'sidebar' not found");
+        let err = IpcError::ExitCodeMismatch { expected: 0, actual: Some(2) };
+        assert_eq!(err.to_string(), "Command finished with exit code 2, expected exit code 0");
+        let err = IpcError::ExitCodeMismatch { expected: 0, actual: None };
+        assert_eq!(err.to_string(), "Command finished with no exit code, expected exit code 0");
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_wait_for_update_wakes_promptly() {
+        let segment = test_segment("wait_for_update", 10, 2);
+        let mut shm = DaemonSharedMemory::open(&segment.name).unwrap();
+
+        let result = shm.wait_for_update(Duration::from_millis(20));
+        assert!(matches!(result, Err(IpcError::Timeout(_))));
+
+        let elapsed = std::thread::scope(|scope| {
+            scope.spawn(|| {
+                std::thread::sleep(Duration::from_millis(5));
+                segment.set_sequence(4);
+            });
+
+            let start = Instant::now();
+            shm.wait_for_update(Duration::from_secs(2)).unwrap();
+            start.elapsed()
+        });
+
+        assert_eq!(shm.sequence_number(), 4);
+        // Well under the 50ms interval the harness loops used to sleep for
+        assert!(elapsed < Duration::from_millis(40), "took {:?}", elapsed);
+
+        // An update that landed before the call returns immediately
+        segment.set_sequence(6);
+        let start = Instant::now();
+        shm.wait_for_update(Duration::from_secs(2)).unwrap();
+        assert!(start.elapsed() < Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_mock_wait_for_update() {
+        let mut shm = MockSharedMemory::new(10, 2);
+        shm.refresh().unwrap();
+        let result = shm.wait_for_update(Duration::from_millis(5));
+        assert!(matches!(result, Err(IpcError::Timeout(_))));
+
+        shm.set_grid("changed");
+        assert!(shm.wait_for_update(Duration::from_secs(2)).is_ok());
+    }
+
+    #[test]
+    fn test_mock_cells_in_range() {
+        let red = CellAttributes { fg: 0xFF0000FF, ..Default::default() };
+        let mut shm = MockSharedMemory::new(4, 3).with_grid("abcd\nefgh\nijkl");
+        shm.set_cell_attrs(1, 2, red);
+
+        let cells = shm.cells_in_range(RegionBounds::new(1, 1, 2, 2)).unwrap();
+        let chars: String = cells.iter().map(|(c, _)| c).collect();
+        assert_eq!(chars, "fgjk");
+        assert_eq!(cells[1].1, red);
+        assert!(shm.cells_in_range(RegionBounds::new(2, 0, 4, 2)).is_err());
+    }
+
+    #[test]
+    fn test_diff_since_reports_changed_cells() {
+        let mut shm = MockSharedMemory::new(10, 3).with_grid("editor\n\n-- NORMAL");
+        let before = shm.snapshot().unwrap();
+
+        shm.set_grid("editor\n\n-- INSERT");
+        let bold = CellAttributes {
+            flags: CellFlags::BOLD.bits(),
+            ..Default::default()
+        };
+        shm.set_cell_attrs(0, 0, bold);
+
+        let diffs = shm.diff_since(&before).unwrap();
+        let positions: Vec<(u16, u16)> = diffs.iter().map(|d| (d.row, d.col)).collect();
+        assert_eq!(positions, vec![(0, 0), (2, 3), (2, 4), (2, 5), (2, 6), (2, 7), (2, 8)]);
+
+        // Style-only change keeps the character
+        assert_eq!(diffs[0].old_char, 'e');
+        assert_eq!(diffs[0].new_char, 'e');
+        assert_eq!(diffs[0].new_attrs, bold);
+        assert_eq!((diffs[1].old_char, diffs[1].new_char), ('N', 'I'));
+    }
+
+    #[test]
+    fn test_diff_since_unchanged_sequence() {
+        let shm = MockSharedMemory::new(10, 3).with_grid("same");
+        let snapshot = shm.snapshot().unwrap();
+        assert!(shm.diff_since(&snapshot).unwrap().is_empty());
+
+        // Same sequence number short-circuits even if the cells differ
+        let other = MockSharedMemory::new(10, 3).with_grid("other");
+        assert_eq!(other.sequence_number(), snapshot.sequence_number());
+        assert!(other.diff_since(&snapshot).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_diff_after_resize() {
+        let old = MockSharedMemory::new(2, 1)
+            .with_grid("ab")
+            .snapshot()
+            .unwrap();
+        let mut shm = MockSharedMemory::new(3, 1).with_grid("ab");
+        shm.bump_sequence();
+        shm.set_grid("abc");
+
+        let diffs = old.diff(&shm.snapshot().unwrap());
+        assert_eq!(diffs.len(), 1);
+        assert_eq!((diffs[0].col, diffs[0].old_char, diffs[0].new_char), (2, ' ', 'c'));
+    }
 }
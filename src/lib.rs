@@ -182,6 +182,7 @@
 #![deny(unsafe_code)]
 
 pub mod audit;
+pub mod clock;
 mod error;
 pub mod events;
 pub mod golden;
@@ -230,21 +231,29 @@ mod async_harness;
 #[cfg(feature = "async-tokio")]
 pub use async_harness::{AsyncTuiTestHarness, WaitResult};
 pub use audit::{
-    AuditSummary, HarnessType, PlaceholderPattern, PlaceholderTest, ScaffoldConfig, TestAuditor,
+    AuditSummary, HarnessType, PlaceholderPattern, PlaceholderTest, ScaffoldConfig, ScanConfig,
+    TestAuditor, TestLocation,
 };
+pub use clock::{Clock, FakeClock, SystemClock};
 pub use error::{Result, TermTestError};
-pub use events::{KeyCode, KeyEvent, Modifiers, MouseButton, MouseEvent, ScrollDirection};
+pub use events::{
+    KeyCode, KeyEvent, Modifiers, MouseButton, MouseEvent, MouseMode, ScrollDirection,
+};
 pub use golden::{GoldenFile, GoldenMetadata};
-pub use harness::{Axis, MemoryResults, RecordedEvent, TuiTestHarness};
+pub use harness::{Axis, MemoryResults, Newline, RecordedEvent, TuiTestHarness};
 pub use navigation::{
     FocusInfo, HintElementType, HintLabel, NavMode, NavigationTestExt, PromptMarker,
     PromptMarkerType,
 };
 pub use parallel::{
-    IsolatedTerminal, PoolConfig, PoolStats, TerminalGuard, TerminalId, TerminalPool, TestContext,
+    HarnessPool, IsolatedTerminal, PoolConfig, PoolStats, PooledHarness, TerminalGuard, TerminalId,
+    TerminalPool, TestContext,
 };
 pub use pty::TestTerminal;
-pub use screen::{Cell, GridSnapshot, ITerm2Region, KittyRegion, Rect, ScreenState, SixelRegion};
+pub use screen::{
+    strip_ansi, Cell, GridSnapshot, ITerm2Region, KittyRegion, Rect, ScreenCheckpoint, ScreenState,
+    SixelRegion, UnderlineColor, UnderlineStyle,
+};
 pub use terminal_profiles::{
     ColorDepth, Feature, MouseProtocol, TerminalCapabilities, TerminalProfile,
 };
@@ -270,6 +279,7 @@ pub use bevy::ComponentSnapshot;
 #[cfg(feature = "bevy")]
 pub use bevy::{
     BevyTuiTestHarness, HeadlessBevyRunner, HybridBevyHarness, HybridBevyHarnessBuilder,
+    TerminalOutput,
 };
 #[cfg(feature = "sixel")]
 pub use graphics::{GraphicsCapture, GraphicsProtocol, GraphicsRegion};
@@ -280,24 +290,24 @@ pub use sixel::{SixelCapture, SixelSequence};
 
 #[cfg(feature = "ipc")]
 pub use ipc::{
-    CellAttributes, CellFlags, ControlMessage, DaemonConfig, DaemonConfigBuilder, DaemonIpcClient,
-    DaemonTestExt, DaemonTestHarness, IpcError, IpcResult, ShmHeader,
+    CellAttributes, CellDiff, CellFlags, ColorLayer, ControlMessage, DaemonConfig,
+    DaemonConfigBuilder, DaemonIpcClient, DaemonTestExt, DaemonTestHarness, IpcError, IpcResult,
+    MockSharedMemory, ShmHeader, ShmSnapshot,
 };
 
 #[cfg(feature = "ipc")]
-pub use theme::{AnsiColor, ColorPalette, ColorScan, ThemeTestExt};
+pub use theme::{AnsiColor, CellColors, ColorDiagnostics, ColorPalette, ColorScan, ThemeTestExt};
 
 #[cfg(feature = "ipc")]
-pub use seqlock::{SeqlockReport, SeqlockVerifier};
+pub use seqlock::{MockSeqlockMemory, SeqlockMemory, SeqlockReport, SeqlockVerifier};
 
 #[cfg(feature = "ipc")]
-pub use regions::{RegionAnchor, RegionBounds, UiRegion, UiRegionTester};
-
-#[cfg(feature = "scarab")]
-pub use regions::UiRegionTestExt;
+pub use regions::{RegionAnchor, RegionBounds, UiRegion, UiRegionTestExt, UiRegionTester};
 
 #[cfg(feature = "ipc")]
-pub use zones::{Osc133Marker, Osc133Parser, SemanticZone, SemanticZoneExt, ZoneType};
+pub use zones::{
+    CommandRecord, Osc133Marker, Osc133Parser, PromptKind, SemanticZone, SemanticZoneExt, ZoneType,
+};
 
 #[cfg(feature = "scarab")]
 pub use scarab::{
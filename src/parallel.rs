@@ -6,6 +6,7 @@
 //! # Key Features
 //!
 //! - **PTY Pool Management**: Allocate and release terminals from a shared pool
+//! - **Harness Pooling**: Reuse pre-allocated harnesses across tests via [`HarnessPool`]
 //! - **Resource Isolation**: Each test gets an isolated terminal instance
 //! - **Thread Safety**: All components are `Send + Sync` for parallel execution
 //! - **Timeout Handling**: Proper cleanup on test failure or timeout
@@ -41,12 +42,14 @@
 
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex, RwLock},
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, PoisonError, RwLock},
     time::{Duration, Instant},
 };
 
 use crate::{
     error::{Result, TermTestError},
+    harness::TuiTestHarness,
     pty::TestTerminal,
 };
 
@@ -632,6 +635,175 @@ impl Drop for TerminalGuard {
     }
 }
 
+/// A pool of pre-allocated test harnesses.
+///
+/// Creating a PTY for every test is expensive. A `HarnessPool` creates a
+/// fixed number of [`TuiTestHarness`] instances up front and hands them out
+/// with [`acquire()`](Self::acquire), which also bounds how many tests can
+/// run at once. When the returned [`PooledHarness`] is dropped, any leftover
+/// child process is killed, the screen is cleared, and the harness goes back
+/// into the pool.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::{sync::Arc, thread};
+///
+/// use terminal_testlib::parallel::HarnessPool;
+///
+/// # fn test() -> terminal_testlib::Result<()> {
+/// let pool = Arc::new(HarnessPool::new(4, 80, 24)?);
+///
+/// let handles: Vec<_> = (0..16)
+///     .map(|_| {
+///         let pool = Arc::clone(&pool);
+///         thread::spawn(move || {
+///             let mut harness = pool.acquire()?;
+///             harness.state_mut().feed(b"Hello");
+///             assert!(harness.screen_contents().contains("Hello"));
+///             Ok::<(), terminal_testlib::TermTestError>(())
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct HarnessPool {
+    width: u16,
+    height: u16,
+    capacity: usize,
+    acquire_timeout: Duration,
+    idle: Mutex<Vec<TuiTestHarness>>,
+}
+
+impl std::fmt::Debug for HarnessPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HarnessPool")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("capacity", &self.capacity)
+            .field("available", &self.available())
+            .field("acquire_timeout", &self.acquire_timeout)
+            .finish()
+    }
+}
+
+impl HarnessPool {
+    /// Creates a pool of `count` harnesses with the given dimensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any harness cannot be created.
+    pub fn new(count: usize, width: u16, height: u16) -> Result<Self> {
+        let harnesses = (0..count)
+            .map(|_| TuiTestHarness::new(width, height))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            width,
+            height,
+            capacity: count,
+            acquire_timeout: DEFAULT_ACQUIRE_TIMEOUT,
+            idle: Mutex::new(harnesses),
+        })
+    }
+
+    /// Sets how long [`acquire()`](Self::acquire) waits for a free harness.
+    pub fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
+    /// Acquires a harness, waiting until one is returned if all are in use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Timeout`] if no harness becomes available
+    /// within the acquire timeout.
+    pub fn acquire(&self) -> Result<PooledHarness<'_>> {
+        let start = Instant::now();
+        let poll_interval = Duration::from_millis(10);
+
+        loop {
+            if let Some(harness) = self.lock_idle().pop() {
+                return Ok(PooledHarness { harness: Some(harness), pool: self });
+            }
+
+            if start.elapsed() >= self.acquire_timeout {
+                return Err(TermTestError::Timeout {
+                    timeout_ms: self.acquire_timeout.as_millis() as u64,
+                });
+            }
+
+            std::thread::sleep(poll_interval);
+        }
+    }
+
+    /// Returns the total number of harnesses owned by the pool.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of harnesses not currently acquired.
+    pub fn available(&self) -> usize {
+        self.lock_idle().len()
+    }
+
+    /// Returns the dimensions of the pooled harnesses as (width, height).
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    fn lock_idle(&self) -> std::sync::MutexGuard<'_, Vec<TuiTestHarness>> {
+        // A panicking test must not take the whole pool down with it
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A harness borrowed from a [`HarnessPool`].
+///
+/// Dereferences to [`TuiTestHarness`]. The harness is reset and returned to
+/// the pool when this guard is dropped, even if the test panics.
+pub struct PooledHarness<'a> {
+    harness: Option<TuiTestHarness>,
+    pool: &'a HarnessPool,
+}
+
+impl std::fmt::Debug for PooledHarness<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledHarness")
+            .field("pool", self.pool)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Deref for PooledHarness<'_> {
+    type Target = TuiTestHarness;
+
+    fn deref(&self) -> &Self::Target {
+        self.harness.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledHarness<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.harness.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledHarness<'_> {
+    fn drop(&mut self) {
+        if let Some(mut harness) = self.harness.take() {
+            harness.reset();
+            self.pool.lock_idle().push(harness);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
@@ -845,4 +1017,73 @@ mod tests {
         let stats = pool.stats();
         assert!(stats.total >= 2);
     }
+
+    #[test]
+    fn test_harness_pool_reuse_resets_screen() {
+        let pool = HarnessPool::new(1, 40, 10).unwrap();
+        assert_eq!(pool.capacity(), 1);
+        assert_eq!(pool.available(), 1);
+
+        {
+            let mut harness = pool.acquire().unwrap();
+            assert_eq!(pool.available(), 0);
+            harness.state_mut().feed(b"\x1b[3;5Hleftover output");
+            assert!(harness.screen_contents().contains("leftover"));
+        }
+        assert_eq!(pool.available(), 1);
+
+        let harness = pool.acquire().unwrap();
+        assert_eq!(harness.state().size(), (40, 10));
+        assert_eq!(harness.cursor_position(), (0, 0));
+        assert!(!harness.screen_contents().contains("leftover"));
+    }
+
+    #[test]
+    fn test_harness_pool_kills_leftover_child() {
+        let pool = HarnessPool::new(1, 80, 24).unwrap();
+
+        {
+            let mut harness = pool.acquire().unwrap();
+            let mut cmd = portable_pty::CommandBuilder::new("sleep");
+            cmd.arg("10");
+            harness.spawn(cmd).unwrap();
+            assert!(harness.is_running());
+        }
+
+        let mut harness = pool.acquire().unwrap();
+        assert!(!harness.is_running());
+    }
+
+    #[test]
+    fn test_harness_pool_bounds_concurrency() {
+        let pool = HarnessPool::new(1, 80, 24)
+            .unwrap()
+            .with_acquire_timeout(Duration::from_millis(50));
+
+        let _held = pool.acquire().unwrap();
+        assert!(matches!(pool.acquire(), Err(TermTestError::Timeout { .. })));
+    }
+
+    #[test]
+    fn test_harness_pool_parallel_acquire() {
+        let pool = Arc::new(HarnessPool::new(2, 80, 24).unwrap());
+
+        let handles: Vec<_> = (0..6)
+            .map(|i| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    let mut harness = pool.acquire().unwrap();
+                    assert!(!harness.screen_contents().contains("thread"));
+                    harness.state_mut().feed(format!("thread {}", i).as_bytes());
+                    thread::sleep(Duration::from_millis(10));
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(pool.available(), 2);
+    }
 }
@@ -19,6 +19,10 @@ const DEFAULT_BUFFER_SIZE: usize = 8192;
 /// Default timeout for spawn operations.
 const DEFAULT_SPAWN_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Number of chunks the background output pump queues before its reader
+/// waits for the test to drain them.
+const PUMP_CHANNEL_CAPACITY: usize = 256;
+
 /// A test terminal backed by a pseudo-terminal (PTY).
 ///
 /// This provides low-level access to PTY operations for spawning processes,
@@ -29,6 +33,7 @@ pub struct TestTerminal {
     exit_status: Option<ExitStatus>,
     buffer_size: usize,
     writer: Option<Box<dyn Write + Send>>,
+    kill_on_drop: bool,
 }
 
 impl TestTerminal {
@@ -72,6 +77,7 @@ impl TestTerminal {
             exit_status: None,
             buffer_size: DEFAULT_BUFFER_SIZE,
             writer: None,
+            kill_on_drop: true,
         })
     }
 
@@ -176,6 +182,33 @@ impl TestTerminal {
         Ok(())
     }
 
+    /// Sets whether dropping the terminal kills the child's process group.
+    ///
+    /// Enabled by default: on drop, the child and everything it started are sent
+    /// `SIGKILL` and the child is reaped, so a test that panics mid-run does not
+    /// leak processes. Disable this when a test intentionally leaves a daemon
+    /// running. Closing the PTY still sends `SIGHUP` to the child's session, so a
+    /// process meant to outlive the terminal must ignore `SIGHUP` or start its
+    /// own session.
+    ///
+    /// If the child's exit has already been observed (through
+    /// [`try_wait`](Self::try_wait), [`is_running`](Self::is_running) or a
+    /// wait), its process group is not signalled, because the group ID may
+    /// have been reused by then. Anything the child left running keeps running.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to kill the child's process group on drop
+    pub fn with_kill_on_drop(mut self, enabled: bool) -> Self {
+        self.kill_on_drop = enabled;
+        self
+    }
+
+    /// Returns the process ID of the spawned child, if one is running.
+    pub fn process_id(&self) -> Option<u32> {
+        self.child.as_ref()?.process_id()
+    }
+
     /// Reads available output from the PTY.
     ///
     /// This is a non-blocking read that returns immediately with whatever data is available.
@@ -641,6 +674,51 @@ impl TestTerminal {
         }
     }
 
+    /// Checks whether the child process has exited without blocking.
+    ///
+    /// Returns `Some(status)` if the process has exited (including when it was
+    /// already reaped by [`wait`](Self::wait) or [`wait_timeout`](Self::wait_timeout)),
+    /// or `None` if it is still running.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::NoProcessRunning`] if no process was spawned, or
+    /// an I/O error if the process status cannot be queried.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use terminal_testlib::TestTerminal;
+    ///
+    /// let mut terminal = TestTerminal::new(80, 24)?;
+    /// terminal.spawn(CommandBuilder::new("true"))?;
+    /// while terminal.try_wait()?.is_none() {
+    ///     std::thread::sleep(std::time::Duration::from_millis(10));
+    /// }
+    /// # Ok::<(), terminal_testlib::TermTestError>(())
+    /// ```
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        let Some(ref mut child) = self.child else {
+            return match &self.exit_status {
+                Some(status) => Ok(Some(status.clone())),
+                None => Err(TermTestError::NoProcessRunning),
+            };
+        };
+
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                self.exit_status = Some(status.clone());
+                Ok(Some(status))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(TermTestError::Io(std::io::Error::other(format!(
+                "Failed to check process status: {}",
+                e
+            )))),
+        }
+    }
+
     /// Returns the cached exit status of the child process, if available.
     ///
     /// This returns the exit status if the process has already exited.
@@ -665,17 +743,121 @@ impl TestTerminal {
     pub fn get_exit_status(&self) -> Option<ExitStatus> {
         self.exit_status.clone()
     }
+
+    /// Starts a background thread that continuously drains PTY output.
+    ///
+    /// The thread reads until the PTY reports EOF or an error, or the returned
+    /// [`OutputPump`] is dropped. At most [`PUMP_CHANNEL_CAPACITY`] chunks are
+    /// queued; once the queue is full the thread stops reading until it is
+    /// drained.
+    pub(crate) fn start_output_pump(&self) -> Result<OutputPump> {
+        let mut reader = self.pty_pair.master.try_clone_reader().map_err(|e| {
+            TermTestError::Io(std::io::Error::other(format!("Failed to clone PTY reader: {}", e)))
+        })?;
+
+        let (tx, rx) = mpsc::sync_channel(PUMP_CHANNEL_CAPACITY);
+        let buffer_size = self.buffer_size;
+        std::thread::spawn(move || {
+            let mut buf = vec![0u8; buffer_size];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(buf[..n].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(OutputPump { receiver: rx })
+    }
+
+    /// Kills the child's process group after a wait for it timed out, unless
+    /// the terminal was configured to leave its child running on drop.
+    pub(crate) fn kill_after_timeout(&mut self) {
+        if !self.kill_on_drop {
+            return;
+        }
+        if let Some(child) = self.child.take() {
+            self.exit_status = kill_process_group(child, self.exit_status.is_some());
+        }
+    }
+
+    /// Returns the terminal to a freshly created state so it can be reused.
+    ///
+    /// Kills and reaps any leftover child, forgets its exit status, and
+    /// discards output still buffered in the PTY.
+    pub(crate) fn reset(&mut self) {
+        if let Some(child) = self.child.take() {
+            kill_process_group(child, self.exit_status.is_some());
+        }
+        self.exit_status = None;
+        let _ = self.read_all();
+    }
+}
+
+/// Receiving end of a background PTY reader started by
+/// [`TestTerminal::start_output_pump`].
+///
+/// The reader thread keeps the kernel buffer empty, so a child that writes
+/// faster than the test polls does not block on a full PTY. Output queues here
+/// until it is drained; when the queue is full the reader waits, and the child
+/// blocks as it would without the pump.
+pub(crate) struct OutputPump {
+    receiver: mpsc::Receiver<Vec<u8>>,
+}
+
+impl OutputPump {
+    /// Returns every chunk received so far without blocking.
+    pub(crate) fn drain(&self) -> Vec<Vec<u8>> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Returns chunks until none arrives for `quiet`, for collecting the
+    /// tail of a child's output after it exits.
+    pub(crate) fn drain_until_quiet(&self, quiet: Duration) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        while let Ok(chunk) = self.receiver.recv_timeout(quiet) {
+            chunks.push(chunk);
+        }
+        chunks
+    }
 }
 
 impl Drop for TestTerminal {
     fn drop(&mut self) {
-        // Kill the child process if it's still running
-        if let Some(mut child) = self.child.take() {
-            let _ = child.kill();
+        if let Some(child) = self.child.take() {
+            if self.kill_on_drop {
+                kill_process_group(child, self.exit_status.is_some());
+            }
         }
     }
 }
 
+/// Kills a child together with any processes it started, then reaps it.
+///
+/// `portable-pty` starts each child in a new session, so the child's process
+/// ID is also the ID of its process group.
+///
+/// `reaped` says whether [`try_wait`](TestTerminal::try_wait) or
+/// [`is_running`](TestTerminal::is_running) has already collected the child's
+/// exit status. The group is then left alone: once its last member exits the
+/// ID can be reused, and signalling it could kill an unrelated process group.
+/// Processes the child left behind in its group keep running in that case.
+#[allow(unsafe_code)]
+fn kill_process_group(mut child: Box<dyn Child + Send + Sync>, reaped: bool) -> Option<ExitStatus> {
+    #[cfg(unix)]
+    if let Some(pid) = child.process_id().filter(|_| !reaped) {
+        unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) };
+    }
+    let _ = child.kill();
+    child.wait().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
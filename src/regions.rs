@@ -89,7 +89,14 @@
 //! let tester = UiRegionTester::new(80, 24).with_region(custom);
 //! ```
 
-use crate::ipc::{IpcError, IpcResult};
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+
+use crate::{
+    ipc::{CellAttributes, CellFlags, IpcError, IpcResult},
+    screen::Rect,
+};
 
 /// Defines a fixed UI region.
 ///
@@ -123,13 +130,16 @@ pub struct UiRegion {
     /// Where the region is anchored.
     pub anchor: RegionAnchor,
     /// Size in rows (for Top/Bottom anchors) or columns (for Left/Right anchors).
+    ///
+    /// Ignored for [`RegionAnchor::Center`], which carries its own dimensions.
     pub size: u16,
 }
 
 /// Where a UI region is anchored.
 ///
-/// Regions are anchored to one of the four edges of the terminal.
-/// The anchor determines how the region's position is calculated.
+/// Regions are anchored to one of the four edges of the terminal, or float
+/// centered over the content area. The anchor determines how the region's
+/// position is calculated.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RegionAnchor {
     /// Anchored to the top of the screen.
@@ -140,6 +150,17 @@ pub enum RegionAnchor {
     Left,
     /// Anchored to the right of the screen.
     Right,
+    /// Floating box centered in the content area (e.g., a modal dialog).
+    ///
+    /// Centered regions overlay the content area and do not shrink it. When
+    /// the remaining space can't be split evenly, the offset is rounded down,
+    /// leaving the extra row or column below or to the right of the box.
+    Center {
+        /// Width of the box in columns.
+        width: u16,
+        /// Height of the box in rows.
+        height: u16,
+    },
 }
 
 /// Rectangle bounds (row, col, width, height).
@@ -260,6 +281,29 @@ impl RegionBounds {
             || other.col.saturating_add(other.width) <= self.col)
     }
 
+    /// Check if another region lies entirely within this region.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The region to check
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use terminal_testlib::regions::RegionBounds;
+    ///
+    /// let content = RegionBounds::new(1, 0, 80, 22);
+    ///
+    /// assert!(content.contains_bounds(&RegionBounds::new(2, 10, 20, 5)));
+    /// assert!(!content.contains_bounds(&RegionBounds::new(2, 70, 20, 5))); // Spills right
+    /// ```
+    pub const fn contains_bounds(&self, other: &RegionBounds) -> bool {
+        other.row >= self.row
+            && other.col >= self.col
+            && other.bottom() <= self.bottom()
+            && other.right() <= self.right()
+    }
+
     /// Get the bottom row (exclusive).
     #[inline]
     const fn bottom(&self) -> u16 {
@@ -273,6 +317,12 @@ impl RegionBounds {
     }
 }
 
+impl From<Rect> for RegionBounds {
+    fn from(rect: Rect) -> Self {
+        Self::new(rect.y, rect.x, rect.width, rect.height)
+    }
+}
+
 /// UI region tester for verifying fixed regions.
 ///
 /// This struct manages a collection of fixed UI regions and provides
@@ -438,6 +488,41 @@ impl UiRegionTester {
         self
     }
 
+    /// Add a floating region centered in the content area.
+    ///
+    /// Useful for modal dialogs and popups drawn on top of the main content.
+    /// The box is clamped to the content area if it doesn't fit.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the region
+    /// * `width` - Width of the box in columns
+    /// * `height` - Height of the box in rows
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use terminal_testlib::regions::UiRegionTester;
+    ///
+    /// let tester = UiRegionTester::new(80, 24).with_floating_region("dialog", 40, 10);
+    /// let bounds = tester.region_bounds("dialog").unwrap();
+    /// assert_eq!((bounds.row, bounds.col), (7, 20));
+    /// assert!(tester.is_in_region("dialog", 12, 40));
+    /// ```
+    pub fn with_floating_region(
+        mut self,
+        name: impl Into<String>,
+        width: u16,
+        height: u16,
+    ) -> Self {
+        self.regions.push(UiRegion {
+            name: name.into(),
+            anchor: RegionAnchor::Center { width, height },
+            size: 0,
+        });
+        self
+    }
+
     /// Add a custom region.
     ///
     /// # Arguments
@@ -504,6 +589,7 @@ impl UiRegionTester {
                 RegionAnchor::Bottom => bottom_offset = bottom_offset.saturating_add(r.size),
                 RegionAnchor::Left => left_offset = left_offset.saturating_add(r.size),
                 RegionAnchor::Right => right_offset = right_offset.saturating_add(r.size),
+                RegionAnchor::Center { .. } => {}
             }
         }
 
@@ -539,6 +625,14 @@ impl UiRegionTester {
                     .saturating_sub(right_offset);
                 RegionBounds::new(top_offset, col, region.size, available_height)
             }
+            RegionAnchor::Center { width, height } => {
+                let content = self.content_area();
+                let width = width.min(content.width);
+                let height = height.min(content.height);
+                let row = content.row + (content.height - height) / 2;
+                let col = content.col + (content.width - width) / 2;
+                RegionBounds::new(row, col, width, height)
+            }
         }
     }
 
@@ -575,6 +669,7 @@ impl UiRegionTester {
                 RegionAnchor::Bottom => bottom = bottom.saturating_add(region.size),
                 RegionAnchor::Left => left = left.saturating_add(region.size),
                 RegionAnchor::Right => right = right.saturating_add(region.size),
+                RegionAnchor::Center { .. } => {}
             }
         }
 
@@ -649,14 +744,156 @@ impl UiRegionTester {
     pub fn region_names(&self) -> Vec<String> {
         self.regions.iter().map(|r| r.name.clone()).collect()
     }
+
+    /// Split the content area into `n` equal-width columns.
+    ///
+    /// Panes are named `pane_0`, `pane_1`, … from left to right. When the
+    /// width doesn't divide evenly, the leftmost panes get one extra column
+    /// each. Returns an empty list when `n` is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of columns
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use terminal_testlib::regions::{RegionBounds, UiRegionTester};
+    ///
+    /// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    /// let panes = tester.split_content_columns(3);
+    ///
+    /// assert_eq!(panes[0], ("pane_0".to_string(), RegionBounds::new(0, 0, 27, 23)));
+    /// assert_eq!(panes[1], ("pane_1".to_string(), RegionBounds::new(0, 27, 27, 23)));
+    /// assert_eq!(panes[2], ("pane_2".to_string(), RegionBounds::new(0, 54, 26, 23)));
+    /// ```
+    pub fn split_content_columns(&self, n: u16) -> Vec<(String, RegionBounds)> {
+        self.split_content_grid(1, n)
+    }
+
+    /// Split the content area into a `rows` × `cols` grid.
+    ///
+    /// Panes are named `pane_0`, `pane_1`, … in row-major order. Remainder
+    /// columns go to the leftmost panes and remainder rows to the topmost
+    /// panes. Returns an empty list when either dimension is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Number of pane rows
+    /// * `cols` - Number of pane columns
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use terminal_testlib::regions::{RegionBounds, UiRegionTester};
+    ///
+    /// let tester = UiRegionTester::new(80, 24);
+    /// let panes = tester.split_content_grid(2, 2);
+    ///
+    /// assert_eq!(panes.len(), 4);
+    /// assert_eq!(panes[3], ("pane_3".to_string(), RegionBounds::new(12, 40, 40, 12)));
+    /// ```
+    pub fn split_content_grid(&self, rows: u16, cols: u16) -> Vec<(String, RegionBounds)> {
+        let content = self.content_area();
+        let row_spans = split_evenly(content.row, content.height, rows);
+        let col_spans = split_evenly(content.col, content.width, cols);
+
+        let mut panes = Vec::with_capacity(row_spans.len() * col_spans.len());
+        for &(row, height) in &row_spans {
+            for &(col, width) in &col_spans {
+                let name = format!("pane_{}", panes.len());
+                panes.push((name, RegionBounds::new(row, col, width, height)));
+            }
+        }
+        panes
+    }
+
+    /// Check that no two fixed regions overlap.
+    ///
+    /// Computes the bounds of every region and reports each pair whose bounds
+    /// intersect, in definition order. Floating regions
+    /// ([`RegionAnchor::Center`]) are meant to overlay other content and are
+    /// skipped, as are regions that end up with no area.
+    ///
+    /// # Errors
+    ///
+    /// Returns the names of every conflicting pair of regions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use terminal_testlib::regions::UiRegionTester;
+    ///
+    /// let tester = UiRegionTester::new(80, 24)
+    ///     .with_tab_bar(2)
+    ///     .with_status_bar(1)
+    ///     .with_floating_region("dialog", 40, 10);
+    /// assert!(tester.validate().is_ok());
+    ///
+    /// // Sidebar defined first starts at row 0, underneath the tab bar
+    /// let tester = UiRegionTester::new(80, 24)
+    ///     .with_left_sidebar(20)
+    ///     .with_tab_bar(2);
+    /// assert_eq!(
+    ///     tester.validate(),
+    ///     Err(vec![("left_sidebar".to_string(), "tab_bar".to_string())])
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<(String, String)>> {
+        let bounds: Vec<(&str, RegionBounds)> = self
+            .regions
+            .iter()
+            .filter(|r| !matches!(r.anchor, RegionAnchor::Center { .. }))
+            .map(|r| (r.name.as_str(), self.calculate_bounds(r)))
+            .filter(|(_, b)| b.width > 0 && b.height > 0)
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (i, (name_a, a)) in bounds.iter().enumerate() {
+            for (name_b, b) in &bounds[i + 1..] {
+                if a.intersects(b) {
+                    conflicts.push((name_a.to_string(), name_b.to_string()));
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+}
+
+/// Split `len` cells starting at `start` into `n` spans, giving the remainder
+/// to the first spans.
+fn split_evenly(start: u16, len: u16, n: u16) -> Vec<(u16, u16)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let base = len / n;
+    let extra = len % n;
+    let mut offset = start;
+    (0..n)
+        .map(|i| {
+            let size = base + u16::from(i < extra);
+            let span = (offset, size);
+            offset += size;
+            span
+        })
+        .collect()
 }
 
 /// Extension trait for UI region testing with harnesses.
 ///
 /// This trait integrates UI region testing with test harnesses like
-/// `ScarabTestHarness`. It provides methods to extract region contents,
-/// verify region constraints, and test resize behavior.
-#[cfg(feature = "scarab")]
+/// `TuiTestHarness` and `ScarabTestHarness`. It provides methods to extract
+/// region contents, verify region constraints, and test resize behavior.
+///
+/// Methods added after the first release have default implementations
+/// built on the original required ones, so existing implementors keep
+/// compiling.
 pub trait UiRegionTestExt {
     /// Get the grid contents for a specific region.
     ///
@@ -696,6 +933,108 @@ pub trait UiRegionTestExt {
     /// ```
     fn region_contents(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<String>;
 
+    /// Get the grid contents for a specific region as one string per row.
+    ///
+    /// Each line is cut to the region's column span, so `lines[0]` is the
+    /// first row of the region rather than of the screen. The default
+    /// implementation splits [`region_contents`](Self::region_contents) on
+    /// newlines.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to extract
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - Failed to read the terminal grid
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_tab_bar(2);
+    ///
+    /// let lines = harness.region_lines(&tester, "tab_bar")?;
+    /// assert!(lines[1].starts_with("[1] main"));
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn region_lines(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<Vec<String>> {
+        let contents = self.region_contents(tester, region_name)?;
+        Ok(contents.split('\n').map(str::to_string).collect())
+    }
+
+    /// Get the glyphs and attributes of every cell in a region.
+    ///
+    /// Rows and columns are relative to the region, as in
+    /// [`region_lines`](Self::region_lines), so one call backs both text and
+    /// color assertions, such as checking that the selected tab is the
+    /// highlighted one. As there, rows below the grid are left out and
+    /// columns past its right edge are blank with default attributes.
+    ///
+    /// [`TuiTestHarness`](crate::TuiTestHarness) tracks palette indices rather
+    /// than RGBA, so its colors are resolved with xterm's default palette and
+    /// a cell in the terminal's default color reports `0`, as in
+    /// [`CellAttributes::default`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to extract
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - Failed to read the terminal grid or attributes
+    /// - The harness does not provide cell attributes (implementors that do
+    ///   not override this method)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_tab_bar(1);
+    ///
+    /// let cells = harness.region_cells(&tester, "tab_bar")?;
+    /// let (glyph, attrs) = cells[0][1];
+    /// assert_eq!(glyph, '1');
+    /// assert_ne!(attrs.bg, 0);
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn region_cells(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+    ) -> IpcResult<Vec<Vec<(char, CellAttributes)>>> {
+        lookup_region(tester, region_name)?;
+        Err(IpcError::InvalidData(
+            "This harness does not provide cell attributes".to_string(),
+        ))
+    }
+
     /// Get the content area grid contents (excluding fixed regions).
     ///
     /// This method extracts the terminal grid content that falls within
@@ -749,7 +1088,8 @@ pub trait UiRegionTestExt {
     /// Returns an error if:
     /// - The region doesn't exist
     /// - Failed to read the terminal grid
-    /// - The text is found in the region
+    /// - The text is found in the region; the error gives the screen
+    ///   position (row, col) of each match
     ///
     /// # Examples
     ///
@@ -819,22 +1159,24 @@ pub trait UiRegionTestExt {
         expected: &str,
     ) -> IpcResult<()>;
 
-    /// Verify resize event correctly calculated terminal dimensions.
+    /// Assert that content in a fixed region matches a regular expression.
     ///
-    /// This method resizes the terminal and verifies that the content area
-    /// and fixed regions are recalculated correctly.
+    /// Useful for dynamic content such as clocks or counters where the
+    /// literal text isn't known in advance.
     ///
     /// # Arguments
     ///
-    /// * `tester` - The UI region tester with region definitions (will be updated)
-    /// * `new_width` - New terminal width
-    /// * `new_height` - New terminal height
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to check
+    /// * `pattern` - Regular expression the region text must match
     ///
     /// # Errors
     ///
     /// Returns an error if:
-    /// - Failed to resize the terminal
-    /// - The terminal dimensions don't match after resize
+    /// - The region doesn't exist
+    /// - Failed to read the terminal grid
+    /// - The pattern is not a valid regular expression
+    /// - The region text doesn't match the pattern
     ///
     /// # Examples
     ///
@@ -847,98 +1189,634 @@ pub trait UiRegionTestExt {
     /// };
     ///
     /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut harness = ScarabTestHarness::connect()?;
-    /// let mut tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
     ///
-    /// harness.verify_resize(&mut tester, 100, 30)?;
+    /// harness.assert_region_matches(&tester, "status_bar", r"\b\d{2}:\d{2}\b")?;
     /// # Ok(())
     /// # }
     /// # }
     /// ```
-    fn verify_resize(
-        &mut self,
-        tester: &mut UiRegionTester,
-        new_width: u16,
-        new_height: u16,
-    ) -> IpcResult<()>;
-}
-
-#[cfg(feature = "scarab")]
-impl UiRegionTestExt for crate::scarab::ScarabTestHarness {
-    fn region_contents(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<String> {
-        let bounds = tester.region_bounds(region_name).ok_or_else(|| {
-            IpcError::InvalidData(format!("Region '{}' not found", region_name))
-        })?;
-
-        let full_grid = self.grid_contents()?;
-        extract_region_from_grid(&full_grid, tester.screen_width, &bounds)
-    }
-
-    fn content_area_contents(&self, tester: &UiRegionTester) -> IpcResult<String> {
-        let bounds = tester.content_area();
-        let full_grid = self.grid_contents()?;
-        extract_region_from_grid(&full_grid, tester.screen_width, &bounds)
-    }
-
-    fn assert_not_in_region(
+    fn assert_region_matches(
         &self,
         tester: &UiRegionTester,
         region_name: &str,
-        text: &str,
+        pattern: &str,
     ) -> IpcResult<()> {
         let region_content = self.region_contents(tester, region_name)?;
-
-        if region_content.contains(text) {
-            return Err(IpcError::InvalidData(format!(
-                "Text '{}' found in region '{}' but should not be present.\nRegion content:\n{}",
-                text, region_name, region_content
-            )));
-        }
-
-        Ok(())
+        check_region_matches(region_name, &region_content, pattern)
     }
 
-    fn assert_region_contains(
-        &self,
-        tester: &UiRegionTester,
-        region_name: &str,
-        expected: &str,
-    ) -> IpcResult<()> {
-        let region_content = self.region_contents(tester, region_name)?;
-
-        if !region_content.contains(expected) {
-            return Err(IpcError::InvalidData(format!(
-                "Expected text '{}' not found in region '{}'.\nRegion content:\n{}",
-                expected, region_name, region_content
-            )));
-        }
-
-        Ok(())
+    /// Assert that the cursor lies within a region.
+    ///
+    /// Useful for focus testing, e.g. checking the cursor moved into a
+    /// dialog after it opened.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region that should contain the cursor
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - Failed to read the cursor position
+    /// - The cursor is outside the region
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_floating_region("dialog", 40, 10);
+    ///
+    /// harness.assert_cursor_in_region(&tester, "dialog")?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn assert_cursor_in_region(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<()> {
+        let bounds = lookup_region(tester, region_name)?;
+        check_cursor_in(&format!("region '{}'", region_name), self.region_cursor()?, &bounds)
     }
 
-    fn verify_resize(
-        &mut self,
-        tester: &mut UiRegionTester,
-        new_width: u16,
-        new_height: u16,
-    ) -> IpcResult<()> {
-        // Send resize request
-        self.resize(new_width, new_height)?;
-
-        // Wait briefly for the resize to take effect
+    /// Assert that the cursor lies within the content area.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cursor position can't be read or is outside
+    /// the content area.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    ///
+    /// harness.assert_cursor_in_content(&tester)?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn assert_cursor_in_content(&self, tester: &UiRegionTester) -> IpcResult<()> {
+        check_cursor_in("content area", self.region_cursor()?, &tester.content_area())
+    }
+
+    /// Get the cursor position (row, col) checked by
+    /// [`assert_cursor_in_region`](Self::assert_cursor_in_region) and
+    /// [`assert_cursor_in_content`](Self::assert_cursor_in_content).
+    ///
+    /// # Errors
+    ///
+    /// The default returns [`IpcError::InvalidData`], as the required methods
+    /// give no way to read the cursor. Implement this to enable the cursor
+    /// assertions.
+    fn region_cursor(&self) -> IpcResult<(u16, u16)> {
+        Err(IpcError::InvalidData(
+            "This harness does not report a cursor position for region checks".to_string(),
+        ))
+    }
+
+    /// Verify resize event correctly calculated terminal dimensions.
+    ///
+    /// This method resizes the terminal and verifies that the content area
+    /// and fixed regions are recalculated correctly.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions (will be updated)
+    /// * `new_width` - New terminal width
+    /// * `new_height` - New terminal height
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Failed to resize the terminal
+    /// - The terminal dimensions don't match after resize
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// let mut tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    ///
+    /// harness.verify_resize(&mut tester, 100, 30)?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn verify_resize(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+    ) -> IpcResult<()>;
+
+    /// Verify a resize and that the app redrew in response to it.
+    ///
+    /// Like [`verify_resize`](Self::verify_resize), but after resizing it
+    /// waits up to `timeout` for the screen to change. Matching dimensions
+    /// only show that the terminal took the new size; the redraw shows that
+    /// the app handled `SIGWINCH`. A Scarab harness waits for the sequence
+    /// number to move on; a PTY harness waits for the screen contents to
+    /// differ from how they read right after the resize. The default
+    /// implementation calls [`verify_resize`](Self::verify_resize) and then
+    /// re-reads [`content_area_contents`](Self::content_area_contents)
+    /// every 10ms until it changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions (will be updated)
+    /// * `new_width` - New terminal width
+    /// * `new_height` - New terminal height
+    /// * `timeout` - How long to wait for the redraw
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Failed to resize the terminal
+    /// - The terminal dimensions don't match after resize
+    /// - The app did not redraw within `timeout` ([`IpcError::ResizeNotRedrawn`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use std::time::Duration;
+    ///
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// let mut tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    ///
+    /// harness.verify_resize_redraw(&mut tester, 100, 30, Duration::from_secs(2))?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn verify_resize_redraw(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+        timeout: Duration,
+    ) -> IpcResult<()> {
+        self.verify_resize(tester, new_width, new_height)?;
+
+        let baseline = self.content_area_contents(tester)?;
+        let redrawn = poll_text(timeout, || {
+            let text = self.content_area_contents(tester)?;
+            Ok((text != baseline).then_some(text))
+        });
+        match redrawn {
+            Err(IpcError::Timeout(_)) => Err(IpcError::ResizeNotRedrawn {
+                cols: new_width,
+                rows: new_height,
+                timeout,
+            }),
+            other => other.map(drop),
+        }
+    }
+
+    /// Assert that every Sixel image on screen lies within a specific region.
+    ///
+    /// Each image's cell footprint must fit entirely inside the region. For
+    /// [`TuiTestHarness`](crate::TuiTestHarness) the footprint uses the
+    /// screen's cell size (see
+    /// [`ScreenState::cell_footprint`](crate::ScreenState::cell_footprint)).
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region the images must stay within
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - No Sixel image is on screen
+    /// - Any image spills outside the region
+    /// - The harness does not track Sixel graphics. This is always the case
+    ///   for `ScarabTestHarness`, whose shared memory carries no graphics, and
+    ///   for implementors that do not override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use terminal_testlib::{
+    ///     regions::{RegionAnchor, UiRegion, UiRegionTestExt, UiRegionTester},
+    ///     TuiTestHarness,
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// let tester = UiRegionTester::new(80, 24).with_region(UiRegion {
+    ///     name: "preview".to_string(),
+    ///     anchor: RegionAnchor::Right,
+    ///     size: 40,
+    /// });
+    ///
+    /// harness.assert_sixel_in_region(&tester, "preview")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn assert_sixel_in_region(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<()> {
+        lookup_region(tester, region_name)?;
+        Err(IpcError::InvalidData("This harness does not track Sixel graphics".to_string()))
+    }
+
+    /// Wait until a region shows any non-blank content.
+    ///
+    /// For panes that fill in asynchronously, when the exact text they will
+    /// show is not known. The region is re-read after each refresh of the
+    /// terminal state; the default implementation, for harnesses with no
+    /// refresh to wait on, re-reads it every 10ms.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to wait on
+    /// * `timeout` - How long to wait
+    ///
+    /// # Returns
+    ///
+    /// The region text, as from [`region_contents`](Self::region_contents).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - Failed to read the terminal grid
+    /// - The region is still blank after `timeout` ([`IpcError::RegionTimeout`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use std::time::Duration;
+    ///
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_right_sidebar(30);
+    ///
+    /// let preview =
+    ///     harness.wait_for_region_nonempty(&tester, "right_sidebar", Duration::from_secs(5))?;
+    /// assert!(preview.contains("README"));
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn wait_for_region_nonempty(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        poll_region(self, tester, region_name, timeout, region_nonempty)
+    }
+
+    /// Wait until a region's text differs from a captured baseline.
+    ///
+    /// Capture `baseline` with [`region_contents`](Self::region_contents)
+    /// before triggering the update, then wait for the region to move on
+    /// from it. Polls like
+    /// [`wait_for_region_nonempty`](Self::wait_for_region_nonempty).
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to wait on
+    /// * `baseline` - Region text to wait to change from
+    /// * `timeout` - How long to wait
+    ///
+    /// # Returns
+    ///
+    /// The new region text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The region doesn't exist
+    /// - Failed to read the terminal grid
+    /// - The region still matches `baseline` after `timeout`
+    ///   ([`IpcError::RegionTimeout`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use std::time::Duration;
+    ///
+    /// use terminal_testlib::{
+    ///     scarab::ScarabTestHarness,
+    ///     regions::{UiRegionTester, UiRegionTestExt},
+    /// };
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    ///
+    /// let before = harness.region_contents(&tester, "status_bar")?;
+    /// harness.send_input("\x13")?; // Ctrl+S
+    /// let after =
+    ///     harness.wait_for_region_change(&tester, "status_bar", &before, Duration::from_secs(2))?;
+    /// assert!(after.contains("Saved"));
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    fn wait_for_region_change(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        baseline: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        poll_region(self, tester, region_name, timeout, |text| text != baseline)
+    }
+}
+
+impl UiRegionTestExt for crate::TuiTestHarness {
+    fn region_contents(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<String> {
+        Ok(self.region_lines(tester, region_name)?.join("\n"))
+    }
+
+    fn region_lines(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<Vec<String>> {
+        let bounds = lookup_region(tester, region_name)?;
+        Ok(extract_region_lines(&self.screen_contents(), &bounds))
+    }
+
+    fn region_cells(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+    ) -> IpcResult<Vec<Vec<(char, CellAttributes)>>> {
+        let bounds = lookup_region(tester, region_name)?;
+        let cells = self.state().cells();
+        let end_row = bounds.row.saturating_add(bounds.height);
+
+        Ok(cells
+            .iter()
+            .take(end_row as usize)
+            .skip(bounds.row as usize)
+            .map(|line| {
+                (bounds.col..bounds.col.saturating_add(bounds.width))
+                    .map(|col| match line.get(col as usize) {
+                        Some(cell) => (cell.c, screen_cell_attributes(cell)),
+                        None => (' ', CellAttributes::default()),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn content_area_contents(&self, tester: &UiRegionTester) -> IpcResult<String> {
+        let bounds = tester.content_area();
+        Ok(extract_region_lines(&self.screen_contents(), &bounds).join("\n"))
+    }
+
+    fn assert_not_in_region(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        text: &str,
+    ) -> IpcResult<()> {
+        let bounds = lookup_region(tester, region_name)?;
+        let region_content = self.region_contents(tester, region_name)?;
+        check_not_in_region(region_name, &bounds, &region_content, text)
+    }
+
+    fn assert_region_contains(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        expected: &str,
+    ) -> IpcResult<()> {
+        let region_content = self.region_contents(tester, region_name)?;
+        check_region_contains(region_name, &region_content, expected)
+    }
+
+    fn region_cursor(&self) -> IpcResult<(u16, u16)> {
+        Ok(self.cursor_position())
+    }
+
+    fn verify_resize(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+    ) -> IpcResult<()> {
+        self.resize(new_width, new_height)
+            .map_err(|e| IpcError::InvalidData(format!("Resize failed: {}", e)))?;
+        check_resized((new_width, new_height), self.state().size())?;
+
+        tester.screen_width = new_width;
+        tester.screen_height = new_height;
+
+        Ok(())
+    }
+
+    fn verify_resize_redraw(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+        timeout: Duration,
+    ) -> IpcResult<()> {
+        // Take in output still pending from before the resize, so it is not
+        // mistaken for the redraw.
+        self.update_state()
+            .map_err(|e| IpcError::InvalidData(format!("Reading terminal output failed: {}", e)))?;
+        self.resize(new_width, new_height)
+            .map_err(|e| IpcError::InvalidData(format!("Resize failed: {}", e)))?;
+        check_resized((new_width, new_height), self.state().size())?;
+
+        let baseline = self.state().contents();
+        let redrawn =
+            self.wait_until(|state| state.contents() != baseline, "redraw after resize", timeout);
+        match redrawn {
+            Ok(()) => {}
+            Err(crate::TermTestError::Timeout { .. }) => {
+                return Err(IpcError::ResizeNotRedrawn {
+                    cols: new_width,
+                    rows: new_height,
+                    timeout,
+                })
+            }
+            Err(e) => {
+                return Err(IpcError::InvalidData(format!(
+                    "Waiting for redraw after resize failed: {}",
+                    e
+                )))
+            }
+        }
+
+        tester.screen_width = new_width;
+        tester.screen_height = new_height;
+
+        Ok(())
+    }
+
+    fn assert_sixel_in_region(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<()> {
+        let bounds = lookup_region(tester, region_name)?;
+        let state = self.state();
+        let footprints: Vec<RegionBounds> = state
+            .sixel_regions()
+            .iter()
+            .map(|sixel| state.cell_footprint(sixel).into())
+            .collect();
+        check_sixels_in(region_name, &footprints, &bounds)
+    }
+
+    fn wait_for_region_nonempty(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        wait_for_harness_region(self, tester, region_name, timeout, region_nonempty)
+    }
+
+    fn wait_for_region_change(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        baseline: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        wait_for_harness_region(self, tester, region_name, timeout, |text| text != baseline)
+    }
+}
+
+#[cfg(feature = "scarab")]
+impl UiRegionTestExt for crate::scarab::ScarabTestHarness {
+    fn region_contents(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<String> {
+        Ok(self.region_lines(tester, region_name)?.join("\n"))
+    }
+
+    fn region_lines(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<Vec<String>> {
+        let bounds = lookup_region(tester, region_name)?;
+
+        let full_grid = self.grid_contents()?;
+        Ok(extract_region_lines(&full_grid, &bounds))
+    }
+
+    fn region_cells(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+    ) -> IpcResult<Vec<Vec<(char, CellAttributes)>>> {
+        let bounds = lookup_region(tester, region_name)?;
+        let (cols, rows) = self.dimensions();
+        let lines = self.region_lines(tester, region_name)?;
+
+        lines
+            .iter()
+            .zip(bounds.row..)
+            .map(|(line, row)| {
+                line.chars()
+                    .zip(bounds.col..)
+                    .map(|(glyph, col)| {
+                        let attrs = if row < rows && col < cols {
+                            self.cell_attrs_at(row, col)?
+                        } else {
+                            CellAttributes::default()
+                        };
+                        Ok((glyph, attrs))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn content_area_contents(&self, tester: &UiRegionTester) -> IpcResult<String> {
+        let bounds = tester.content_area();
+        let full_grid = self.grid_contents()?;
+        Ok(extract_region_lines(&full_grid, &bounds).join("\n"))
+    }
+
+    fn assert_not_in_region(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        text: &str,
+    ) -> IpcResult<()> {
+        let bounds = lookup_region(tester, region_name)?;
+        let region_content = self.region_contents(tester, region_name)?;
+        check_not_in_region(region_name, &bounds, &region_content, text)
+    }
+
+    fn assert_region_contains(
+        &self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        expected: &str,
+    ) -> IpcResult<()> {
+        let region_content = self.region_contents(tester, region_name)?;
+        check_region_contains(region_name, &region_content, expected)
+    }
+
+    fn region_cursor(&self) -> IpcResult<(u16, u16)> {
+        self.cursor_position()
+    }
+
+    fn verify_resize(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+    ) -> IpcResult<()> {
+        // Send resize request
+        self.resize(new_width, new_height)?;
+
+        // Wait briefly for the resize to take effect
         std::thread::sleep(std::time::Duration::from_millis(100));
 
         // Refresh the shared memory to get updated dimensions
         self.refresh()?;
 
-        // Verify the dimensions
-        let (actual_width, actual_height) = self.dimensions();
-        if actual_width != new_width || actual_height != new_height {
-            return Err(IpcError::InvalidData(format!(
-                "Resize verification failed: expected {}x{}, got {}x{}",
-                new_width, new_height, actual_width, actual_height
-            )));
-        }
+        // Verify the dimensions
+        check_resized((new_width, new_height), self.dimensions())?;
 
         // Update the tester's dimensions
         tester.screen_width = new_width;
@@ -946,46 +1824,348 @@ impl UiRegionTestExt for crate::scarab::ScarabTestHarness {
 
         Ok(())
     }
+
+    fn verify_resize_redraw(
+        &mut self,
+        tester: &mut UiRegionTester,
+        new_width: u16,
+        new_height: u16,
+        timeout: Duration,
+    ) -> IpcResult<()> {
+        self.resize_and_wait_for_redraw(new_width, new_height, timeout)?;
+
+        tester.screen_width = new_width;
+        tester.screen_height = new_height;
+
+        Ok(())
+    }
+
+    fn wait_for_region_nonempty(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        wait_for_scarab_region(self, tester, region_name, timeout, region_nonempty)
+    }
+
+    fn wait_for_region_change(
+        &mut self,
+        tester: &UiRegionTester,
+        region_name: &str,
+        baseline: &str,
+        timeout: Duration,
+    ) -> IpcResult<String> {
+        wait_for_scarab_region(self, tester, region_name, timeout, |text| text != baseline)
+    }
 }
 
-/// Helper function to extract a region from the full grid.
-#[cfg(feature = "scarab")]
-fn extract_region_from_grid(
-    grid: &str,
-    screen_width: u16,
-    bounds: &RegionBounds,
+/// Whether region text has anything besides whitespace.
+fn region_nonempty(text: &str) -> bool {
+    !text.trim().is_empty()
+}
+
+/// How often [`poll_text`] re-reads the screen.
+const REGION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Call `read` every [`REGION_POLL_INTERVAL`] until it yields a value.
+///
+/// Returns [`IpcError::Timeout`] if nothing is yielded within `timeout`.
+fn poll_text(
+    timeout: Duration,
+    mut read: impl FnMut() -> IpcResult<Option<String>>,
+) -> IpcResult<String> {
+    let start = Instant::now();
+    loop {
+        if let Some(text) = read()? {
+            return Ok(text);
+        }
+        if start.elapsed() >= timeout {
+            return Err(IpcError::Timeout(timeout));
+        }
+        std::thread::sleep(REGION_POLL_INTERVAL);
+    }
+}
+
+/// Poll a region of any harness until `done` accepts its text.
+///
+/// Backs the default `wait_for_region_*` methods.
+fn poll_region<H: UiRegionTestExt + ?Sized>(
+    harness: &H,
+    tester: &UiRegionTester,
+    region_name: &str,
+    timeout: Duration,
+    done: impl Fn(&str) -> bool,
+) -> IpcResult<String> {
+    let mut last_seen = String::new();
+    let result = poll_text(timeout, || {
+        let text = harness.region_contents(tester, region_name)?;
+        if done(&text) {
+            return Ok(Some(text));
+        }
+        last_seen = text;
+        Ok(None)
+    });
+
+    match result {
+        Err(IpcError::Timeout(timeout)) => Err(IpcError::RegionTimeout {
+            region: region_name.to_string(),
+            last_seen,
+            timeout,
+        }),
+        other => other,
+    }
+}
+
+/// Wait on a region of a PTY harness until `done` accepts its text.
+fn wait_for_harness_region(
+    harness: &mut crate::TuiTestHarness,
+    tester: &UiRegionTester,
+    region_name: &str,
+    timeout: Duration,
+    done: impl Fn(&str) -> bool,
 ) -> IpcResult<String> {
-    let lines: Vec<&str> = grid.lines().collect();
+    let bounds = lookup_region(tester, region_name)?;
+    let region_text =
+        |state: &crate::ScreenState| extract_region_lines(&state.contents(), &bounds).join("\n");
+
+    let description = format!("region '{}'", region_name);
+    match harness.wait_until(|state| done(&region_text(state)), &description, timeout) {
+        Ok(()) => Ok(region_text(harness.state())),
+        Err(crate::TermTestError::Timeout { .. }) => Err(IpcError::RegionTimeout {
+            region: region_name.to_string(),
+            last_seen: region_text(harness.state()),
+            timeout,
+        }),
+        Err(e) => Err(IpcError::InvalidData(format!(
+            "Waiting for region '{}' failed: {}",
+            region_name, e
+        ))),
+    }
+}
 
-    let mut result = String::new();
-    let start_row = bounds.row as usize;
-    let end_row = (bounds.row + bounds.height) as usize;
-    let start_col = bounds.col as usize;
-    let end_col = (bounds.col + bounds.width) as usize;
+/// Wait on a region of a Scarab harness until `done` accepts its text.
+#[cfg(feature = "scarab")]
+fn wait_for_scarab_region(
+    harness: &mut crate::scarab::ScarabTestHarness,
+    tester: &UiRegionTester,
+    region_name: &str,
+    timeout: Duration,
+    done: impl Fn(&str) -> bool,
+) -> IpcResult<String> {
+    lookup_region(tester, region_name)?;
 
-    for row_idx in start_row..end_row {
-        if row_idx >= lines.len() {
-            break;
+    let mut last_seen = String::new();
+    let result = harness.poll_frames(timeout, |harness| {
+        let text = harness.region_contents(tester, region_name)?;
+        if done(&text) {
+            return Ok(Some(text));
         }
+        last_seen = text;
+        Ok(None)
+    });
+
+    match result {
+        Err(IpcError::Timeout(timeout)) => Err(IpcError::RegionTimeout {
+            region: region_name.to_string(),
+            last_seen,
+            timeout,
+        }),
+        other => other,
+    }
+}
+
+/// Check the dimensions a terminal reports after a resize.
+pub(crate) fn check_resized(expected: (u16, u16), actual: (u16, u16)) -> IpcResult<()> {
+    if actual != expected {
+        return Err(IpcError::InvalidData(format!(
+            "Resize verification failed: expected {}x{}, got {}x{}",
+            expected.0, expected.1, actual.0, actual.1
+        )));
+    }
+    Ok(())
+}
+
+/// Look up a region's bounds by name.
+fn lookup_region(tester: &UiRegionTester, region_name: &str) -> IpcResult<RegionBounds> {
+    tester
+        .region_bounds(region_name)
+        .ok_or_else(|| IpcError::RegionNotFound(region_name.to_string()))
+}
+
+/// Check that text is absent from a region.
+///
+/// On failure the error lists the screen position of each match.
+fn check_not_in_region(
+    region_name: &str,
+    bounds: &RegionBounds,
+    region_content: &str,
+    text: &str,
+) -> IpcResult<()> {
+    if !region_content.contains(text) {
+        return Ok(());
+    }
+
+    let positions = find_in_region(bounds, region_content, text);
+    let location = if positions.is_empty() {
+        String::new()
+    } else {
+        let cells: Vec<String> = positions
+            .iter()
+            .map(|(row, col)| format!("({}, {})", row, col))
+            .collect();
+        format!(" at {}", cells.join(", "))
+    };
+
+    Err(IpcError::InvalidData(format!(
+        "Text '{}' found in region '{}'{} but should not be present.\nRegion content:\n{}",
+        text, region_name, location, region_content
+    )))
+}
 
-        let line = lines[row_idx];
-        let chars: Vec<char> = line.chars().collect();
+/// Screen positions (row, col) of non-overlapping matches of `text` in a
+/// region's contents. Like [`ScreenState::find_all`](crate::ScreenState::find_all),
+/// matches do not span rows.
+fn find_in_region(bounds: &RegionBounds, region_content: &str, text: &str) -> Vec<(u16, u16)> {
+    if text.is_empty() {
+        return Vec::new();
+    }
 
-        if row_idx > start_row {
-            result.push('\n');
+    let mut positions = Vec::new();
+    for (row, line) in region_content.lines().enumerate() {
+        for (byte, _) in line.match_indices(text) {
+            let col = line[..byte].chars().count();
+            positions.push((bounds.row + row as u16, bounds.col + col as u16));
         }
+    }
+    positions
+}
 
-        // Extract the column range
-        for col_idx in start_col..end_col.min(chars.len().max(screen_width as usize)) {
-            if col_idx < chars.len() {
-                result.push(chars[col_idx]);
-            } else {
-                result.push(' ');
-            }
+/// Check that text is present in a region.
+fn check_region_contains(region_name: &str, region_content: &str, expected: &str) -> IpcResult<()> {
+    if !region_content.contains(expected) {
+        return Err(IpcError::InvalidData(format!(
+            "Expected text '{}' not found in region '{}'.\nRegion content:\n{}",
+            expected, region_name, region_content
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that at least one Sixel image is on screen and all lie within bounds.
+///
+/// `footprints` holds the cells covered by each image.
+fn check_sixels_in(
+    region_name: &str,
+    footprints: &[RegionBounds],
+    bounds: &RegionBounds,
+) -> IpcResult<()> {
+    if footprints.is_empty() {
+        return Err(IpcError::InvalidData(format!(
+            "Expected a Sixel image in region '{}' but none is on screen",
+            region_name
+        )));
+    }
+
+    for footprint in footprints {
+        if !bounds.contains_bounds(footprint) {
+            return Err(IpcError::InvalidData(format!(
+                "Sixel image at (row {}, col {}, {}x{} cells) spills outside region '{}' \
+                 (row {}, col {}, {}x{})",
+                footprint.row,
+                footprint.col,
+                footprint.width,
+                footprint.height,
+                region_name,
+                bounds.row,
+                bounds.col,
+                bounds.width,
+                bounds.height
+            )));
         }
     }
 
-    Ok(result)
+    Ok(())
+}
+
+/// Check region text against a regular expression.
+fn check_region_matches(region_name: &str, region_content: &str, pattern: &str) -> IpcResult<()> {
+    let regex = Regex::new(pattern)
+        .map_err(|e| IpcError::InvalidData(format!("Invalid pattern '{}': {}", pattern, e)))?;
+
+    if !regex.is_match(region_content) {
+        return Err(IpcError::InvalidData(format!(
+            "Pattern '{}' did not match region '{}'.\nRegion content:\n{}",
+            pattern, region_name, region_content
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that a cursor position lies within bounds.
+fn check_cursor_in(label: &str, cursor: (u16, u16), bounds: &RegionBounds) -> IpcResult<()> {
+    let (row, col) = cursor;
+    if !bounds.contains(row, col) {
+        return Err(IpcError::InvalidData(format!(
+            "Cursor at ({}, {}) is outside {} (row {}, col {}, {}x{})",
+            row, col, label, bounds.row, bounds.col, bounds.width, bounds.height
+        )));
+    }
+
+    Ok(())
+}
+
+/// xterm's default colors for palette indices 0-15, as RGBA.
+const XTERM_ANSI_COLORS: [u32; 16] = [
+    0x000000FF, 0xCD0000FF, 0x00CD00FF, 0xCDCD00FF, 0x0000EEFF, 0xCD00CDFF, 0x00CDCDFF, 0xE5E5E5FF,
+    0x7F7F7FFF, 0xFF0000FF, 0x00FF00FF, 0xFFFF00FF, 0x5C5CFFFF, 0xFF00FFFF, 0x00FFFFFF, 0xFFFFFFFF,
+];
+
+/// Convert a [`ScreenState`](crate::ScreenState) cell's style to shared
+/// memory attributes.
+///
+/// Palette indices are resolved with xterm's defaults; the terminal's
+/// default color becomes `0`.
+fn screen_cell_attributes(cell: &crate::screen::Cell) -> CellAttributes {
+    let rgba = |index: Option<u8>| match index {
+        None => 0,
+        Some(i) => crate::theme::rgb_from_256(i).unwrap_or_else(|| XTERM_ANSI_COLORS[i as usize]),
+    };
+
+    let mut flags = CellFlags::empty();
+    flags.set(CellFlags::BOLD, cell.bold);
+    flags.set(CellFlags::ITALIC, cell.italic);
+    flags.set(CellFlags::UNDERLINE, cell.underline);
+
+    CellAttributes {
+        fg: rgba(cell.fg),
+        bg: rgba(cell.bg),
+        flags: flags.bits(),
+        reserved: 0,
+    }
+}
+
+/// Helper function to extract a region from the full grid, one line per row.
+///
+/// Each line is exactly `bounds.width` characters: columns are clamped to
+/// `[col, col + width)` and rows shorter than the region are padded with
+/// spaces.
+fn extract_region_lines(grid: &str, bounds: &RegionBounds) -> Vec<String> {
+    let start_col = bounds.col as usize;
+    let width = bounds.width as usize;
+
+    grid.lines()
+        .skip(bounds.row as usize)
+        .take(bounds.height as usize)
+        .map(|line| {
+            let mut row: String = line.chars().skip(start_col).take(width).collect();
+            let len = row.chars().count();
+            row.extend(std::iter::repeat(' ').take(width - len));
+            row
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -1226,4 +2406,574 @@ mod tests {
         assert!(!bounds.contains(0, 1));
         assert!(!bounds.contains(1, 0));
     }
+
+    #[test]
+    fn test_floating_region_even_dimensions() {
+        let tester = UiRegionTester::new(80, 24).with_floating_region("dialog", 40, 10);
+
+        let dialog = tester.region_bounds("dialog").unwrap();
+        assert_eq!(dialog, RegionBounds::new(7, 20, 40, 10));
+
+        // Floating regions don't shrink the content area
+        assert_eq!(tester.content_area(), RegionBounds::new(0, 0, 80, 24));
+        assert!(tester.is_in_region("dialog", 7, 20));
+        assert!(tester.is_in_region("dialog", 16, 59));
+        assert!(!tester.is_in_region("dialog", 6, 20));
+        assert!(!tester.is_in_region("dialog", 17, 59));
+        assert!(!tester.is_in_region("dialog", 7, 60));
+    }
+
+    #[test]
+    fn test_floating_region_odd_dimensions() {
+        // 41 spare columns and 15 spare rows: offsets round down
+        let tester = UiRegionTester::new(81, 25).with_floating_region("dialog", 40, 10);
+        let dialog = tester.region_bounds("dialog").unwrap();
+        assert_eq!(dialog, RegionBounds::new(7, 20, 40, 10));
+
+        // Odd box on an even screen
+        let tester = UiRegionTester::new(80, 24).with_floating_region("dialog", 41, 11);
+        let dialog = tester.region_bounds("dialog").unwrap();
+        assert_eq!(dialog, RegionBounds::new(6, 19, 41, 11));
+    }
+
+    #[test]
+    fn test_floating_region_centers_in_content_area() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_floating_region("dialog", 20, 5)
+            .with_tab_bar(2)
+            .with_status_bar(1)
+            .with_left_sidebar(20);
+
+        // Content area is rows 2..23, cols 20..80
+        let dialog = tester.region_bounds("dialog").unwrap();
+        assert_eq!(dialog, RegionBounds::new(10, 40, 20, 5));
+    }
+
+    #[test]
+    fn test_floating_region_clamped_to_content_area() {
+        let tester = UiRegionTester::new(40, 10)
+            .with_status_bar(1)
+            .with_floating_region("dialog", 60, 20);
+
+        let dialog = tester.region_bounds("dialog").unwrap();
+        assert_eq!(dialog, RegionBounds::new(0, 0, 40, 9));
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_tab_bar(2)
+            .with_status_bar(1)
+            .with_left_sidebar(20)
+            .with_right_sidebar(15)
+            .with_floating_region("dialog", 40, 10);
+        assert!(tester.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_overlapping_header_and_footer() {
+        // A header and a footer that together exceed the screen height
+        let header = UiRegion {
+            name: "header".to_string(),
+            anchor: RegionAnchor::Top,
+            size: 15,
+        };
+        let footer = UiRegion {
+            name: "footer".to_string(),
+            anchor: RegionAnchor::Bottom,
+            size: 15,
+        };
+        let tester = UiRegionTester::new(80, 24)
+            .with_region(header)
+            .with_region(footer);
+
+        assert_eq!(tester.validate(), Err(vec![("header".to_string(), "footer".to_string())]));
+    }
+
+    #[test]
+    fn test_validate_reports_every_pair() {
+        // Sidebars defined before the bars start at row 0 and span the full height
+        let tester = UiRegionTester::new(80, 24)
+            .with_left_sidebar(20)
+            .with_right_sidebar(20)
+            .with_tab_bar(1)
+            .with_status_bar(1);
+
+        let conflicts = tester.validate().unwrap_err();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("left_sidebar".to_string(), "tab_bar".to_string()),
+                ("left_sidebar".to_string(), "status_bar".to_string()),
+                ("right_sidebar".to_string(), "tab_bar".to_string()),
+                ("right_sidebar".to_string(), "status_bar".to_string()),
+            ]
+        );
+    }
+
+    /// Assert that panes tile the content area exactly once.
+    fn assert_exact_cover(tester: &UiRegionTester, panes: &[(String, RegionBounds)]) {
+        let content = tester.content_area();
+        let (width, height) = tester.screen_dimensions();
+        for row in 0..height {
+            for col in 0..width {
+                let hits = panes.iter().filter(|(_, b)| b.contains(row, col)).count();
+                let expected = usize::from(content.contains(row, col));
+                assert_eq!(hits, expected, "cell ({}, {}) covered {} times", row, col, hits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_content_columns() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_tab_bar(2)
+            .with_left_sidebar(20);
+
+        // 60 columns over 7 panes: 4 panes of 9, 3 panes of 8
+        let panes = tester.split_content_columns(7);
+        assert_eq!(panes.len(), 7);
+        let widths: Vec<u16> = panes.iter().map(|(_, b)| b.width).collect();
+        assert_eq!(widths, vec![9, 9, 9, 9, 8, 8, 8]);
+        assert_eq!(panes[0], ("pane_0".to_string(), RegionBounds::new(2, 20, 9, 22)));
+        assert_eq!(panes[6], ("pane_6".to_string(), RegionBounds::new(2, 72, 8, 22)));
+        assert_exact_cover(&tester, &panes);
+    }
+
+    #[test]
+    fn test_split_content_grid() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_tab_bar(1)
+            .with_status_bar(1)
+            .with_right_sidebar(3);
+
+        // 22 rows x 77 columns split 3 x 4
+        let panes = tester.split_content_grid(3, 4);
+        assert_eq!(panes.len(), 12);
+        let names: Vec<&str> = panes.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names[0], "pane_0");
+        assert_eq!(names[11], "pane_11");
+
+        assert_eq!(panes[0].1, RegionBounds::new(1, 0, 20, 8));
+        assert_eq!(panes[3].1, RegionBounds::new(1, 58, 19, 8));
+        assert_eq!(panes[4].1, RegionBounds::new(9, 0, 20, 7));
+        assert_eq!(panes[11].1, RegionBounds::new(16, 58, 19, 7));
+        assert_exact_cover(&tester, &panes);
+    }
+
+    #[test]
+    fn test_split_content_zero_panes() {
+        let tester = UiRegionTester::new(80, 24);
+        assert!(tester.split_content_columns(0).is_empty());
+        assert!(tester.split_content_grid(0, 3).is_empty());
+        assert!(tester.split_content_grid(3, 0).is_empty());
+    }
+
+    #[test]
+    fn test_extract_region_lines() {
+        let grid = "tab one\nline two\nbody text\nstatus";
+        let lines = extract_region_lines(grid, &RegionBounds::new(1, 5, 4, 2));
+        assert_eq!(lines, vec!["two ".to_string(), "text".to_string()]);
+
+        // Rows past the end of the grid are dropped
+        let lines = extract_region_lines(grid, &RegionBounds::new(3, 0, 10, 5));
+        assert_eq!(lines, vec!["status    ".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_region_lines_right_sidebar() {
+        let tester = UiRegionTester::new(12, 2).with_right_sidebar(4);
+        let bounds = tester.region_bounds("right_sidebar").unwrap();
+
+        // Padded grid wider than the screen must not leak into the sidebar
+        let grid = "content|side|extra\nmore    bar |extra";
+        let lines = extract_region_lines(grid, &bounds);
+        assert_eq!(lines, vec!["side".to_string(), "bar ".to_string()]);
+
+        let grid = "content side\nmore    bar ";
+        let lines = extract_region_lines(grid, &bounds);
+        assert_eq!(lines, vec!["side".to_string(), "bar ".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_region_lines_short_rows() {
+        let tester = UiRegionTester::new(12, 3).with_right_sidebar(4);
+        let bounds = tester.region_bounds("right_sidebar").unwrap();
+
+        // Trimmed rows: one ends inside the sidebar, one before it, one empty
+        let grid = "content si\nshort\n\n";
+        let lines = extract_region_lines(grid, &bounds);
+        assert_eq!(lines, vec!["si  ".to_string(), "    ".to_string(), "    ".to_string()]);
+    }
+
+    #[test]
+    fn test_check_region_matches() {
+        let status = "NORMAL  main.rs   14:05 ";
+        assert!(check_region_matches("status_bar", status, r"\b\d{2}:\d{2}\b").is_ok());
+
+        let status = "NORMAL  main.rs   --:-- ";
+        let err = check_region_matches("status_bar", status, r"\b\d{2}:\d{2}\b").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("region 'status_bar'"));
+        assert!(message.contains("--:--"));
+
+        assert!(check_region_matches("status_bar", status, r"(").is_err());
+    }
+
+    #[test]
+    fn test_check_cursor_in() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_status_bar(1)
+            .with_floating_region("dialog", 40, 10);
+        let dialog = tester.region_bounds("dialog").unwrap();
+
+        assert!(check_cursor_in("region 'dialog'", (7, 20), &dialog).is_ok());
+        assert!(check_cursor_in("content area", (22, 0), &tester.content_area()).is_ok());
+
+        let err = check_cursor_in("region 'dialog'", (0, 0), &dialog).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid shared memory data: Cursor at (0, 0) is outside region 'dialog' (row 6, col 20, 40x10)"
+        );
+
+        // Status bar row is not part of the content area
+        assert!(check_cursor_in("content area", (23, 0), &tester.content_area()).is_err());
+    }
+
+    #[test]
+    fn test_not_in_region_reports_leak_position() {
+        let tester = UiRegionTester::new(40, 10).with_status_bar(1);
+        let mut harness = crate::TuiTestHarness::new(40, 10).unwrap();
+
+        harness.state_mut().feed(b"ERROR: disk full");
+        assert!(harness
+            .assert_not_in_region(&tester, "status_bar", "ERROR")
+            .is_ok());
+
+        // The error leaks into the status bar on the last row
+        harness.state_mut().feed(b"\x1b[10;1HNORMAL  ERROR  ERROR");
+        let message = harness
+            .assert_not_in_region(&tester, "status_bar", "ERROR")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("found in region 'status_bar' at (9, 8), (9, 15)"));
+        assert!(message.contains("NORMAL  ERROR  ERROR"));
+    }
+
+    #[test]
+    fn test_wait_for_region_on_pty_screen() {
+        let tester = UiRegionTester::new(40, 10).with_status_bar(1);
+        let mut harness = crate::TuiTestHarness::new(40, 10)
+            .unwrap()
+            .with_background_pump(true)
+            .with_poll_interval(Duration::from_millis(250))
+            .with_clock(crate::FakeClock::new());
+        harness
+            .spawn(portable_pty::CommandBuilder::new("cat"))
+            .unwrap();
+        let timeout = Duration::from_secs(1);
+
+        harness.state_mut().feed(b"\x1b[10;1H");
+        match harness.wait_for_region_nonempty(&tester, "status_bar", timeout) {
+            Err(IpcError::RegionTimeout { region, last_seen, .. }) => {
+                assert_eq!(region, "status_bar");
+                assert!(last_seen.trim().is_empty());
+            }
+            other => panic!("Expected RegionTimeout, got {:?}", other),
+        }
+
+        harness.state_mut().feed(b"NORMAL");
+        let baseline = harness
+            .wait_for_region_nonempty(&tester, "status_bar", timeout)
+            .unwrap();
+        assert_eq!(baseline.trim_end(), "NORMAL");
+        assert!(matches!(
+            harness.wait_for_region_change(&tester, "status_bar", &baseline, timeout),
+            Err(IpcError::RegionTimeout { .. })
+        ));
+
+        harness.state_mut().feed(b"\rINSERT");
+        let changed = harness
+            .wait_for_region_change(&tester, "status_bar", &baseline, timeout)
+            .unwrap();
+        assert_eq!(changed.trim_end(), "INSERT");
+    }
+
+    #[test]
+    fn test_verify_resize_redraw_on_pty() {
+        let mut tester = UiRegionTester::new(40, 10).with_status_bar(1);
+        let mut harness = crate::TuiTestHarness::new(40, 10)
+            .unwrap()
+            .with_background_pump(true);
+        let mut cmd = portable_pty::CommandBuilder::new("sh");
+        cmd.args([
+            "-c",
+            "trap 'echo redrawn' WINCH; echo ready; while :; do sleep 0.1; done",
+        ]);
+        harness.spawn(cmd).unwrap();
+        harness.wait_for_text("ready").unwrap();
+
+        harness
+            .verify_resize_redraw(&mut tester, 50, 12, Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(tester.screen_dimensions(), (50, 12));
+        harness.wait_for_text("redrawn").unwrap();
+
+        // cat ignores SIGWINCH, so the size changes but nothing is redrawn
+        let clock = crate::FakeClock::new();
+        let mut harness = crate::TuiTestHarness::new(40, 10)
+            .unwrap()
+            .with_background_pump(true)
+            .with_poll_interval(Duration::from_millis(250))
+            .with_clock(clock.clone());
+        harness
+            .spawn(portable_pty::CommandBuilder::new("cat"))
+            .unwrap();
+        let timeout = Duration::from_secs(1);
+
+        match harness.verify_resize_redraw(&mut tester, 60, 20, timeout) {
+            Err(IpcError::ResizeNotRedrawn { cols, rows, timeout: t }) => {
+                assert_eq!((cols, rows, t), (60, 20, timeout));
+            }
+            other => panic!("Expected ResizeNotRedrawn, got {:?}", other),
+        }
+        assert_eq!(harness.state().size(), (60, 20));
+        assert_eq!(tester.screen_dimensions(), (50, 12));
+    }
+
+    #[test]
+    fn test_unknown_region_is_region_not_found() {
+        let tester = UiRegionTester::new(40, 10).with_status_bar(1);
+        let harness = crate::TuiTestHarness::new(40, 10).unwrap();
+
+        match harness.assert_region_contains(&tester, "sidebar", "x") {
+            Err(IpcError::RegionNotFound(name)) => assert_eq!(name, "sidebar"),
+            other => panic!("Expected RegionNotFound, got {:?}", other),
+        }
+        assert!(matches!(
+            harness.assert_not_in_region(&tester, "sidebar", "x"),
+            Err(IpcError::RegionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_sixel_in_region() {
+        let tester = UiRegionTester::new(80, 24)
+            .with_status_bar(1)
+            .with_right_sidebar(30);
+        let mut harness = crate::TuiTestHarness::new(80, 24).unwrap();
+
+        // Nothing rendered yet
+        assert!(harness
+            .assert_sixel_in_region(&tester, "right_sidebar")
+            .is_err());
+
+        // 160x60 pixels at (2, 50) covers 16x3 cells: fits the sidebar
+        harness
+            .state_mut()
+            .feed(b"\x1b[3;51H\x1bPq\"1;1;160;60#0~\x1b\\");
+        let sidebar = tester.region_bounds("right_sidebar").unwrap();
+        assert!(harness.state().sixels_within(&sidebar));
+        assert!(!harness.state().sixels_within(&tester.content_area()));
+        assert!(harness
+            .assert_sixel_in_region(&tester, "right_sidebar")
+            .is_ok());
+        assert!(harness.assert_sixel_in_region(&tester, "missing").is_err());
+    }
+
+    #[test]
+    fn test_sixel_overflowing_right_edge() {
+        let tester = UiRegionTester::new(80, 24).with_right_sidebar(30);
+        let mut harness = crate::TuiTestHarness::new(80, 24).unwrap();
+
+        // 240 pixels wide at col 60 covers cols 60..84, past the screen edge
+        harness
+            .state_mut()
+            .feed(b"\x1b[3;61H\x1bPq\"1;1;240;60#0~\x1b\\");
+        let sidebar = tester.region_bounds("right_sidebar").unwrap();
+        assert!(!harness.state().sixels_within(&sidebar));
+
+        let message = harness
+            .assert_sixel_in_region(&tester, "right_sidebar")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("(row 2, col 60, 24x3 cells)"));
+        assert!(message.contains("region 'right_sidebar' (row 0, col 50, 30x24)"));
+    }
+
+    /// Implementor with only the required methods, as an external crate
+    /// written against the original trait would have.
+    struct FixedScreen {
+        lines: Vec<String>,
+    }
+
+    impl FixedScreen {
+        fn new(text: &str) -> Self {
+            Self {
+                lines: text.split('\n').map(str::to_string).collect(),
+            }
+        }
+    }
+
+    impl UiRegionTestExt for FixedScreen {
+        fn region_contents(&self, tester: &UiRegionTester, region_name: &str) -> IpcResult<String> {
+            let bounds = lookup_region(tester, region_name)?;
+            Ok(extract_region_lines(&self.lines.join("\n"), &bounds).join("\n"))
+        }
+
+        fn content_area_contents(&self, tester: &UiRegionTester) -> IpcResult<String> {
+            Ok(extract_region_lines(&self.lines.join("\n"), &tester.content_area()).join("\n"))
+        }
+
+        fn assert_not_in_region(
+            &self,
+            tester: &UiRegionTester,
+            region_name: &str,
+            text: &str,
+        ) -> IpcResult<()> {
+            let bounds = lookup_region(tester, region_name)?;
+            let region_content = self.region_contents(tester, region_name)?;
+            check_not_in_region(region_name, &bounds, &region_content, text)
+        }
+
+        fn assert_region_contains(
+            &self,
+            tester: &UiRegionTester,
+            region_name: &str,
+            expected: &str,
+        ) -> IpcResult<()> {
+            let region_content = self.region_contents(tester, region_name)?;
+            check_region_contains(region_name, &region_content, expected)
+        }
+
+        fn verify_resize(
+            &mut self,
+            tester: &mut UiRegionTester,
+            new_width: u16,
+            new_height: u16,
+        ) -> IpcResult<()> {
+            tester.screen_width = new_width;
+            tester.screen_height = new_height;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_region_lines() {
+        let screen = FixedScreen::new("one  \ntwo  \n[ok]");
+        let tester = UiRegionTester::new(5, 3).with_status_bar(2);
+
+        let lines = screen.region_lines(&tester, "status_bar").unwrap();
+        assert_eq!(lines, vec!["two  ", "[ok] "]);
+    }
+
+    #[test]
+    fn test_default_assert_region_matches() {
+        let screen = FixedScreen::new("main.rs\n12:34");
+        let tester = UiRegionTester::new(7, 2).with_status_bar(1);
+
+        assert!(screen
+            .assert_region_matches(&tester, "status_bar", r"\d{2}:\d{2}")
+            .is_ok());
+        assert!(screen
+            .assert_region_matches(&tester, "status_bar", r"main")
+            .is_err());
+    }
+
+    #[test]
+    fn test_default_cursor_assertions_need_region_cursor() {
+        let screen = FixedScreen::new("$ \n");
+        let tester = UiRegionTester::new(2, 2).with_status_bar(1);
+
+        // Without region_cursor there is no cursor to check
+        assert!(matches!(
+            screen.assert_cursor_in_content(&tester),
+            Err(IpcError::InvalidData(_))
+        ));
+        assert!(matches!(
+            screen.assert_cursor_in_region(&tester, "status_bar"),
+            Err(IpcError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_assert_sixel_in_region() {
+        let screen = FixedScreen::new("");
+        let tester = UiRegionTester::new(80, 24).with_right_sidebar(30);
+
+        assert!(matches!(
+            screen.assert_sixel_in_region(&tester, "right_sidebar"),
+            Err(IpcError::InvalidData(_))
+        ));
+        assert!(matches!(
+            screen.assert_sixel_in_region(&tester, "missing"),
+            Err(IpcError::RegionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_region_cells() {
+        let screen = FixedScreen::new("");
+        let tester = UiRegionTester::new(80, 24).with_tab_bar(1);
+
+        assert!(matches!(screen.region_cells(&tester, "tab_bar"), Err(IpcError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_region_cells_on_pty_screen() {
+        let tester = UiRegionTester::new(10, 3).with_tab_bar(1);
+        let mut harness = crate::TuiTestHarness::new(10, 3).unwrap();
+        harness
+            .state_mut()
+            .feed(b" \x1b[1;31;47m1\x1b[0m main \x1b[38;5;196mx");
+
+        let cells = harness.region_cells(&tester, "tab_bar").unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].len(), 10);
+
+        let (glyph, attrs) = cells[0][1];
+        assert_eq!(glyph, '1');
+        assert_eq!(attrs.fg, 0xCD0000FF);
+        assert_eq!(attrs.bg, 0xE5E5E5FF);
+        assert_eq!(attrs.flags, CellFlags::BOLD.bits());
+
+        // Default colors read as 0; 256-color indices resolve through the cube
+        assert_eq!(cells[0][3], ('m', CellAttributes::default()));
+        assert_eq!(cells[0][8].1.fg, 0xFF0000FF);
+    }
+
+    #[test]
+    fn test_default_region_waits() {
+        let mut screen = FixedScreen::new("one  \ntwo  \n[ok]");
+        let tester = UiRegionTester::new(5, 3).with_status_bar(1);
+        let timeout = Duration::from_millis(30);
+
+        let text = screen
+            .wait_for_region_nonempty(&tester, "status_bar", timeout)
+            .unwrap();
+        assert_eq!(text, "[ok] ");
+
+        match screen.wait_for_region_change(&tester, "status_bar", &text, timeout) {
+            Err(IpcError::RegionTimeout { region, last_seen, .. }) => {
+                assert_eq!(region, "status_bar");
+                assert_eq!(last_seen, "[ok] ");
+            }
+            other => panic!("Expected RegionTimeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_verify_resize_redraw() {
+        let mut screen = FixedScreen::new("static");
+        let mut tester = UiRegionTester::new(6, 1);
+
+        match screen.verify_resize_redraw(&mut tester, 8, 2, Duration::from_millis(30)) {
+            Err(IpcError::ResizeNotRedrawn { cols, rows, .. }) => {
+                assert_eq!((cols, rows), (8, 2));
+            }
+            other => panic!("Expected ResizeNotRedrawn, got {:?}", other),
+        }
+        // The resize itself was verified before waiting on the redraw
+        assert_eq!((tester.screen_width, tester.screen_height), (8, 2));
+    }
 }
@@ -60,6 +60,7 @@
 //! - Socket: `/tmp/scarab-daemon.sock`
 //! - Shared memory: `/scarab_shm_v1`
 //! - Image buffer: `/scarab_img_v1`
+//! - Raw output log: `/scarab_out_v1` (optional, see [`DaemonOutputLog`])
 //! - Magic number: `0x5343_5241` ("SCRA")
 //!
 //! # Custom Configuration
@@ -131,17 +132,21 @@
 //! # }
 //! ```
 
-use std::{
-    path::PathBuf,
-    time::Duration,
-};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
-use crate::ipc::{DaemonIpcClient, DaemonSharedMemory, IpcError, IpcResult};
+use crate::clock::{Clock, SystemClock};
+use crate::ipc::{
+    CellDiff, ColorLayer, DaemonIpcClient, DaemonOutputLog, DaemonSharedMemory, IpcError,
+    IpcResult, MockSharedMemory, ShmSnapshot,
+};
+use crate::regions::check_resized;
+use crate::screen::row_mismatch;
 
 // Scarab-specific defaults
 const SCARAB_SOCKET_PATH: &str = "/tmp/scarab-daemon.sock";
 const SCARAB_SHM_PATH: &str = "/scarab_shm_v1";
 const SCARAB_IMAGE_SHM_PATH: &str = "/scarab_img_v1";
+const SCARAB_OUTPUT_SHM_PATH: &str = "/scarab_out_v1";
 const SCARAB_MAGIC: u32 = 0x5343_5241; // "SCRA"
 const SCARAB_VERSION: u32 = 1;
 
@@ -159,12 +164,23 @@ pub struct ScarabConfig {
     /// Path to the shared memory segment for image buffer.
     pub image_shm_path: Option<String>,
 
+    /// Path to the shared memory segment for the raw output log.
+    ///
+    /// Opened on connect if the daemon publishes it; see [`DaemonOutputLog`].
+    pub output_shm_path: Option<String>,
+
     /// Terminal dimensions (cols, rows).
     pub dimensions: Option<(u16, u16)>,
 
     /// Connection timeout.
     pub connect_timeout: Duration,
 
+    /// Number of times to retry connecting before giving up.
+    pub connect_retries: u32,
+
+    /// Delay before the first retry; doubles after each failed attempt.
+    pub retry_interval: Duration,
+
     /// Default timeout for wait operations.
     pub default_timeout: Duration,
 
@@ -178,8 +194,11 @@ impl Default for ScarabConfig {
             socket_path: PathBuf::from(SCARAB_SOCKET_PATH),
             shm_path: SCARAB_SHM_PATH.to_string(),
             image_shm_path: Some(SCARAB_IMAGE_SHM_PATH.to_string()),
+            output_shm_path: Some(SCARAB_OUTPUT_SHM_PATH.to_string()),
             dimensions: Some((80, 24)),
             connect_timeout: Duration::from_secs(5),
+            connect_retries: 5,
+            retry_interval: Duration::from_millis(50),
             default_timeout: Duration::from_secs(10),
             prompt_patterns: vec![
                 "$ ".to_string(),
@@ -223,6 +242,12 @@ impl ScarabConfigBuilder {
         self
     }
 
+    /// Set the raw output log shared memory path.
+    pub fn output_shm_path(mut self, path: impl Into<String>) -> Self {
+        self.config.output_shm_path = Some(path.into());
+        self
+    }
+
     /// Set terminal dimensions.
     pub fn dimensions(mut self, cols: u16, rows: u16) -> Self {
         self.config.dimensions = Some((cols, rows));
@@ -235,6 +260,18 @@ impl ScarabConfigBuilder {
         self
     }
 
+    /// Set how many times to retry connecting.
+    pub fn connect_retries(mut self, retries: u32) -> Self {
+        self.config.connect_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first connection retry.
+    pub fn retry_interval(mut self, interval: Duration) -> Self {
+        self.config.retry_interval = interval;
+        self
+    }
+
     /// Set default wait timeout.
     pub fn default_timeout(mut self, timeout: Duration) -> Self {
         self.config.default_timeout = timeout;
@@ -262,9 +299,19 @@ impl ScarabConfigBuilder {
 /// Scarab-specific shared memory reader.
 ///
 /// Wraps [`DaemonSharedMemory`] with Scarab's magic number and version validation.
+/// For unit tests it can instead wrap a [`MockSharedMemory`].
 #[cfg(target_family = "unix")]
 pub struct ScarabSharedMemory {
-    inner: DaemonSharedMemory,
+    inner: ShmBackend,
+    output: Option<DaemonOutputLog>,
+}
+
+/// Where a [`ScarabSharedMemory`] reads terminal state from.
+#[cfg(target_family = "unix")]
+#[derive(Debug)]
+enum ShmBackend {
+    Daemon(DaemonSharedMemory),
+    Mock(MockSharedMemory),
 }
 
 #[cfg(target_family = "unix")]
@@ -272,6 +319,7 @@ impl std::fmt::Debug for ScarabSharedMemory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ScarabSharedMemory")
             .field("inner", &self.inner)
+            .field("output", &self.output)
             .finish()
     }
 }
@@ -285,63 +333,194 @@ impl ScarabSharedMemory {
             SCARAB_MAGIC,
             SCARAB_VERSION,
         )?;
-        Ok(Self { inner })
+        Ok(Self {
+            inner: ShmBackend::Daemon(inner),
+            output: None,
+        })
+    }
+
+    /// Wrap an in-memory mock instead of a mapped segment.
+    pub fn from_mock(mock: MockSharedMemory) -> Self {
+        Self {
+            inner: ShmBackend::Mock(mock),
+            output: None,
+        }
+    }
+
+    /// Open the daemon's raw output log, so [`raw_output`](Self::raw_output)
+    /// can read it.
+    ///
+    /// The log is validated with Scarab's magic number and version, like the
+    /// grid segment.
+    pub fn attach_output_log(&mut self, output_path: &str) -> IpcResult<()> {
+        self.output = Some(DaemonOutputLog::open_with_validation(
+            output_path,
+            SCARAB_MAGIC,
+            SCARAB_VERSION,
+        )?);
+        Ok(())
     }
 
     /// Refresh the header from shared memory.
     pub fn refresh(&mut self) -> IpcResult<()> {
-        self.inner.refresh()
+        match &mut self.inner {
+            ShmBackend::Daemon(shm) => shm.refresh(),
+            ShmBackend::Mock(shm) => shm.refresh(),
+        }
     }
 
     /// Get the terminal dimensions (cols, rows).
     pub fn dimensions(&self) -> (u16, u16) {
-        self.inner.dimensions()
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.dimensions(),
+            ShmBackend::Mock(shm) => shm.dimensions(),
+        }
+    }
+
+    /// Block until the sequence number changes, then refresh.
+    pub fn wait_for_update(&mut self, timeout: Duration) -> IpcResult<()> {
+        match &mut self.inner {
+            ShmBackend::Daemon(shm) => shm.wait_for_update(timeout),
+            ShmBackend::Mock(shm) => shm.wait_for_update(timeout),
+        }
+    }
+
+    /// Like [`wait_for_update`](Self::wait_for_update), but a mock sleeps out
+    /// the timeout on `clock`. The daemon is polled in real time.
+    fn wait_for_update_on(&mut self, clock: &dyn Clock, timeout: Duration) -> IpcResult<()> {
+        match &mut self.inner {
+            ShmBackend::Daemon(shm) => shm.wait_for_update(timeout),
+            ShmBackend::Mock(shm) => shm.wait_for_update_on(clock, timeout),
+        }
     }
 
     /// Get the cursor position (row, col).
     pub fn cursor_position(&self) -> (u16, u16) {
-        self.inner.cursor_position()
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.cursor_position(),
+            ShmBackend::Mock(shm) => shm.cursor_position(),
+        }
     }
 
     /// Get the sequence number for change detection.
     pub fn sequence_number(&self) -> u32 {
-        self.inner.sequence_number()
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.sequence_number(),
+            ShmBackend::Mock(shm) => shm.sequence_number(),
+        }
     }
 
     /// Read the terminal grid as a string.
     pub fn grid_contents(&self) -> IpcResult<String> {
-        self.inner.grid_contents()
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.grid_contents(),
+            ShmBackend::Mock(shm) => shm.grid_contents(),
+        }
     }
 
     /// Check if the grid contains the given text.
     pub fn contains(&self, text: &str) -> IpcResult<bool> {
-        self.inner.contains(text)
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.contains(text),
+            ShmBackend::Mock(shm) => shm.contains(text),
+        }
     }
 
     /// Get a specific cell character at (row, col).
     pub fn cell_at(&self, row: u16, col: u16) -> IpcResult<char> {
-        self.inner.cell_at(row, col)
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.cell_at(row, col),
+            ShmBackend::Mock(shm) => shm.cell_at(row, col),
+        }
     }
 
     /// Get cell attributes at (row, col).
     ///
     /// Returns the color and style attributes for the specified cell.
     pub fn cell_attrs_at(&self, row: u16, col: u16) -> IpcResult<crate::ipc::CellAttributes> {
-        self.inner.cell_attrs_at(row, col)
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.cell_attrs_at(row, col),
+            ShmBackend::Mock(shm) => shm.cell_attrs_at(row, col),
+        }
     }
 
     /// Get all cell attributes for a specific row.
     ///
     /// Returns a vector of attributes for all cells in the specified row.
     pub fn row_attrs(&self, row: u16) -> IpcResult<Vec<crate::ipc::CellAttributes>> {
-        self.inner.row_attrs(row)
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.row_attrs(row),
+            ShmBackend::Mock(shm) => shm.row_attrs(row),
+        }
+    }
+
+    /// Read the characters and attributes of every cell in a rectangle.
+    ///
+    /// Cells are returned in row-major order.
+    pub fn cells_in_range(
+        &self,
+        bounds: crate::regions::RegionBounds,
+    ) -> IpcResult<Vec<(char, crate::ipc::CellAttributes)>> {
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.cells_in_range(bounds),
+            ShmBackend::Mock(shm) => shm.cells_in_range(bounds),
+        }
+    }
+
+    /// Read the raw output stream the terminal has received, escape
+    /// sequences included.
+    ///
+    /// A daemon's stream comes from its output log, which only keeps the
+    /// most recent [`DaemonOutputLog::capacity`] bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::InvalidData`] for a daemon segment with no output
+    /// log attached: the grid layout only carries rendered cells.
+    pub fn raw_output(&self) -> IpcResult<Vec<u8>> {
+        match (&self.inner, &self.output) {
+            (ShmBackend::Daemon(_), Some(log)) => log.read(),
+            (ShmBackend::Daemon(_), None) => Err(IpcError::InvalidData(
+                "Daemon publishes no raw output log; see ScarabConfig::output_shm_path".to_string(),
+            )),
+            (ShmBackend::Mock(shm), _) => Ok(shm.raw_output().to_vec()),
+        }
+    }
+
+    /// Capture the current grid and sequence number.
+    pub fn snapshot(&self) -> IpcResult<ShmSnapshot> {
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.snapshot(),
+            ShmBackend::Mock(shm) => shm.snapshot(),
+        }
     }
 
-    /// Get a reference to the underlying DaemonSharedMemory.
+    /// List the cells that changed since `previous` was taken.
+    pub fn diff_since(&self, previous: &ShmSnapshot) -> IpcResult<Vec<CellDiff>> {
+        match &self.inner {
+            ShmBackend::Daemon(shm) => shm.diff_since(previous),
+            ShmBackend::Mock(shm) => shm.diff_since(previous),
+        }
+    }
+
+    /// Get a reference to the underlying DaemonSharedMemory, if this reader
+    /// wraps one.
     ///
     /// This is useful for advanced operations like seqlock verification.
-    pub fn as_daemon_shm(&self) -> &DaemonSharedMemory {
-        &self.inner
+    /// Returns `None` for a [`MockSharedMemory`].
+    pub fn as_daemon_shm(&self) -> Option<&DaemonSharedMemory> {
+        match &self.inner {
+            ShmBackend::Daemon(shm) => Some(shm),
+            ShmBackend::Mock(_) => None,
+        }
+    }
+
+    /// Get a mutable reference to the mock, if this reader wraps one.
+    pub fn as_mock_mut(&mut self) -> Option<&mut MockSharedMemory> {
+        match &mut self.inner {
+            ShmBackend::Daemon(_) => None,
+            ShmBackend::Mock(shm) => Some(shm),
+        }
     }
 }
 
@@ -373,9 +552,10 @@ impl ScarabSharedMemory {
 #[cfg(target_family = "unix")]
 #[derive(Debug)]
 pub struct ScarabTestHarness {
-    ipc: DaemonIpcClient,
+    ipc: Option<DaemonIpcClient>,
     shm: ScarabSharedMemory,
     config: ScarabConfig,
+    clock: Arc<dyn Clock>,
 }
 
 #[cfg(target_family = "unix")]
@@ -396,34 +576,157 @@ impl ScarabTestHarness {
     }
 
     /// Create a harness with custom configuration.
+    ///
+    /// The daemon may still be starting when the test runs, so a failed socket
+    /// connect or shared memory open is retried with exponential backoff, up
+    /// to `connect_retries` times within `connect_timeout`. The last error is
+    /// returned if every attempt fails.
     pub fn with_config(config: ScarabConfig) -> IpcResult<Self> {
-        // Connect to IPC socket
-        let ipc = DaemonIpcClient::connect(&config.socket_path)?;
+        let (ipc, mut shm) = retry_with_backoff(&config, || {
+            // Connect to IPC socket
+            let ipc = DaemonIpcClient::connect(&config.socket_path)?;
+
+            // Open shared memory with Scarab-specific validation
+            let shm = ScarabSharedMemory::open(&config.shm_path)?;
 
-        // Open shared memory with Scarab-specific validation
-        let shm = ScarabSharedMemory::open(&config.shm_path)?;
+            Ok((ipc, shm))
+        })?;
 
-        Ok(Self { ipc, shm, config })
+        // The output log is optional; without it only raw_output is unavailable
+        if let Some(output_path) = &config.output_shm_path {
+            match shm.attach_output_log(output_path) {
+                Ok(()) | Err(IpcError::SharedMemoryNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Self {
+            ipc: Some(ipc),
+            shm,
+            config,
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Create a harness around in-memory terminal state, without a daemon.
+    ///
+    /// Reads (grid, attributes, cursor) come from the mock, so assertions and
+    /// extension traits can be unit-tested. Operations that talk to the daemon,
+    /// such as [`send_input`](Self::send_input) and [`resize`](Self::resize),
+    /// return [`IpcError::ConnectionFailed`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::ipc::MockSharedMemory;
+    /// use terminal_testlib::scarab::ScarabTestHarness;
+    ///
+    /// let shm = MockSharedMemory::new(80, 24).with_grid("$ ls\nCargo.toml");
+    /// let harness = ScarabTestHarness::with_mock(shm);
+    ///
+    /// harness.assert_contains("Cargo.toml").unwrap();
+    /// # }
+    /// ```
+    pub fn with_mock(mock: MockSharedMemory) -> Self {
+        Self {
+            ipc: None,
+            shm: ScarabSharedMemory::from_mock(mock),
+            config: ScarabConfig::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the clock used to measure `wait_for_*` timeouts.
+    ///
+    /// Defaults to [`SystemClock`]. With a [`FakeClock`](crate::FakeClock)
+    /// and a mock, a wait that never matches reaches its timeout without
+    /// sleeping; a daemon connection is still polled in real time.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get a mutable reference to the mock terminal state, if any.
+    ///
+    /// Use this to simulate the daemon publishing a new frame between
+    /// assertions.
+    pub fn mock_mut(&mut self) -> Option<&mut MockSharedMemory> {
+        self.shm.as_mock_mut()
+    }
+
+    /// Get the IPC client, or an error when running against a mock.
+    fn ipc_mut(&mut self) -> IpcResult<&mut DaemonIpcClient> {
+        self.ipc.as_mut().ok_or_else(|| {
+            IpcError::ConnectionFailed(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "harness was created with a mock and has no daemon connection",
+            ))
+        })
     }
 
     /// Send input text to the PTY via IPC.
     pub fn send_input(&mut self, text: &str) -> IpcResult<()> {
-        self.ipc.send_text(text)
+        self.ipc_mut()?.send_text(text)
     }
 
     /// Send raw bytes to the PTY via IPC.
     pub fn send_bytes(&mut self, bytes: &[u8]) -> IpcResult<()> {
-        self.ipc.send_input(bytes)
+        self.ipc_mut()?.send_input(bytes)
     }
 
     /// Resize the terminal.
+    ///
+    /// A mock harness resizes its grid directly; see
+    /// [`MockSharedMemory::resize`].
     pub fn resize(&mut self, cols: u16, rows: u16) -> IpcResult<()> {
-        self.ipc.resize(cols, rows)
+        if let Some(mock) = self.shm.as_mock_mut() {
+            mock.resize(cols, rows);
+            return Ok(());
+        }
+        self.ipc_mut()?.resize(cols, rows)
+    }
+
+    /// Resize the terminal and wait for the app to redraw at the new size.
+    ///
+    /// [`resize`](Self::resize) followed by [`dimensions`](Self::dimensions)
+    /// only shows that the daemon stored the new size. This also waits up to
+    /// `timeout` for the sequence number to move on, which shows the app
+    /// handled `SIGWINCH` and drew a frame, before checking the dimensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::InvalidData`] if the dimensions are wrong after the
+    /// wait, or [`IpcError::ResizeNotRedrawn`] if they are right but no new
+    /// frame arrived in time.
+    pub fn resize_and_wait_for_redraw(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        timeout: Duration,
+    ) -> IpcResult<()> {
+        self.shm.refresh()?;
+        self.resize(cols, rows)?;
+
+        let redrawn = self.shm.wait_for_update_on(&*self.clock, timeout);
+        self.shm.refresh()?;
+        check_resized((cols, rows), self.shm.dimensions())?;
+
+        match redrawn {
+            Err(IpcError::Timeout(_)) => Err(IpcError::ResizeNotRedrawn { cols, rows, timeout }),
+            other => other,
+        }
     }
 
     /// Request a state refresh from the daemon.
+    ///
+    /// For a mock harness there is no daemon to ask, so only the shared
+    /// memory view is refreshed.
     pub fn refresh(&mut self) -> IpcResult<()> {
-        self.ipc.refresh()?;
+        if let Some(ipc) = self.ipc.as_mut() {
+            ipc.refresh()?;
+        }
         self.shm.refresh()
     }
 
@@ -449,8 +752,7 @@ impl ScarabTestHarness {
 
     /// Wait until the grid contains the specified text.
     pub fn wait_for_text(&mut self, text: &str, timeout: Duration) -> IpcResult<()> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(50);
+        let start = self.clock.now();
 
         loop {
             self.shm.refresh()?;
@@ -459,18 +761,35 @@ impl ScarabTestHarness {
                 return Ok(());
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
+            self.wait_for_frame(start, timeout)?;
+        }
+    }
+
+    /// Wait until the grid contains any of `texts`, returning the index of
+    /// the one found.
+    ///
+    /// All candidates are checked on every frame, so a command that can end
+    /// in success or failure is caught either way without waiting out the
+    /// timeout on one of them. If several appear in the same frame the lowest
+    /// index wins.
+    pub fn wait_for_any(&mut self, texts: &[&str], timeout: Duration) -> IpcResult<usize> {
+        let start = self.clock.now();
+
+        loop {
+            self.shm.refresh()?;
+
+            let grid = self.shm.grid_contents()?;
+            if let Some(index) = texts.iter().position(|text| grid.contains(text)) {
+                return Ok(index);
             }
 
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 
     /// Wait until the grid does NOT contain the specified text.
     pub fn wait_for_text_absent(&mut self, text: &str, timeout: Duration) -> IpcResult<()> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(50);
+        let start = self.clock.now();
 
         loop {
             self.shm.refresh()?;
@@ -479,11 +798,34 @@ impl ScarabTestHarness {
                 return Ok(());
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
+            self.wait_for_frame(start, timeout)?;
+        }
+    }
+
+    /// Wait until the cursor is at `(row, col)`.
+    ///
+    /// A timeout returns [`IpcError::CursorTimeout`] with the position the
+    /// cursor was last seen at.
+    pub fn wait_for_cursor_at(&mut self, row: u16, col: u16, timeout: Duration) -> IpcResult<()> {
+        let start = self.clock.now();
+
+        loop {
+            self.shm.refresh()?;
+
+            if self.shm.cursor_position() == (row, col) {
+                return Ok(());
             }
 
-            std::thread::sleep(poll_interval);
+            if let Err(err) = self.wait_for_frame(start, timeout) {
+                return Err(match err {
+                    IpcError::Timeout(timeout) => IpcError::CursorTimeout {
+                        expected: (row, col),
+                        last_seen: self.shm.cursor_position(),
+                        timeout,
+                    },
+                    other => other,
+                });
+            }
         }
     }
 
@@ -491,8 +833,7 @@ impl ScarabTestHarness {
     ///
     /// Uses the configured prompt patterns (default: `$`, `#`, `>`).
     pub fn wait_for_prompt(&mut self, timeout: Duration) -> IpcResult<()> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(50);
+        let start = self.clock.now();
         let patterns = self.config.prompt_patterns.clone();
 
         loop {
@@ -505,24 +846,66 @@ impl ScarabTestHarness {
                 }
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
-
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 
     /// Wait for a sequence of text strings to appear in order.
+    ///
+    /// All steps share `timeout`. If it runs out, the error is
+    /// [`IpcError::SequenceTimeout`] naming the step that was pending. Use
+    /// [`wait_for_sequence_with_timeouts`](Self::wait_for_sequence_with_timeouts)
+    /// to give each step its own budget.
     pub fn wait_for_sequence(&mut self, texts: &[&str], timeout: Duration) -> IpcResult<()> {
-        let start = std::time::Instant::now();
+        let start = self.clock.now();
+
+        for (step, text) in texts.iter().enumerate() {
+            let remaining = timeout.saturating_sub(self.clock.elapsed_since(start));
+            let result = if remaining.is_zero() {
+                Err(IpcError::Timeout(timeout))
+            } else {
+                self.wait_for_text(text, remaining)
+            };
+            result.map_err(|err| sequence_error(err, step, text, texts.len(), timeout))?;
+        }
 
-        for text in texts {
-            let remaining = timeout.saturating_sub(start.elapsed());
-            if remaining.is_zero() {
-                return Err(IpcError::Timeout(timeout));
-            }
-            self.wait_for_text(text, remaining)?;
+        Ok(())
+    }
+
+    /// Wait for a sequence of text strings to appear in order, each with its
+    /// own timeout.
+    ///
+    /// A slow step cannot use up the time of later ones. The clock for each
+    /// step starts once the previous step has matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::SequenceTimeout`] with the index and text of the
+    /// step that timed out.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use std::time::Duration;
+    /// use terminal_testlib::scarab::ScarabTestHarness;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// harness.wait_for_sequence_with_timeouts(&[
+    ///     ("Login:", Duration::from_secs(5)),
+    ///     ("Main menu", Duration::from_secs(2)),
+    ///     ("Result", Duration::from_millis(500)),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn wait_for_sequence_with_timeouts(&mut self, steps: &[(&str, Duration)]) -> IpcResult<()> {
+        for (step, &(text, timeout)) in steps.iter().enumerate() {
+            self.wait_for_text(text, timeout)
+                .map_err(|err| sequence_error(err, step, text, steps.len(), timeout))?;
         }
 
         Ok(())
@@ -530,22 +913,39 @@ impl ScarabTestHarness {
 
     /// Wait for the sequence number to change, indicating a state update.
     pub fn wait_for_update(&mut self, timeout: Duration) -> IpcResult<()> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(10);
-        let initial_seq = self.shm.sequence_number();
+        self.shm.wait_for_update_on(&*self.clock, timeout)
+    }
+
+    /// Poll `check` after each refresh until it yields a value or `timeout`
+    /// runs out, waiting for a new frame between checks.
+    pub(crate) fn poll_frames<T>(
+        &mut self,
+        timeout: Duration,
+        mut check: impl FnMut(&Self) -> IpcResult<Option<T>>,
+    ) -> IpcResult<T> {
+        let start = self.clock.now();
 
         loop {
             self.shm.refresh()?;
 
-            if self.shm.sequence_number() != initial_seq {
-                return Ok(());
+            if let Some(value) = check(self)? {
+                return Ok(value);
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
+            self.wait_for_frame(start, timeout)?;
+        }
+    }
+
+    /// Sleep until the next frame, or fail once `timeout` has elapsed since `start`.
+    fn wait_for_frame(&mut self, start: std::time::Instant, timeout: Duration) -> IpcResult<()> {
+        let remaining = timeout.saturating_sub(self.clock.elapsed_since(start));
+        if remaining.is_zero() {
+            return Err(IpcError::Timeout(timeout));
+        }
 
-            std::thread::sleep(poll_interval);
+        match self.shm.wait_for_update_on(&*self.clock, remaining) {
+            Err(IpcError::Timeout(_)) => Err(IpcError::Timeout(timeout)),
+            other => other,
         }
     }
 
@@ -562,6 +962,23 @@ impl ScarabTestHarness {
         }
     }
 
+    /// Assert that a grid row reads exactly `expected`, ignoring trailing spaces.
+    ///
+    /// Leading and interior spaces are significant. On mismatch the error shows
+    /// both strings with a caret under the first differing column.
+    pub fn assert_row_eq(&self, row: u16, expected: &str) -> IpcResult<()> {
+        let (_, rows) = self.shm.dimensions();
+        let grid = self.shm.grid_contents()?;
+        let actual = grid.lines().nth(row as usize).ok_or_else(|| {
+            IpcError::InvalidData(format!("Row {} out of bounds (max {})", row, rows))
+        })?;
+
+        match row_mismatch(row, expected, actual.trim_end_matches('\0')) {
+            Some(message) => Err(IpcError::InvalidData(message)),
+            None => Ok(()),
+        }
+    }
+
     /// Get the default timeout from configuration.
     pub fn default_timeout(&self) -> Duration {
         self.config.default_timeout
@@ -579,6 +996,43 @@ impl ScarabTestHarness {
         self.shm.cell_attrs_at(row, col)
     }
 
+    /// Capture the current grid for a later [`changed_cells_since`](Self::changed_cells_since).
+    pub fn snapshot(&self) -> IpcResult<ShmSnapshot> {
+        self.shm.snapshot()
+    }
+
+    /// List the cells that changed since `snapshot` was taken.
+    ///
+    /// Refreshes the shared memory view first. Returns an empty list without
+    /// comparing cells when the sequence number is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use std::time::Duration;
+    /// use terminal_testlib::scarab::ScarabTestHarness;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut harness = ScarabTestHarness::connect()?;
+    /// let before = harness.snapshot()?;
+    ///
+    /// harness.send_input("j")?;
+    /// harness.wait_for_update(Duration::from_secs(1))?;
+    ///
+    /// // Only the status line should have changed
+    /// let changed = harness.changed_cells_since(&before)?;
+    /// assert!(changed.iter().all(|cell| cell.row == 23));
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn changed_cells_since(&mut self, snapshot: &ShmSnapshot) -> IpcResult<Vec<CellDiff>> {
+        self.shm.refresh()?;
+        self.shm.diff_since(snapshot)
+    }
+
     /// Assert that a cell has the expected foreground color.
     ///
     /// # Arguments
@@ -608,10 +1062,13 @@ impl ScarabTestHarness {
         if attrs.fg == expected_color {
             Ok(())
         } else {
-            Err(IpcError::InvalidData(format!(
-                "Cell ({}, {}) foreground color mismatch: expected 0x{:08X}, got 0x{:08X}",
-                row, col, expected_color, attrs.fg
-            )))
+            Err(IpcError::ColorMismatch {
+                row,
+                col,
+                layer: ColorLayer::Foreground,
+                expected: expected_color,
+                actual: attrs.fg,
+            })
         }
     }
 
@@ -644,13 +1101,60 @@ impl ScarabTestHarness {
         if attrs.bg == expected_color {
             Ok(())
         } else {
-            Err(IpcError::InvalidData(format!(
-                "Cell ({}, {}) background color mismatch: expected 0x{:08X}, got 0x{:08X}",
-                row, col, expected_color, attrs.bg
-            )))
+            Err(IpcError::ColorMismatch {
+                row,
+                col,
+                layer: ColorLayer::Background,
+                expected: expected_color,
+                actual: attrs.bg,
+            })
         }
     }
 
+    /// Render a Markdown report of a region's text and per-cell colors.
+    ///
+    /// See [`ColorDiagnostics`](crate::theme::ColorDiagnostics) for the
+    /// report contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `tester` - The UI region tester with region definitions
+    /// * `region_name` - Name of the region to report on
+    /// * `palette` - Palette used to name the nearest ANSI colors
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the region doesn't exist or the grid cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::regions::UiRegionTester;
+    /// use terminal_testlib::scarab::ScarabTestHarness;
+    /// use terminal_testlib::theme::ColorPalette;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    /// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
+    ///
+    /// let report = harness.color_report(&tester, "status_bar", &ColorPalette::slime())?;
+    /// println!("{}", report);
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn color_report(
+        &self,
+        tester: &crate::regions::UiRegionTester,
+        region_name: &str,
+        palette: &crate::theme::ColorPalette,
+    ) -> IpcResult<String> {
+        let report = crate::theme::ColorDiagnostics::capture(self, tester, region_name, palette)?;
+        Ok(report.to_markdown())
+    }
+
     /// Assert that a cell has the expected style flags.
     ///
     /// # Arguments
@@ -690,12 +1194,121 @@ impl ScarabTestHarness {
         }
     }
 
+    /// Assert that a cell has exactly the given style flags.
+    ///
+    /// Unlike [`assert_cell_styled`](Self::assert_cell_styled), which passes
+    /// when the expected flags are a subset of the cell's flags, this fails
+    /// if the cell carries any additional flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the cell (0-indexed)
+    /// * `col` - The column of the cell (0-indexed)
+    /// * `flags` - The exact expected style flags
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "scarab")]
+    /// # {
+    /// use terminal_testlib::scarab::ScarabTestHarness;
+    /// use terminal_testlib::ipc::CellFlags;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let harness = ScarabTestHarness::connect()?;
+    ///
+    /// // Assert cell at (0, 0) is bold and nothing else
+    /// harness.assert_cell_style_exact(0, 0, CellFlags::BOLD)?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn assert_cell_style_exact(
+        &self,
+        row: u16,
+        col: u16,
+        flags: crate::ipc::CellFlags,
+    ) -> IpcResult<()> {
+        let attrs = self.cell_attrs_at(row, col)?;
+        check_style_exact(row, col, &attrs, flags)
+    }
+
+    /// Assert that a cell has no style flags set.
+    ///
+    /// Equivalent to [`assert_cell_style_exact`](Self::assert_cell_style_exact)
+    /// with empty flags.
+    pub fn assert_cell_unstyled(&self, row: u16, col: u16) -> IpcResult<()> {
+        self.assert_cell_style_exact(row, col, crate::ipc::CellFlags::empty())
+    }
+
     /// Get a mutable reference to the underlying shared memory reader.
     ///
     /// This is useful for advanced operations like seqlock verification
-    /// that need mutable access to the shared memory.
-    pub fn shared_memory_mut(&mut self) -> &mut DaemonSharedMemory {
-        &mut self.shm.inner
+    /// that need mutable access to the shared memory. Returns `None` if the
+    /// harness was created with [`with_mock`](Self::with_mock).
+    pub fn shared_memory_mut(&mut self) -> Option<&mut DaemonSharedMemory> {
+        match &mut self.shm.inner {
+            ShmBackend::Daemon(shm) => Some(shm),
+            ShmBackend::Mock(_) => None,
+        }
+    }
+
+    /// Get the daemon shared memory, or an error when running against a mock.
+    fn daemon_shm_mut(&mut self) -> IpcResult<&mut DaemonSharedMemory> {
+        self.shared_memory_mut().ok_or_else(|| {
+            IpcError::ConnectionFailed(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "harness was created with a mock and has no daemon shared memory",
+            ))
+        })
+    }
+}
+
+/// Check that a cell's style flags equal `expected` exactly.
+fn check_style_exact(
+    row: u16,
+    col: u16,
+    attrs: &crate::ipc::CellAttributes,
+    expected: crate::ipc::CellFlags,
+) -> IpcResult<()> {
+    let actual = crate::ipc::CellFlags::from_bits_truncate(attrs.flags);
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(IpcError::InvalidData(format!(
+            "Cell ({}, {}) style flags mismatch: expected exactly {:?}, got {:?}",
+            row, col, expected, actual
+        )))
+    }
+}
+
+/// Run `attempt` until it succeeds, doubling the delay after each failure.
+///
+/// Gives up after `config.connect_retries` retries, or earlier if the next
+/// delay would run past `config.connect_timeout`, returning the last error.
+#[cfg(target_family = "unix")]
+fn retry_with_backoff<T>(
+    config: &ScarabConfig,
+    mut attempt: impl FnMut() -> IpcResult<T>,
+) -> IpcResult<T> {
+    let start = std::time::Instant::now();
+    let mut interval = config.retry_interval;
+    let mut retries = 0;
+
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let out_of_time = start.elapsed() + interval > config.connect_timeout;
+                if retries >= config.connect_retries || out_of_time {
+                    return Err(err);
+                }
+                std::thread::sleep(interval);
+                interval = interval.saturating_mul(2);
+                retries += 1;
+            }
+        }
     }
 }
 
@@ -704,6 +1317,48 @@ impl crate::theme::ThemeTestExt for ScarabTestHarness {
     fn cell_attrs_at(&self, row: u16, col: u16) -> IpcResult<crate::ipc::CellAttributes> {
         self.shm.cell_attrs_at(row, col)
     }
+
+    fn scan_colors_in_region(
+        &self,
+        start_row: u16,
+        start_col: u16,
+        end_row: u16,
+        end_col: u16,
+    ) -> IpcResult<crate::theme::ColorScan> {
+        let mut scan = crate::theme::ColorScan::new();
+
+        // Clip to the grid, matching the per-cell default which skips
+        // out-of-bounds cells
+        let (cols, rows) = self.shm.dimensions();
+        let end_row = end_row.min(rows.saturating_sub(1));
+        let end_col = end_col.min(cols.saturating_sub(1));
+        if rows == 0 || cols == 0 || start_row > end_row || start_col > end_col {
+            return Ok(scan);
+        }
+
+        let bounds = crate::regions::RegionBounds::new(
+            start_row,
+            start_col,
+            end_col - start_col + 1,
+            end_row - start_row + 1,
+        );
+        for (_, attrs) in self.shm.cells_in_range(bounds)? {
+            scan.add_foreground(attrs.fg);
+            scan.add_background(attrs.bg);
+            scan.increment_cells();
+        }
+
+        Ok(scan)
+    }
+
+    fn cursor_color(&self) -> IpcResult<Option<u32>> {
+        // The grid carries no cursor color, so replay the daemon's raw
+        // output to find the last OSC 12 / OSC 112
+        let (cols, rows) = self.shm.dimensions();
+        let mut screen = crate::ScreenState::new(cols, rows);
+        screen.feed(&self.shm.raw_output()?);
+        Ok(screen.cursor_color())
+    }
 }
 
 /// Extension trait for integrating Scarab testing with TuiTestHarness.
@@ -764,34 +1419,35 @@ impl SeqlockTestExt for ScarabTestHarness {
         F: Fn(&DaemonSharedMemory) -> IpcResult<T>,
     {
         let mut verifier = crate::seqlock::SeqlockVerifier::new();
-        let (result, _retries) = verifier.synchronized_read(self.shared_memory_mut(), reader)?;
+        let (result, _retries) = verifier.synchronized_read(self.daemon_shm_mut()?, reader)?;
         Ok(result)
     }
 
     fn verify_seqlock(&mut self, duration: Duration) -> IpcResult<crate::seqlock::SeqlockReport> {
         let mut verifier = crate::seqlock::SeqlockVerifier::new();
         let poll_interval = Duration::from_millis(10);
-        verifier.verify_seqlock_pattern(self.shared_memory_mut(), duration, poll_interval)
+        verifier.verify_seqlock_pattern(self.daemon_shm_mut()?, duration, poll_interval)
     }
 }
 
 // Implement SemanticZoneExt for ScarabTestHarness
 #[cfg(target_family = "unix")]
 impl crate::zones::SemanticZoneExt for ScarabTestHarness {
+    /// Parses OSC 133 markers from the raw output stream.
+    ///
+    /// Against a daemon this needs its output log; see
+    /// [`ScarabSharedMemory::raw_output`]. Positions assume the stream started
+    /// at the top-left cell and has not scrolled. The whole stream is parsed on each call, so Output zone
+    /// durations do not reflect how long commands ran.
     fn zones(&self) -> IpcResult<Vec<crate::zones::SemanticZone>> {
-        // For now, return empty - this would need grid data parsing
-        // In a real implementation, we'd parse the grid contents for OSC 133 markers
-        Ok(Vec::new())
+        let mut parser = crate::zones::Osc133Parser::new();
+        parser.parse(&self.shm.raw_output()?);
+        Ok(parser.zones())
     }
 
     fn zone_at(&self, row: u16, col: u16) -> IpcResult<Option<crate::zones::SemanticZone>> {
         let zones = self.zones()?;
-        Ok(zones.into_iter().find(|zone| {
-            row >= zone.start_row
-                && row <= zone.end_row
-                && col >= zone.start_col
-                && col <= zone.end_col
-        }))
+        Ok(zones.into_iter().find(|zone| zone.contains(row, col)))
     }
 
     fn last_output_zone(&self) -> IpcResult<Option<crate::zones::SemanticZone>> {
@@ -817,6 +1473,8 @@ impl crate::zones::SemanticZoneExt for ScarabTestHarness {
         let mut result = String::new();
         for row in zone.start_row..=zone.end_row {
             if let Some(line) = lines.get(row as usize) {
+                // Columns are character positions, not byte offsets
+                let chars: Vec<char> = line.chars().collect();
                 let start_col = if row == zone.start_row {
                     zone.start_col as usize
                 } else {
@@ -825,12 +1483,12 @@ impl crate::zones::SemanticZoneExt for ScarabTestHarness {
                 let end_col = if row == zone.end_row {
                     zone.end_col as usize
                 } else {
-                    line.len()
+                    chars.len()
                 };
 
-                if start_col < line.len() {
-                    let end = end_col.min(line.len());
-                    result.push_str(&line[start_col..end]);
+                if start_col < chars.len() {
+                    let end = end_col.min(chars.len());
+                    result.extend(&chars[start_col..end]);
                 }
 
                 if row < zone.end_row {
@@ -862,8 +1520,7 @@ impl crate::zones::SemanticZoneExt for ScarabTestHarness {
     }
 
     fn wait_for_output_zone(&mut self, timeout: Duration) -> IpcResult<crate::zones::SemanticZone> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(50);
+        let start = self.clock.now();
         let initial_count = self.zones()?.len();
 
         loop {
@@ -881,17 +1538,12 @@ impl crate::zones::SemanticZoneExt for ScarabTestHarness {
                 }
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
-
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 
     fn wait_for_command_complete(&mut self, timeout: Duration) -> IpcResult<Option<i32>> {
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(50);
+        let start = self.clock.now();
 
         loop {
             self.shm.refresh()?;
@@ -900,15 +1552,31 @@ impl crate::zones::SemanticZoneExt for ScarabTestHarness {
                 return Ok(zone.exit_code);
             }
 
-            if start.elapsed() >= timeout {
-                return Err(IpcError::Timeout(timeout));
-            }
-
-            std::thread::sleep(poll_interval);
+            self.wait_for_frame(start, timeout)?;
         }
     }
 }
 
+/// Turn a timeout while waiting for one step of a sequence into
+/// [`IpcError::SequenceTimeout`]; other errors pass through.
+fn sequence_error(
+    err: IpcError,
+    step: usize,
+    text: &str,
+    total: usize,
+    timeout: Duration,
+) -> IpcError {
+    match err {
+        IpcError::Timeout(_) => IpcError::SequenceTimeout {
+            step,
+            text: text.to_string(),
+            total,
+            timeout,
+        },
+        other => other,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -988,4 +1656,34 @@ mod tests {
         assert!(flags.contains(CellFlags::BOLD));
         assert!(flags.contains(CellFlags::ITALIC));
     }
+
+    #[test]
+    fn test_style_exact_distinguishes_extra_flags() {
+        use crate::ipc::{CellAttributes, CellFlags};
+        let attrs = CellAttributes {
+            flags: (CellFlags::BOLD | CellFlags::ITALIC).bits(),
+            ..Default::default()
+        };
+
+        assert!(check_style_exact(0, 0, &attrs, CellFlags::BOLD | CellFlags::ITALIC).is_ok());
+
+        // Subset match would pass, exact match must not
+        let err = check_style_exact(0, 0, &attrs, CellFlags::BOLD).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("expected exactly CellFlags(BOLD)"));
+        assert!(message.contains("got CellFlags(BOLD | ITALIC)"));
+    }
+
+    #[test]
+    fn test_style_exact_unstyled() {
+        use crate::ipc::{CellAttributes, CellFlags};
+        let plain = CellAttributes::default();
+        let underlined = CellAttributes {
+            flags: CellFlags::UNDERLINE.bits(),
+            ..Default::default()
+        };
+
+        assert!(check_style_exact(1, 2, &plain, CellFlags::empty()).is_ok());
+        assert!(check_style_exact(1, 2, &underlined, CellFlags::empty()).is_err());
+    }
 }
@@ -69,8 +69,15 @@
 //! // - Sixel regions: oracle.sixel_regions()
 //! ```
 
+use std::collections::BTreeSet;
+
 use vtparse::{CsiParam, VTActor, VTParser};
 
+use crate::{
+    error::{Result, TermTestError},
+    events::MouseMode,
+};
+
 /// Represents a single terminal cell with character and attributes.
 ///
 /// This struct tracks the complete state of a terminal cell including:
@@ -90,8 +97,12 @@ pub struct Cell {
     pub bold: bool,
     /// Italic attribute
     pub italic: bool,
-    /// Underline attribute
+    /// Underline attribute (set for any [`underline_style`](Self::underline_style))
     pub underline: bool,
+    /// Underline shape, from `SGR 4`, `SGR 4:n` and `SGR 21`
+    pub underline_style: UnderlineStyle,
+    /// Underline color from `SGR 58` (None = same as the foreground)
+    pub underline_color: Option<UnderlineColor>,
 }
 
 impl Default for Cell {
@@ -103,10 +114,62 @@ impl Default for Cell {
             bold: false,
             italic: false,
             underline: false,
+            underline_style: UnderlineStyle::None,
+            underline_color: None,
+        }
+    }
+}
+
+/// Underline shape of a cell.
+///
+/// Plain `SGR 4` gives [`Single`](Self::Single) and `SGR 21` gives
+/// [`Double`](Self::Double). The extended shapes use the colon
+/// sub-parameter form popularized by kitty: `ESC[4:0m` through `ESC[4:5m`
+/// select the variants in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnderlineStyle {
+    /// No underline
+    #[default]
+    None,
+    /// Straight single line (`4` or `4:1`)
+    Single,
+    /// Double line (`21` or `4:2`)
+    Double,
+    /// Wavy line (`4:3`), commonly used for spelling and syntax errors
+    Curly,
+    /// Dotted line (`4:4`)
+    Dotted,
+    /// Dashed line (`4:5`)
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Maps the sub-parameter of `SGR 4:n` to a style.
+    fn from_sgr(n: i64) -> Option<Self> {
+        match n {
+            0 => Some(Self::None),
+            1 => Some(Self::Single),
+            2 => Some(Self::Double),
+            3 => Some(Self::Curly),
+            4 => Some(Self::Dotted),
+            5 => Some(Self::Dashed),
+            _ => None,
         }
     }
 }
 
+/// Underline color set with `SGR 58`.
+///
+/// Both the semicolon form (`ESC[58;5;nm`, `ESC[58;2;r;g;bm`) and the colon
+/// form (`ESC[58:5:nm`, `ESC[58:2::r:g:bm`) are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnderlineColor {
+    /// Palette color (0-255)
+    Indexed(u8),
+    /// 24-bit color
+    Rgb(u8, u8, u8),
+}
+
 /// A rectangular area in terminal coordinate space.
 ///
 /// Represents a rectangular region with a position and size. This is compatible
@@ -248,6 +311,70 @@ pub struct SixelRegion {
     pub data: Vec<u8>,
 }
 
+impl SixelRegion {
+    /// Returns the cells covered by this image for a given cell size.
+    ///
+    /// A partially covered cell counts as covered.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_width` - Width of one character cell in pixels
+    /// * `cell_height` - Height of one character cell in pixels
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{Rect, SixelRegion};
+    ///
+    /// let region = SixelRegion {
+    ///     start_row: 5,
+    ///     start_col: 10,
+    ///     width: 100,
+    ///     height: 50,
+    ///     data: Vec::new(),
+    /// };
+    /// assert_eq!(region.cell_footprint(10, 20), Rect::new(10, 5, 10, 3));
+    /// assert_eq!(region.cell_footprint(9, 18), Rect::new(10, 5, 12, 3));
+    /// ```
+    pub fn cell_footprint(&self, cell_width: u32, cell_height: u32) -> Rect {
+        let (width, height) =
+            TerminalState::pixels_to_cells_sized(self.width, self.height, cell_width, cell_height);
+        Rect::new(self.start_col, self.start_row, width, height)
+    }
+
+    /// Returns the cells covered by this image.
+    ///
+    /// The pixel size is converted to cells assuming 8x6 pixel cells, and a
+    /// partially covered cell counts as covered. That fixed size disagrees
+    /// with [`ScreenState`], which uses its configurable cell size; use
+    /// [`cell_footprint`](Self::cell_footprint) or
+    /// [`ScreenState::cell_footprint`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![allow(deprecated)]
+    /// use terminal_testlib::{Rect, SixelRegion};
+    ///
+    /// let region = SixelRegion {
+    ///     start_row: 5,
+    ///     start_col: 10,
+    ///     width: 100,
+    ///     height: 50,
+    ///     data: Vec::new(),
+    /// };
+    /// assert_eq!(region.cell_bounds(), Rect::new(10, 5, 13, 9));
+    /// ```
+    #[deprecated(
+        since = "0.6.0",
+        note = "assumes 8x6 pixel cells; use `cell_footprint` or `ScreenState::cell_footprint`"
+    )]
+    pub fn cell_bounds(&self) -> Rect {
+        let (width, height) = TerminalState::pixels_to_cells(self.width, self.height);
+        Rect::new(self.start_col, self.start_row, width, height)
+    }
+}
+
 /// Represents a Kitty graphics region in the terminal.
 ///
 /// Kitty graphics protocol is an advanced protocol that supports various
@@ -344,10 +471,99 @@ pub struct GridSnapshot {
     pub cursor: (u16, u16),
 }
 
+/// A saved copy of the emulator state that a [`ScreenState`] can roll back to.
+///
+/// Created by [`ScreenState::checkpoint`] and applied with
+/// [`ScreenState::restore`]. Unlike [`GridSnapshot`], which is a read-only
+/// view for comparison, a checkpoint holds everything the emulator tracks:
+/// cells, cursor, current text attributes, tab stops, private modes and
+/// graphics regions.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::ScreenState;
+///
+/// let mut screen = ScreenState::new(80, 24);
+/// screen.feed(b"Menu");
+/// let checkpoint = screen.checkpoint();
+///
+/// screen.feed(b"\r\nBranch A");
+/// screen.restore(&checkpoint);
+/// assert!(!screen.contains("Branch A"));
+///
+/// screen.feed(b"\r\nBranch B");
+/// assert!(screen.contains("Branch B"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScreenCheckpoint {
+    state: TerminalState,
+    width: u16,
+    height: u16,
+}
+
+impl ScreenCheckpoint {
+    /// Returns the grid as it was when the checkpoint was taken.
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            width: self.width,
+            height: self.height,
+            cells: self.state.cells.clone(),
+            cursor: self.state.cursor_pos,
+        }
+    }
+}
+
+/// DEC private modes set via `ESC [ ? Pm h` (DECSET) and cleared via
+/// `ESC [ ? Pm l` (DECRST).
+///
+/// Modes the parser has never seen report their power-on default: autowrap
+/// (7) and cursor visibility (25) start enabled, everything else disabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DecPrivateModes {
+    enabled: BTreeSet<u16>,
+}
+
+impl DecPrivateModes {
+    /// DECAWM - autowrap at the right margin
+    const AUTOWRAP: u16 = 7;
+    /// DECTCEM - text cursor visible
+    const CURSOR_VISIBLE: u16 = 25;
+    /// X10 compatibility mouse reporting
+    const MOUSE_X10: u16 = 9;
+    /// Normal, button-event and any-event mouse tracking
+    const MOUSE_TRACKING: [u16; 3] = [1000, 1002, 1003];
+    /// SGR extended mouse encoding
+    const MOUSE_SGR: u16 = 1006;
+    /// Bracketed paste markers around pasted text
+    const BRACKETED_PASTE: u16 = 2004;
+
+    fn get(&self, mode: u16) -> bool {
+        self.enabled.contains(&mode)
+    }
+
+    fn set(&mut self, mode: u16, enabled: bool) {
+        if enabled {
+            self.enabled.insert(mode);
+        } else {
+            self.enabled.remove(&mode);
+        }
+    }
+}
+
+impl Default for DecPrivateModes {
+    fn default() -> Self {
+        Self {
+            enabled: [Self::AUTOWRAP, Self::CURSOR_VISIBLE].into_iter().collect(),
+        }
+    }
+}
+
 /// Terminal state tracking for vtparse parser.
 ///
 /// Implements VTActor to handle escape sequences including DCS for Sixel,
 /// APC for Kitty graphics, and OSC for iTerm2 inline images.
+#[derive(Debug, Clone)]
 struct TerminalState {
     cursor_pos: (u16, u16),
     sixel_regions: Vec<SixelRegion>,
@@ -368,12 +584,37 @@ struct TerminalState {
     width: u16,
     height: u16,
     cells: Vec<Vec<Cell>>,
+    /// Columns with a tab stop set (initialized to every 8 columns)
+    tab_stops: BTreeSet<u16>,
+    /// Working directory reported by the most recent OSC 7 sequence
+    current_directory: Option<String>,
+    /// Text from the most recent OSC 52 clipboard write
+    clipboard: Option<String>,
+    /// Cursor color set by OSC 12, as RGBA
+    cursor_color: Option<u32>,
+    /// Whether every OSC dispatch is appended to `osc_log`
+    record_osc: bool,
+    /// Recorded OSC sequences as (number, payload after the first `;`)
+    osc_log: Vec<(u32, Vec<u8>)>,
+    /// Whether non-Sixel DCS strings are appended to `dcs_log`
+    record_dcs: bool,
+    /// Recorded DCS strings as (final byte, parameters, payload)
+    dcs_log: Vec<(u8, Vec<i64>, Vec<u8>)>,
+    /// Non-Sixel DCS string being collected for `dcs_log`
+    current_dcs: Option<(u8, Vec<i64>, Vec<u8>)>,
+    /// DEC private modes toggled by DECSET/DECRST
+    private_modes: DecPrivateModes,
+    /// Kitty keyboard protocol flags pushed with `CSI > flags u`, innermost last
+    kitty_keyboard_stack: Vec<u8>,
+    /// Rows printed to, scrolled or resized since the dirty set was last cleared
+    dirty_rows: BTreeSet<u16>,
     /// Current text attributes (for SGR sequences)
     current_fg: Option<u8>,
     current_bg: Option<u8>,
     current_bold: bool,
     current_italic: bool,
-    current_underline: bool,
+    current_underline: UnderlineStyle,
+    current_underline_color: Option<UnderlineColor>,
 }
 
 impl TerminalState {
@@ -395,11 +636,24 @@ impl TerminalState {
             width,
             height,
             cells,
+            tab_stops: Self::default_tab_stops(width),
+            current_directory: None,
+            clipboard: None,
+            cursor_color: None,
+            record_osc: false,
+            osc_log: Vec::new(),
+            record_dcs: false,
+            dcs_log: Vec::new(),
+            current_dcs: None,
+            private_modes: DecPrivateModes::default(),
+            kitty_keyboard_stack: Vec::new(),
+            dirty_rows: BTreeSet::new(),
             current_fg: None,
             current_bg: None,
             current_bold: false,
             current_italic: false,
-            current_underline: false,
+            current_underline: UnderlineStyle::None,
+            current_underline_color: None,
         }
     }
 
@@ -412,8 +666,11 @@ impl TerminalState {
                 bg: self.current_bg,
                 bold: self.current_bold,
                 italic: self.current_italic,
-                underline: self.current_underline,
+                underline: self.current_underline != UnderlineStyle::None,
+                underline_style: self.current_underline,
+                underline_color: self.current_underline_color,
             };
+            self.dirty_rows.insert(row);
             // Move cursor forward, but don't wrap automatically
             if col + 1 < self.width {
                 self.cursor_pos.1 = col + 1;
@@ -421,8 +678,128 @@ impl TerminalState {
         }
     }
 
+    /// Returns the default tab stops: every 8 columns, excluding column 0.
+    fn default_tab_stops(width: u16) -> BTreeSet<u16> {
+        (8..width).step_by(8).collect()
+    }
+
+    /// Handles the Kitty keyboard protocol's flag stack.
+    ///
+    /// `CSI > flags u` pushes flags, `CSI < n u` pops `n` entries (default 1)
+    /// and `CSI = flags ; mode u` replaces (1), sets (2) or clears (3) bits of
+    /// the current flags. Queries (`CSI ? u`) and unprefixed `CSI u` are
+    /// ignored.
+    fn kitty_keyboard(&mut self, params: &[CsiParam]) {
+        const MAX_DEPTH: usize = 16;
+
+        let integers: Vec<i64> = params.iter().filter_map(|p| p.as_integer()).collect();
+        let flags = u8::try_from(integers.first().copied().unwrap_or(0)).unwrap_or(0) & 0x1f;
+
+        match params.first() {
+            Some(CsiParam::P(b'>')) => {
+                if self.kitty_keyboard_stack.len() == MAX_DEPTH {
+                    self.kitty_keyboard_stack.remove(0);
+                }
+                self.kitty_keyboard_stack.push(flags);
+            }
+            Some(CsiParam::P(b'<')) => {
+                let count = integers.first().copied().unwrap_or(1).max(1) as usize;
+                let keep = self.kitty_keyboard_stack.len().saturating_sub(count);
+                self.kitty_keyboard_stack.truncate(keep);
+            }
+            Some(CsiParam::P(b'=')) => {
+                let current = self.kitty_keyboard_stack.last().copied().unwrap_or(0);
+                let updated = match integers.get(1).copied().unwrap_or(1) {
+                    1 => flags,
+                    2 => current | flags,
+                    3 => current & !flags,
+                    _ => return,
+                };
+                match self.kitty_keyboard_stack.last_mut() {
+                    Some(top) => *top = updated,
+                    None => self.kitty_keyboard_stack.push(updated),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Changes the grid size, keeping cells in the top-left overlap.
+    ///
+    /// New cells are blank, new columns get the default tab stops, and the
+    /// cursor is clamped onto the new grid. Every row is marked dirty if the
+    /// size changed.
+    fn resize(&mut self, width: u16, height: u16) {
+        let resized = (width, height) != (self.width, self.height);
+        self.cells
+            .resize(height as usize, vec![Cell::default(); width as usize]);
+        for row in &mut self.cells {
+            row.resize(width as usize, Cell::default());
+        }
+
+        let old_width = self.width;
+        self.tab_stops.retain(|&col| col < width);
+        self.tab_stops
+            .append(&mut Self::default_tab_stops(width).split_off(&old_width));
+        self.dirty_rows.retain(|&row| row < height);
+
+        self.width = width;
+        self.height = height;
+        if resized {
+            self.mark_all_dirty();
+        }
+        self.cursor_pos = (
+            self.cursor_pos.0.min(height.saturating_sub(1)),
+            self.cursor_pos.1.min(width.saturating_sub(1)),
+        );
+    }
+
+    /// Returns the column of the next tab stop after the cursor.
+    ///
+    /// Falls back to the last column when no further tab stop is set.
+    fn next_tab_stop(&self, col: u16) -> u16 {
+        let last_col = self.width.saturating_sub(1);
+        self.tab_stops
+            .range(col + 1..)
+            .next()
+            .copied()
+            .unwrap_or(last_col)
+            .min(last_col)
+    }
+
     fn move_cursor(&mut self, row: u16, col: u16) {
-        self.cursor_pos = (row.min(self.height - 1), col.min(self.width - 1));
+        self.cursor_pos =
+            (row.min(self.height.saturating_sub(1)), col.min(self.width.saturating_sub(1)));
+    }
+
+    /// Moves the cursor down one line, scrolling the screen up at the bottom.
+    ///
+    /// DECSTBM is not tracked, so the scroll region is always the full screen.
+    fn index(&mut self) {
+        if self.cursor_pos.0 + 1 < self.height {
+            self.cursor_pos.0 += 1;
+        } else if !self.cells.is_empty() {
+            self.cells.remove(0);
+            self.cells.push(vec![Cell::default(); self.width as usize]);
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Moves the cursor up one line, scrolling the screen down at the top.
+    fn reverse_index(&mut self) {
+        if self.cursor_pos.0 > 0 {
+            self.cursor_pos.0 -= 1;
+        } else if !self.cells.is_empty() {
+            self.cells.pop();
+            self.cells
+                .insert(0, vec![Cell::default(); self.width as usize]);
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Marks every row as dirty, e.g. after the screen scrolls.
+    fn mark_all_dirty(&mut self) {
+        self.dirty_rows.extend(0..self.height);
     }
 
     /// Parse raster attributes from sixel data.
@@ -522,18 +899,21 @@ impl TerminalState {
         const PIXELS_PER_COL: u32 = 8;
         const PIXELS_PER_ROW: u32 = 6;
 
-        let cols = if width_px > 0 {
-            ((width_px + PIXELS_PER_COL - 1) / PIXELS_PER_COL) as u16
-        } else {
-            0
-        };
-
-        let rows = if height_px > 0 {
-            ((height_px + PIXELS_PER_ROW - 1) / PIXELS_PER_ROW) as u16
-        } else {
-            0
-        };
+        Self::pixels_to_cells_sized(width_px, height_px, PIXELS_PER_COL, PIXELS_PER_ROW)
+    }
 
+    /// Converts pixel dimensions to cells of the given pixel size.
+    ///
+    /// Fractional cells are rounded up. Cell dimensions of zero are treated
+    /// as one pixel.
+    fn pixels_to_cells_sized(
+        width_px: u32,
+        height_px: u32,
+        cell_width: u32,
+        cell_height: u32,
+    ) -> (u16, u16) {
+        let cols = width_px.div_ceil(cell_width.max(1)) as u16;
+        let rows = height_px.div_ceil(cell_height.max(1)) as u16;
         (cols, rows)
     }
 
@@ -636,16 +1016,13 @@ impl VTActor for TerminalState {
                 // Carriage return
                 self.cursor_pos.1 = 0;
             }
-            b'\n' => {
-                // Line feed
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+            b'\n' | 0x0b | 0x0c => {
+                // Line feed, vertical tab and form feed all act like IND
+                self.index();
             }
             b'\t' => {
-                // Tab - advance to next tab stop (every 8 columns)
-                let next_tab = ((self.cursor_pos.1 / 8) + 1) * 8;
-                self.cursor_pos.1 = next_tab.min(self.width - 1);
+                // Tab - advance to next tab stop
+                self.cursor_pos.1 = self.next_tab_stop(self.cursor_pos.1);
             }
             _ => {}
         }
@@ -655,24 +1032,33 @@ impl VTActor for TerminalState {
         &mut self,
         mode: u8,
         params: &[i64],
-        _intermediates: &[u8],
+        intermediates: &[u8],
         _ignored_excess_intermediates: bool,
     ) {
-        // Sixel sequences are identified by mode byte 'q' (0x71)
-        if mode == b'q' {
+        // Sixel sequences are identified by mode byte 'q' (0x71) with no
+        // intermediates; `$q` (DECRQSS) and `+q` (XTGETTCAP) are queries
+        if mode == b'q' && intermediates.is_empty() {
             self.in_sixel_mode = true;
             self.current_sixel_data.clear();
             self.current_sixel_params = params.to_vec();
+        } else if self.record_dcs {
+            self.current_dcs = Some((mode, params.to_vec(), Vec::new()));
         }
     }
 
     fn dcs_put(&mut self, byte: u8) {
         if self.in_sixel_mode {
             self.current_sixel_data.push(byte);
+        } else if let Some((_, _, data)) = self.current_dcs.as_mut() {
+            data.push(byte);
         }
     }
 
     fn dcs_unhook(&mut self) {
+        if let Some(dcs) = self.current_dcs.take() {
+            self.dcs_log.push(dcs);
+        }
+
         if self.in_sixel_mode {
             // Parse dimensions from raster attributes if present
             let (width, height) = self
@@ -715,12 +1101,20 @@ impl VTActor for TerminalState {
             b'B' => {
                 // CUD - Cursor Down
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
-                self.cursor_pos.0 = (self.cursor_pos.0 + n).min(self.height - 1);
+                self.cursor_pos.0 = self
+                    .cursor_pos
+                    .0
+                    .saturating_add(n)
+                    .min(self.height.saturating_sub(1));
             }
             b'C' => {
                 // CUF - Cursor Forward
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
-                self.cursor_pos.1 = (self.cursor_pos.1 + n).min(self.width - 1);
+                self.cursor_pos.1 = self
+                    .cursor_pos
+                    .1
+                    .saturating_add(n)
+                    .min(self.width.saturating_sub(1));
             }
             b'D' => {
                 // CUB - Cursor Back
@@ -729,60 +1123,92 @@ impl VTActor for TerminalState {
             }
             b'm' => {
                 // SGR - Select Graphic Rendition (colors and attributes)
-                let integers: Vec<i64> = params.iter().filter_map(|p| p.as_integer()).collect();
+                let mut groups = sgr_groups(params);
 
                 // Handle empty params (reset)
-                if integers.is_empty() {
-                    self.current_fg = None;
-                    self.current_bg = None;
-                    self.current_bold = false;
-                    self.current_italic = false;
-                    self.current_underline = false;
-                    return;
+                if groups.is_empty() {
+                    groups.push(vec![0]);
                 }
 
                 let mut i = 0;
-                while i < integers.len() {
-                    match integers[i] {
+                while i < groups.len() {
+                    let code = groups[i][0];
+                    match code {
                         0 => {
                             // Reset all attributes
                             self.current_fg = None;
                             self.current_bg = None;
                             self.current_bold = false;
                             self.current_italic = false;
-                            self.current_underline = false;
+                            self.current_underline = UnderlineStyle::None;
+                            self.current_underline_color = None;
                         }
                         1 => self.current_bold = true,
                         3 => self.current_italic = true,
-                        4 => self.current_underline = true,
+                        // Plain underline, or a styled one as 4:n
+                        4 => match groups[i].get(1) {
+                            None => self.current_underline = UnderlineStyle::Single,
+                            Some(&n) => {
+                                if let Some(style) = UnderlineStyle::from_sgr(n) {
+                                    self.current_underline = style;
+                                }
+                            }
+                        },
+                        21 => self.current_underline = UnderlineStyle::Double,
                         22 => self.current_bold = false,
                         23 => self.current_italic = false,
-                        24 => self.current_underline = false,
+                        24 => self.current_underline = UnderlineStyle::None,
                         // Foreground colors (30-37: standard, 90-97: bright)
-                        30..=37 => self.current_fg = Some((integers[i] - 30) as u8),
-                        90..=97 => self.current_fg = Some((integers[i] - 90 + 8) as u8),
+                        30..=37 => self.current_fg = Some((code - 30) as u8),
+                        90..=97 => self.current_fg = Some((code - 90 + 8) as u8),
                         39 => self.current_fg = None, // Default foreground
                         // Background colors (40-47: standard, 100-107: bright)
-                        40..=47 => self.current_bg = Some((integers[i] - 40) as u8),
-                        100..=107 => self.current_bg = Some((integers[i] - 100 + 8) as u8),
+                        40..=47 => self.current_bg = Some((code - 40) as u8),
+                        100..=107 => self.current_bg = Some((code - 100 + 8) as u8),
                         49 => self.current_bg = None, // Default background
-                        // 256-color mode: ESC[38;5;N or ESC[48;5;N
-                        38 | 48 => {
-                            if i + 2 < integers.len() && integers[i + 1] == 5 {
-                                let color = integers[i + 2] as u8;
-                                if integers[i] == 38 {
-                                    self.current_fg = Some(color);
-                                } else {
-                                    self.current_bg = Some(color);
-                                }
-                                i += 2; // Skip the '5' and color value
+                        // Extended colors: ESC[38;5;N, ESC[48;2;R;G;B, ESC[58:5:N, ...
+                        // Only palette colors are tracked for fg/bg, but 24-bit
+                        // ones are still consumed so their values are not read
+                        // as attributes.
+                        38 | 48 | 58 => {
+                            let (color, consumed) = sgr_extended_color(&groups, i);
+                            match (code, color) {
+                                (38, Some(UnderlineColor::Indexed(n))) => self.current_fg = Some(n),
+                                (48, Some(UnderlineColor::Indexed(n))) => self.current_bg = Some(n),
+                                (58, Some(color)) => self.current_underline_color = Some(color),
+                                _ => {}
                             }
+                            i += consumed;
                         }
+                        // Default underline color
+                        59 => self.current_underline_color = None,
                         _ => {} // Ignore unknown SGR codes
                     }
                     i += 1;
                 }
             }
+            b'g' => {
+                // TBC - Tab Clear: 0 (default) clears the stop at the cursor, 3 clears all
+                match params.iter().find_map(|p| p.as_integer()).unwrap_or(0) {
+                    0 => {
+                        self.tab_stops.remove(&self.cursor_pos.1);
+                    }
+                    3 => self.tab_stops.clear(),
+                    _ => {}
+                }
+            }
+            b'h' | b'l' => {
+                // DECSET/DECRST - private modes are prefixed with '?'
+                if matches!(params.first(), Some(CsiParam::P(b'?'))) {
+                    let enabled = byte == b'h';
+                    for mode in params.iter().filter_map(|p| p.as_integer()) {
+                        if let Ok(mode) = u16::try_from(mode) {
+                            self.private_modes.set(mode, enabled);
+                        }
+                    }
+                }
+            }
+            b'u' => self.kitty_keyboard(params),
             _ => {}
         }
     }
@@ -790,24 +1216,34 @@ impl VTActor for TerminalState {
     fn esc_dispatch(
         &mut self,
         _params: &[i64],
-        _intermediates: &[u8],
+        intermediates: &[u8],
         _ignored_excess_intermediates: bool,
         byte: u8,
     ) {
+        // Charset designators (ESC ( H) and DEC sequences (ESC # 8) share
+        // final bytes with the sequences below
+        if !intermediates.is_empty() {
+            return;
+        }
+
         match byte {
             b'D' => {
-                // IND - Index (move cursor down)
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+                // IND - Index (move cursor down, scrolling at the bottom)
+                self.index();
             }
             b'E' => {
                 // NEL - Next Line
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+                self.index();
                 self.cursor_pos.1 = 0;
             }
+            b'M' => {
+                // RI - Reverse Index (move cursor up, scrolling at the top)
+                self.reverse_index();
+            }
+            b'H' => {
+                // HTS - Horizontal Tab Set at the cursor column
+                self.tab_stops.insert(self.cursor_pos.1);
+            }
             _ => {}
         }
     }
@@ -819,6 +1255,47 @@ impl VTActor for TerminalState {
             return;
         }
 
+        if self.record_osc {
+            if let Some(number) = std::str::from_utf8(params[0])
+                .ok()
+                .and_then(|n| n.parse::<u32>().ok())
+            {
+                self.osc_log.push((number, params[1..].join(&b';')));
+            }
+        }
+
+        // OSC 7 reports the working directory as a file:// URI
+        if params[0] == b"7" {
+            let uri = params[1..].join(&b';');
+            if let Some(path) = std::str::from_utf8(&uri).ok().and_then(parse_osc7_path) {
+                self.current_directory = Some(path);
+            }
+            return;
+        }
+
+        // OSC 52 writes base64 text to a selection: 52 ; <targets> ; <data>
+        if params[0] == b"52" && params.len() >= 3 {
+            if let Some(text) = parse_osc52_payload(params[1], params[2]) {
+                self.clipboard = Some(text);
+            }
+            return;
+        }
+
+        // OSC 12 sets the cursor color, OSC 112 resets it
+        if params[0] == b"12" && params.len() >= 2 {
+            if let Some(color) = std::str::from_utf8(params[1])
+                .ok()
+                .and_then(parse_color_spec)
+            {
+                self.cursor_color = Some(color);
+            }
+            return;
+        }
+        if params[0] == b"112" {
+            self.cursor_color = None;
+            return;
+        }
+
         // Check if this is an iTerm2 inline image (OSC 1337;File=...)
         if let Ok(first_param) = std::str::from_utf8(params[0]) {
             if first_param.starts_with("1337;File=") || first_param == "1337" {
@@ -883,6 +1360,309 @@ impl VTActor for TerminalState {
     }
 }
 
+/// An escape sequence found by [`scan_escape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EscapeSequence<'a> {
+    /// An OSC string, with the payload between `ESC ]` and the terminator.
+    Osc(&'a [u8]),
+    /// Any other sequence: CSI, DCS/APC/PM/SOS strings, or a plain escape.
+    Other,
+}
+
+/// Measures the escape sequence at the start of `data`, which must begin
+/// with `ESC`.
+///
+/// Returns the sequence and its length including any terminator, or `None`
+/// if `data` ends before the sequence does. OSC strings end at BEL or ST;
+/// DCS, APC, PM and SOS strings end at ST; CSI sequences end at the first
+/// byte outside the parameter and intermediate range.
+pub(crate) fn scan_escape(data: &[u8]) -> Option<(EscapeSequence<'_>, usize)> {
+    debug_assert_eq!(data.first(), Some(&0x1b));
+    let find_st = |from: usize| {
+        (from..data.len().saturating_sub(1)).find(|&i| data[i] == 0x1b && data[i + 1] == b'\\')
+    };
+
+    match *data.get(1)? {
+        b']' => {
+            let end = (2..data.len()).find(|&i| {
+                data[i] == 0x07 || (data[i] == 0x1b && data.get(i + 1) == Some(&b'\\'))
+            })?;
+            let len = if data[end] == 0x07 { end + 1 } else { end + 2 };
+            Some((EscapeSequence::Osc(&data[2..end]), len))
+        }
+        b'P' | b'_' | b'^' | b'X' => Some((EscapeSequence::Other, find_st(2)? + 2)),
+        b'[' => {
+            let end = (2..data.len()).find(|&i| !(0x20..0x40).contains(&data[i]))?;
+            Some((EscapeSequence::Other, end + 1))
+        }
+        _ => {
+            // Intermediate bytes (e.g. `ESC ( B`) followed by a final byte
+            let end = (1..data.len()).find(|&i| !(0x20..0x30).contains(&data[i]))?;
+            Some((EscapeSequence::Other, end + 1))
+        }
+    }
+}
+
+/// Removes escape sequences from terminal output, leaving the visible text.
+///
+/// CSI sequences (SGR colors, cursor moves, erases), OSC strings (titles,
+/// hyperlinks, shell integration) and DCS/APC strings (Sixel, Kitty
+/// graphics) are dropped, as are control characters other than newline and
+/// tab. Cursor movement is not interpreted, so text meant to overwrite other
+/// text is simply concatenated; feed the bytes to a [`ScreenState`] to see
+/// what would actually be rendered. A sequence cut off at the end of
+/// `input` is dropped. Invalid UTF-8 is replaced with U+FFFD.
+///
+/// Useful for normalizing reference transcripts that were captured with
+/// colors before comparing them to plain screen text.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::strip_ansi;
+///
+/// let fixture = b"\x1b[1;32mok\x1b[0m: 3 passed\r\n\x1b]0;title\x07done";
+/// assert_eq!(strip_ansi(fixture), "ok: 3 passed\ndone");
+/// ```
+pub fn strip_ansi(input: &[u8]) -> String {
+    let mut visible = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            0x1b => match scan_escape(&input[i..]) {
+                Some((_, len)) => i += len,
+                None => break,
+            },
+            b'\n' | b'\t' => {
+                visible.push(input[i]);
+                i += 1;
+            }
+            byte if byte < 0x20 || byte == 0x7f => i += 1,
+            byte => {
+                visible.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&visible).into_owned()
+}
+
+/// Extracts the directory path from an OSC 7 `file://host/path` URI.
+///
+/// The host part is ignored and percent-encoded bytes in the path are decoded.
+/// Returns `None` if the URI is not a `file://` URI with an absolute path.
+pub(crate) fn parse_osc7_path(uri: &str) -> Option<String> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Splits SGR parameters into `;`-separated groups of `:`-separated values.
+///
+/// Omitted values read as 0, so `ESC[;4:3m` yields `[[0], [4, 3]]` and
+/// `ESC[58:2::255:0:0m` yields `[[58, 2, 0, 255, 0, 0]]`.
+fn sgr_groups(params: &[CsiParam]) -> Vec<Vec<i64>> {
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    // Whether a separator was seen with no value after it yet
+    let mut pending = true;
+
+    for param in params {
+        match param {
+            CsiParam::Integer(n) => {
+                group.push(*n);
+                pending = false;
+            }
+            CsiParam::P(b':') => {
+                if pending {
+                    group.push(0);
+                }
+                pending = true;
+            }
+            CsiParam::P(b';') => {
+                if pending {
+                    group.push(0);
+                }
+                groups.push(std::mem::take(&mut group));
+                pending = true;
+            }
+            CsiParam::P(_) => {}
+        }
+    }
+    if !params.is_empty() {
+        if pending {
+            group.push(0);
+        }
+        groups.push(group);
+    }
+    groups
+}
+
+/// Reads the extended color selected by the SGR 38/48/58 group at `i`.
+///
+/// The colon form keeps everything in one group (`58:5:n`, `58:2::r:g:b`, or
+/// `58:2:r:g:b` without the color-space slot); the semicolon form spreads it
+/// over the following groups (`58;5;n`, `58;2;r;g;b`). Returns the color, if
+/// well-formed, and how many following groups it used.
+fn sgr_extended_color(groups: &[Vec<i64>], i: usize) -> (Option<UnderlineColor>, usize) {
+    let byte = |n: i64| u8::try_from(n).ok();
+    let rgb = |r, g, b| Some(UnderlineColor::Rgb(byte(r)?, byte(g)?, byte(b)?));
+
+    match groups[i][1..] {
+        [] => {}
+        [5, n] => return (byte(n).map(UnderlineColor::Indexed), 0),
+        [2, r, g, b] | [2, _, r, g, b, ..] => return (rgb(r, g, b), 0),
+        _ => return (None, 0),
+    }
+
+    let next = |offset: usize| groups.get(i + offset).map(|group| group[0]);
+    match next(1) {
+        Some(5) => match next(2) {
+            Some(n) => (byte(n).map(UnderlineColor::Indexed), 2),
+            None => (None, 1),
+        },
+        Some(2) => match (next(2), next(3), next(4)) {
+            (Some(r), Some(g), Some(b)) => (rgb(r, g, b), 4),
+            _ => (None, groups.len() - i - 1),
+        },
+        _ => (None, 0),
+    }
+}
+
+/// Compares a row's text against `expected`, ignoring trailing spaces on both.
+///
+/// Returns `None` when they match, otherwise a message showing both strings
+/// with a caret under the first differing column.
+pub(crate) fn row_mismatch(row: u16, expected: &str, actual: &str) -> Option<String> {
+    let expected = expected.trim_end_matches(' ');
+    let actual = actual.trim_end_matches(' ');
+    if expected == actual {
+        return None;
+    }
+
+    let column = expected
+        .chars()
+        .zip(actual.chars())
+        .take_while(|(e, a)| e == a)
+        .count();
+    Some(format!(
+        "Row {} mismatch at column {}\n  Expected: \"{}\"\n  Found:    \"{}\"\n  {:>width$}",
+        row,
+        column,
+        expected,
+        actual,
+        "^",
+        width = column + 12
+    ))
+}
+
+/// Decodes the text of an OSC 52 clipboard write.
+///
+/// `targets` lists the selections being written (`c` clipboard, `p` primary,
+/// `s` select, `q` secondary, or cut buffers `0`-`7`); an empty list means the
+/// terminal default. Returns `None` for unknown targets, clipboard queries
+/// (`?`), and payloads that are not valid base64.
+pub(crate) fn parse_osc52_payload(targets: &[u8], data: &[u8]) -> Option<String> {
+    if !targets.iter().all(|t| b"cpqs01234567".contains(t)) || data == b"?" {
+        return None;
+    }
+    let decoded = decode_base64(data)?;
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Parses an X11 color specification into RGBA with full alpha.
+///
+/// Accepts `#rgb`, `#rrggbb`, and `rgb:r/g/b` with one to four hex digits
+/// per channel, each scaled to 8 bits. Returns `None` for queries (`?`),
+/// color names, and malformed specs.
+pub(crate) fn parse_color_spec(spec: &str) -> Option<u32> {
+    fn channel(digits: &str) -> Option<u32> {
+        if digits.is_empty() || digits.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        let max = (1u32 << (4 * digits.len())) - 1;
+        Some((value * 0xFF + max / 2) / max)
+    }
+
+    let (r, g, b) = if let Some(hex) = spec.strip_prefix('#') {
+        let width = match hex.len() {
+            3 => 1,
+            6 => 2,
+            _ => return None,
+        };
+        if !hex.is_ascii() {
+            return None;
+        }
+        (
+            channel(&hex[..width])?,
+            channel(&hex[width..2 * width])?,
+            channel(&hex[2 * width..])?,
+        )
+    } else {
+        let mut parts = spec.strip_prefix("rgb:")?.split('/');
+        let rgb = (channel(parts.next()?)?, channel(parts.next()?)?, channel(parts.next()?)?);
+        if parts.next().is_some() {
+            return None;
+        }
+        rgb
+    };
+    Some((r << 24) | (g << 16) | (b << 8) | 0xFF)
+}
+
+/// Decodes standard (RFC 4648) base64, with or without `=` padding.
+fn decode_base64(data: &[u8]) -> Option<Vec<u8>> {
+    fn sextet(byte: u8) -> Option<u32> {
+        match byte {
+            b'A'..=b'Z' => Some((byte - b'A') as u32),
+            b'a'..=b'z' => Some((byte - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((byte - b'0' + 52) as u32),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let data = data
+        .strip_suffix(b"==")
+        .or_else(|| data.strip_suffix(b"="))
+        .unwrap_or(data);
+    if data.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut bits = 0u32;
+        for &byte in chunk {
+            bits = (bits << 6) | sextet(byte)?;
+        }
+        bits <<= 6 * (4 - chunk.len() as u32);
+        decoded.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    Some(decoded)
+}
+
+/// Default character cell size in pixels, as (width, height).
+const DEFAULT_CELL_PIXELS: (u32, u32) = (10, 20);
+
 /// Represents the current state of the terminal screen.
 ///
 /// `ScreenState` is the core terminal emulator that tracks:
@@ -922,12 +1702,16 @@ pub struct ScreenState {
     state: TerminalState,
     width: u16,
     height: u16,
+    /// Size of a character cell in pixels, for graphics footprints
+    cell_pixels: (u32, u32),
 }
 
 impl ScreenState {
     /// Creates a new screen state with the specified dimensions.
     ///
     /// Initializes an empty screen filled with spaces, with the cursor at (0, 0).
+    /// A zero width or height gives a screen with no writable cells, where
+    /// feeding data only updates parser state.
     ///
     /// # Arguments
     ///
@@ -947,7 +1731,13 @@ impl ScreenState {
         let parser = VTParser::new();
         let state = TerminalState::new(width, height);
 
-        Self { parser, state, width, height }
+        Self {
+            parser,
+            state,
+            width,
+            height,
+            cell_pixels: DEFAULT_CELL_PIXELS,
+        }
     }
 
     /// Feeds data from the PTY to the parser.
@@ -983,14 +1773,49 @@ impl ScreenState {
         self.parser.parse(data, &mut self.state);
     }
 
-    /// Returns the screen contents as a string.
+    /// Changes the screen size without clearing it.
     ///
-    /// This includes all visible characters, preserving layout with newlines
-    /// between rows. Empty cells are represented as spaces.
+    /// Like a real terminal, cells in the overlapping top-left area keep their
+    /// contents; rows and columns that no longer fit are dropped and new ones
+    /// start blank. The cursor is clamped onto the new screen. Text is not
+    /// reflowed, and parser state, modes and OSC-reported values are kept.
+    /// If the size changed, every row is marked [dirty](Self::dirty_rows).
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A string containing the entire screen contents, with rows separated by newlines.
+    /// * `width` - New width in columns
+    /// * `height` - New height in rows
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 2);
+    /// screen.feed(b"Hello");
+    ///
+    /// screen.resize(20, 4);
+    /// assert_eq!(screen.size(), (20, 4));
+    /// assert_eq!(screen.row_text(0), "Hello");
+    ///
+    /// screen.resize(3, 1);
+    /// assert_eq!(screen.row_text(0), "Hel");
+    /// assert_eq!(screen.cursor_position(), (0, 2));
+    /// ```
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.state.resize(width, height);
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Returns the screen contents as a string.
+    ///
+    /// This includes all visible characters, preserving layout with newlines
+    /// between rows. Empty cells are represented as spaces.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the entire screen contents, with rows separated by newlines.
     ///
     /// # Example
     ///
@@ -1034,317 +1859,1158 @@ impl ScreenState {
         }
     }
 
-    /// Returns the character at a specific position.
-    ///
-    /// # Arguments
-    ///
-    /// * `row` - Row index (0-based)
-    /// * `col` - Column index (0-based)
-    ///
-    /// # Returns
-    ///
-    /// The character at the position, or None if out of bounds.
-    pub fn text_at(&self, row: u16, col: u16) -> Option<char> {
-        if row < self.height && col < self.width {
-            Some(self.state.cells[row as usize][col as usize].c)
-        } else {
-            None
-        }
-    }
-
-    /// Returns the complete cell (character + attributes) at a specific position.
+    /// Returns the text of a specific row with trailing spaces trimmed.
     ///
-    /// This method provides access to the full cell state including colors and
-    /// text attributes, enabling verification of ANSI escape sequence handling.
+    /// Interior spaces are kept; use [`row_contents`](Self::row_contents) for
+    /// the full padded row.
     ///
     /// # Arguments
     ///
     /// * `row` - Row index (0-based)
-    /// * `col` - Column index (0-based)
     ///
     /// # Returns
     ///
-    /// A reference to the cell, or None if out of bounds.
+    /// The trimmed row text, or empty string if row is out of bounds.
     ///
     /// # Example
     ///
     /// ```rust
     /// use terminal_testlib::ScreenState;
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mRed\x1b[0m");
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"Name:  Alice");
     ///
-    /// if let Some(cell) = screen.get_cell(0, 0) {
-    ///     assert_eq!(cell.c, 'R');
-    ///     assert_eq!(cell.fg, Some(1)); // Red = color 1
-    /// }
+    /// assert_eq!(screen.row_text(0), "Name:  Alice");
+    /// assert_eq!(screen.row_contents(0).len(), 20);
     /// ```
-    pub fn get_cell(&self, row: u16, col: u16) -> Option<&Cell> {
-        if row < self.height && col < self.width {
-            Some(&self.state.cells[row as usize][col as usize])
-        } else {
-            None
-        }
-    }
-
-    /// Returns the current cursor position.
-    ///
-    /// # Returns
-    ///
-    /// A tuple of (row, col) with 0-based indexing.
-    pub fn cursor_position(&self) -> (u16, u16) {
-        self.state.cursor_pos
+    pub fn row_text(&self, row: u16) -> String {
+        self.row_contents(row).trim_end_matches(' ').to_string()
     }
 
-    /// Returns the screen dimensions.
+    /// Returns the indices of rows containing anything other than spaces.
     ///
     /// # Returns
     ///
-    /// A tuple of (width, height) in columns and rows.
-    pub fn size(&self) -> (u16, u16) {
-        (self.width, self.height)
-    }
-
-    /// Returns the screen width in columns.
-    ///
-    /// This is a convenience method equivalent to `self.size().0`.
+    /// Row indices (0-based) in top-to-bottom order.
     ///
     /// # Example
     ///
     /// ```rust
     /// use terminal_testlib::ScreenState;
     ///
-    /// let screen = ScreenState::new(80, 24);
-    /// assert_eq!(screen.cols(), 80);
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"Header\r\n\r\nFooter");
+    ///
+    /// assert_eq!(screen.nonblank_rows(), vec![0, 2]);
     /// ```
-    pub fn cols(&self) -> u16 {
-        self.width
+    pub fn nonblank_rows(&self) -> Vec<u16> {
+        self.state
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.iter().any(|cell| cell.c != ' '))
+            .map(|(index, _)| index as u16)
+            .collect()
     }
 
-    /// Returns the screen height in rows.
+    /// Asserts that a row reads exactly `expected`, ignoring trailing spaces.
     ///
-    /// This is a convenience method equivalent to `self.size().1`.
+    /// Leading and interior spaces are significant, so this checks alignment
+    /// as well as text.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// use terminal_testlib::ScreenState;
+    /// * `row` - Row index (0-based)
+    /// * `expected` - Expected row text
     ///
-    /// let screen = ScreenState::new(80, 24);
-    /// assert_eq!(screen.rows(), 24);
-    /// ```
-    pub fn rows(&self) -> u16 {
-        self.height
-    }
-
-    /// Returns an iterator over all rows in the screen.
+    /// # Errors
     ///
-    /// Each item in the iterator is a reference to a row (a slice of cells).
+    /// Returns [`TermTestError::Parse`] if the row is out of bounds or differs.
+    /// The message shows both strings with a caret under the first differing
+    /// column.
     ///
     /// # Example
     ///
     /// ```rust
     /// use terminal_testlib::ScreenState;
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"Line 1\r\nLine 2");
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"    === Menu ===");
     ///
-    /// for (row_idx, row) in screen.iter_rows().enumerate() {
-    ///     println!("Row {}: {} cells", row_idx, row.len());
-    ///     for (col_idx, cell) in row.iter().enumerate() {
-    ///         if cell.c != ' ' {
-    ///             println!("  Cell ({}, {}): '{}'", row_idx, col_idx, cell.c);
-    ///         }
-    ///     }
-    /// }
+    /// assert!(screen.assert_row_eq(0, "    === Menu ===").is_ok());
+    /// assert!(screen.assert_row_eq(0, "=== Menu ===").is_err());
     /// ```
-    pub fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
-        self.state.cells.iter().map(|row| row.as_slice())
+    pub fn assert_row_eq(&self, row: u16, expected: &str) -> Result<()> {
+        if row >= self.height {
+            return Err(TermTestError::Parse(format!(
+                "Row {} is out of bounds (screen height: {})",
+                row, self.height
+            )));
+        }
+
+        match row_mismatch(row, expected, &self.row_text(row)) {
+            Some(message) => Err(TermTestError::Parse(message)),
+            None => Ok(()),
+        }
     }
 
-    /// Returns an iterator over all cells in a specific row.
+    /// Asserts that the whole screen reads exactly `expected`.
     ///
-    /// Returns `None` if the row index is out of bounds.
+    /// Line `n` of `expected` is compared with row `n` using the rules of
+    /// [`assert_row_eq`](Self::assert_row_eq). Missing lines count as blank, so
+    /// trailing blank rows need not be written out.
     ///
     /// # Arguments
     ///
-    /// * `row` - Row index (0-based)
+    /// * `expected` - Expected screen text, one line per row
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if any row differs, or if `expected` has
+    /// non-blank lines below the last row. The message shows each differing row
+    /// with a caret under its first differing column.
     ///
     /// # Example
     ///
     /// ```rust
     /// use terminal_testlib::ScreenState;
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mRed\x1b[32mGreen\x1b[34mBlue");
-    ///
-    /// // Use the iterator immediately to avoid lifetime issues
-    /// let colored_cells: Vec<_> = screen
-    ///     .iter_row(0)
-    ///     .unwrap()
-    ///     .enumerate()
-    ///     .filter(|(_, cell)| cell.fg.is_some())
-    ///     .collect();
+    /// let mut screen = ScreenState::new(10, 4);
+    /// screen.feed(b"+------+\r\n| ok   |\r\n+------+");
     ///
-    /// assert!(colored_cells.len() >= 3, "Should have colored cells");
+    /// assert!(screen
+    ///     .assert_screen_eq(
+    ///         "+------+
+    /// | ok   |
+    /// +------+",
+    ///     )
+    ///     .is_ok());
     /// ```
-    pub fn iter_row(&self, row: u16) -> Option<impl Iterator<Item = &Cell>> {
-        if row < self.height {
-            Some(self.state.cells[row as usize].iter())
+    pub fn assert_screen_eq(&self, expected: &str) -> Result<()> {
+        let expected: Vec<&str> = expected.lines().collect();
+        let rows = expected.len().max(self.height as usize);
+
+        let mismatches: Vec<String> = (0..rows)
+            .filter_map(|row| {
+                let line = expected.get(row).copied().unwrap_or("");
+                row_mismatch(row as u16, line, &self.row_text(row as u16))
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
         } else {
-            None
+            Err(TermTestError::Parse(format!(
+                "Screen mismatch in {} row(s)\n{}",
+                mismatches.len(),
+                mismatches.join("\n")
+            )))
         }
     }
 
-    /// Returns a structured snapshot of the entire screen grid.
+    /// Returns the text of a rectangular block of the screen, one string per row.
     ///
-    /// This method provides a complete copy of the screen state suitable for
-    /// deep comparison with other terminal emulators or for serialization.
+    /// The block is clipped to the screen, so rows and columns past the edge
+    /// are left out rather than padded.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A `GridSnapshot` containing:
-    /// - Grid dimensions (width, height)
-    /// - Complete cell data (2D vector of cells)
-    /// - Current cursor position
+    /// * `bounds` - Block to extract, e.g. from
+    ///   [`UiRegionTester::region_bounds`](crate::regions::UiRegionTester::region_bounds)
     ///
     /// # Example
     ///
     /// ```rust
-    /// use terminal_testlib::ScreenState;
+    /// use terminal_testlib::{regions::RegionBounds, ScreenState};
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mHello");
+    /// let mut screen = ScreenState::new(20, 4);
+    /// screen.feed(b"\x1b[2;5H+----+\x1b[3;5H| ok |\x1b[4;5H+----+");
     ///
-    /// let snapshot = screen.snapshot();
-    /// assert_eq!(snapshot.width, 80);
-    /// assert_eq!(snapshot.height, 24);
-    /// assert_eq!(snapshot.cells[0][0].c, 'H');
-    /// assert_eq!(snapshot.cells[0][0].fg, Some(1)); // Red
-    /// assert_eq!(snapshot.cursor, (0, 5));
+    /// assert_eq!(
+    ///     screen.block_text(RegionBounds::new(1, 4, 6, 3)),
+    ///     vec!["+----+", "| ok |", "+----+"]
+    /// );
     /// ```
-    pub fn snapshot(&self) -> GridSnapshot {
-        GridSnapshot {
-            width: self.width,
-            height: self.height,
-            cells: self.state.cells.clone(),
-            cursor: self.state.cursor_pos,
-        }
+    #[cfg(feature = "ipc")]
+    pub fn block_text(&self, bounds: crate::regions::RegionBounds) -> Vec<String> {
+        let row_end = bounds.row.saturating_add(bounds.height).min(self.height);
+        let col_end = bounds.col.saturating_add(bounds.width).min(self.width);
+
+        (bounds.row..row_end)
+            .map(|row| {
+                (bounds.col..col_end)
+                    .map(|col| self.state.cells[row as usize][col as usize].c)
+                    .collect()
+            })
+            .collect()
     }
 
-    /// Returns all Sixel graphics regions currently on screen.
+    /// Asserts that a rectangular block of the screen reads exactly `expected`.
     ///
-    /// This method provides access to all Sixel graphics that have been rendered
-    /// via DCS (Device Control String) sequences. Each region includes position
-    /// and dimension information.
+    /// Line `n` of `expected` is compared with row `n` of the block, cell by
+    /// cell. Short lines and missing lines count as blank, so trailing spaces
+    /// need not be written out. Where the block runs past the screen edge,
+    /// the clipped cells must be expected blank.
     ///
-    /// This is essential for verifying Sixel positioning in tests, particularly
-    /// for ensuring that graphics appear within designated preview areas.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `bounds` - Block to compare
+    /// * `expected` - Expected block text, one line per row
     ///
-    /// A slice of [`SixelRegion`] containing all detected Sixel graphics.
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if `expected` does not fit in `bounds`
+    /// or any cell differs. The message lists each differing cell at its
+    /// screen position, followed by the expected and actual blocks.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use terminal_testlib::ScreenState;
+    /// use terminal_testlib::{regions::RegionBounds, ScreenState};
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// // ... render some Sixel graphics ...
+    /// let mut screen = ScreenState::new(20, 4);
+    /// screen.feed(b"menu\x1b[2;5H+----+\x1b[3;5H| ok |\x1b[4;5H+----+");
     ///
-    /// let regions = screen.sixel_regions();
-    /// for (i, region) in regions.iter().enumerate() {
-    ///     println!(
-    ///         "Region {}: position ({}, {}), size {}x{}",
-    ///         i, region.start_row, region.start_col, region.width, region.height
-    ///     );
-    /// }
+    /// let dialog = RegionBounds::new(1, 4, 6, 3);
+    /// assert!(screen.assert_block_eq(dialog, &["+----+", "| ok |", "+----+"]).is_ok());
+    /// assert!(screen.assert_block_eq(dialog, &["+----+", "| no |", "+----+"]).is_err());
     /// ```
-    pub fn sixel_regions(&self) -> &[SixelRegion] {
-        &self.state.sixel_regions
+    #[cfg(feature = "ipc")]
+    pub fn assert_block_eq(
+        &self,
+        bounds: crate::regions::RegionBounds,
+        expected: &[&str],
+    ) -> Result<()> {
+        let too_wide = expected
+            .iter()
+            .any(|line| line.chars().count() > bounds.width as usize);
+        if expected.len() > bounds.height as usize || too_wide {
+            return Err(TermTestError::Parse(format!(
+                "Expected block does not fit in {}x{} bounds at ({}, {})",
+                bounds.width, bounds.height, bounds.row, bounds.col
+            )));
+        }
+
+        let actual: Vec<Vec<char>> = self
+            .block_text(bounds)
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let mut mismatches = Vec::new();
+        for i in 0..bounds.height as usize {
+            let line: Vec<char> = expected
+                .get(i)
+                .map_or(Vec::new(), |line| line.chars().collect());
+            for j in 0..bounds.width as usize {
+                let want = line.get(j).copied().unwrap_or(' ');
+                let found = actual.get(i).and_then(|row| row.get(j)).copied();
+                let position = (bounds.row as usize + i, bounds.col as usize + j);
+                match found {
+                    Some(c) if c != want => mismatches.push(format!(
+                        "  ({}, {}): expected {:?}, found {:?}",
+                        position.0, position.1, want, c
+                    )),
+                    None if want != ' ' => mismatches.push(format!(
+                        "  ({}, {}): expected {:?}, but the cell is off screen",
+                        position.0, position.1, want
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        let frame = |lines: Vec<String>| {
+            lines
+                .iter()
+                .map(|line| format!("  |{:width$}|", line, width = bounds.width as usize))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        Err(TermTestError::Parse(format!(
+            "Block mismatch in {} cell(s) of {}x{} block at ({}, {})\n{}\n\nExpected:\n{}\nFound:\n{}",
+            mismatches.len(),
+            bounds.width,
+            bounds.height,
+            bounds.row,
+            bounds.col,
+            mismatches.join("\n"),
+            frame(expected.iter().map(|line| line.to_string()).collect()),
+            frame(self.block_text(bounds)),
+        )))
     }
 
-    /// Returns a mutable reference to the Sixel regions.
+    /// Asserts the underline shape of the cell at (`row`, `col`).
     ///
-    /// This is primarily intended for testing purposes, allowing tests to
-    /// inject mock Sixel data for memory profiling and other tests.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    /// * `expected` - Expected underline style
     ///
-    /// A mutable reference to the vector of Sixel regions.
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the cell is out of bounds or its
+    /// style differs.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use terminal_testlib::{screen::SixelRegion, ScreenState};
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
+    /// use terminal_testlib::{ScreenState, UnderlineStyle};
     ///
-    /// // Add a mock Sixel region for testing
-    /// screen.sixel_regions_mut().push(SixelRegion {
-    ///     start_row: 5,
-    ///     start_col: 10,
-    ///     width: 100,
-    ///     height: 50,
-    ///     data: vec![0u8; 1000],
-    /// });
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"let \x1b[4:3mx\x1b[24m = 1;");
     ///
-    /// assert_eq!(screen.sixel_regions().len(), 1);
+    /// assert!(screen.assert_cell_underline_style(0, 4, UnderlineStyle::Curly).is_ok());
+    /// assert!(screen.assert_cell_underline_style(0, 0, UnderlineStyle::Curly).is_err());
     /// ```
-    pub fn sixel_regions_mut(&mut self) -> &mut Vec<SixelRegion> {
-        &mut self.state.sixel_regions
+    pub fn assert_cell_underline_style(
+        &self,
+        row: u16,
+        col: u16,
+        expected: UnderlineStyle,
+    ) -> Result<()> {
+        let cell = self.cell_in_bounds(row, col)?;
+        if cell.underline_style != expected {
+            return Err(TermTestError::Parse(format!(
+                "Underline style mismatch at ({}, {}) on {:?}\n  Expected: {:?}\n  Found:    {:?}",
+                row, col, cell.c, expected, cell.underline_style
+            )));
+        }
+        Ok(())
     }
 
-    /// Checks if a Sixel region exists at the given position.
+    /// Asserts the underline color of the cell at (`row`, `col`).
     ///
-    /// This method checks if any Sixel region has its starting position
-    /// at the exact (row, col) coordinates provided.
+    /// Pass `None` to check that no `SGR 58` color applies, meaning the
+    /// underline is drawn in the foreground color.
     ///
     /// # Arguments
     ///
-    /// * `row` - Row to check (0-indexed)
-    /// * `col` - Column to check (0-indexed)
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    /// * `expected` - Expected underline color
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// `true` if a Sixel region starts at the given position, `false` otherwise.
+    /// Returns [`TermTestError::Parse`] if the cell is out of bounds or its
+    /// color differs.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use terminal_testlib::ScreenState;
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
+    /// use terminal_testlib::{ScreenState, UnderlineColor};
     ///
-    /// // Render a Sixel at position (5, 10) - ESC[5;10H moves to row 5, col 10 (1-based)
-    /// screen.feed(b"\x1b[5;10H"); // Move cursor
-    /// screen.feed(b"\x1bPq"); // Start Sixel
-    /// screen.feed(b"\"1;1;100;50#0~"); // Raster + data
-    /// screen.feed(b"\x1b\\"); // End Sixel
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"\x1b[4:3;58;2;255;0;0mtypo\x1b[0m");
     ///
-    /// // Check for Sixel at 0-based coordinates (4, 9)
-    /// assert!(screen.has_sixel_at(4, 9));
-    /// assert!(!screen.has_sixel_at(0, 0));
+    /// let red = UnderlineColor::Rgb(255, 0, 0);
+    /// assert!(screen.assert_cell_underline_color(0, 0, Some(red)).is_ok());
+    /// assert!(screen.assert_cell_underline_color(0, 4, None).is_ok());
     /// ```
-    pub fn has_sixel_at(&self, row: u16, col: u16) -> bool {
-        self.state
-            .sixel_regions
-            .iter()
-            .any(|region| region.start_row == row && region.start_col == col)
+    pub fn assert_cell_underline_color(
+        &self,
+        row: u16,
+        col: u16,
+        expected: Option<UnderlineColor>,
+    ) -> Result<()> {
+        let cell = self.cell_in_bounds(row, col)?;
+        if cell.underline_color != expected {
+            return Err(TermTestError::Parse(format!(
+                "Underline color mismatch at ({}, {}) on {:?}\n  Expected: {:?}\n  Found:    {:?}",
+                row, col, cell.c, expected, cell.underline_color
+            )));
+        }
+        Ok(())
     }
 
-    /// Returns all Kitty graphics regions currently on screen.
+    /// Returns the cell at (`row`, `col`), or a bounds error for assertions.
+    fn cell_in_bounds(&self, row: u16, col: u16) -> Result<&Cell> {
+        self.get_cell(row, col).ok_or_else(|| {
+            TermTestError::Parse(format!(
+                "Cell ({}, {}) is out of bounds (screen size: {}x{})",
+                row, col, self.width, self.height
+            ))
+        })
+    }
+
+    /// Returns the character at a specific position.
     ///
-    /// This method provides access to all Kitty graphics that have been rendered
-    /// via APC (Application Program Command) sequences. Each region includes position
-    /// and dimension information.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
     ///
-    /// A slice of [`KittyRegion`] containing all detected Kitty graphics.
+    /// # Returns
     ///
-    /// # Example
+    /// The character at the position, or None if out of bounds.
+    pub fn text_at(&self, row: u16, col: u16) -> Option<char> {
+        if row < self.height && col < self.width {
+            Some(self.state.cells[row as usize][col as usize].c)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the complete cell (character + attributes) at a specific position.
+    ///
+    /// This method provides access to the full cell state including colors and
+    /// text attributes, enabling verification of ANSI escape sequence handling.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// A reference to the cell, or None if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mRed\x1b[0m");
+    ///
+    /// if let Some(cell) = screen.get_cell(0, 0) {
+    ///     assert_eq!(cell.c, 'R');
+    ///     assert_eq!(cell.fg, Some(1)); // Red = color 1
+    /// }
+    /// ```
+    pub fn get_cell(&self, row: u16, col: u16) -> Option<&Cell> {
+        if row < self.height && col < self.width {
+            Some(&self.state.cells[row as usize][col as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the whole cell grid, indexed as `cells()[row][col]`.
+    ///
+    /// This is read-only structured access to every cell in one call, for
+    /// callers that need characters together with their colors and
+    /// attributes (exporters, diffs, attribute checks) without going
+    /// through [`get_cell()`](Self::get_cell) position by position. There is
+    /// always exactly [`rows()`](Self::rows) rows of [`cols()`](Self::cols)
+    /// cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 2);
+    /// screen.feed(b"\x1b[1mHi");
+    ///
+    /// let cells = screen.cells();
+    /// assert_eq!(cells.len(), 2);
+    /// assert_eq!(cells[0].len(), 10);
+    /// assert_eq!(cells[0][1].c, 'i');
+    /// assert!(cells[0][1].bold);
+    /// ```
+    pub fn cells(&self) -> &[Vec<Cell>] {
+        &self.state.cells
+    }
+
+    /// Returns the current cursor position.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (row, col) with 0-based indexing.
+    pub fn cursor_position(&self) -> (u16, u16) {
+        self.state.cursor_pos
+    }
+
+    /// Returns the rows written to since the dirty set was last cleared.
+    ///
+    /// A row is marked dirty when a character is printed to it, even if the
+    /// cell already held that character. Every row is marked when the screen
+    /// scrolls (LF, VT or FF at the bottom row, IND, NEL or RI), is
+    /// [resized](Self::resize) or is [restored](Self::restore). Nothing else
+    /// marks rows: cursor movement, attribute changes and mode switches
+    /// don't, and erase sequences such as `ED` and `EL` are not interpreted
+    /// by this emulator, so they leave both the cells and the set unchanged.
+    ///
+    /// Nothing in the crate reads this set; it is for callers polling for
+    /// changes, who can inspect just these rows instead of rescanning the
+    /// whole screen, then call [`clear_dirty_rows()`](Self::clear_dirty_rows).
+    ///
+    /// # Returns
+    ///
+    /// The dirty row indices (0-based) in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[3;1HStatus: ok\x1b[10;1HDone");
+    /// assert_eq!(screen.dirty_rows(), vec![2, 9]);
+    ///
+    /// screen.clear_dirty_rows();
+    /// screen.feed(b"\x1b[3;9Hfail");
+    /// assert_eq!(screen.dirty_rows(), vec![2]);
+    /// ```
+    pub fn dirty_rows(&self) -> Vec<u16> {
+        self.state.dirty_rows.iter().copied().collect()
+    }
+
+    /// Returns whether any row has been written to since the dirty set was
+    /// last cleared.
+    pub fn has_dirty_rows(&self) -> bool {
+        !self.state.dirty_rows.is_empty()
+    }
+
+    /// Clears the dirty set, so later writes can be detected.
+    pub fn clear_dirty_rows(&mut self) {
+        self.state.dirty_rows.clear();
+    }
+
+    /// Returns the working directory most recently reported via OSC 7.
+    ///
+    /// Shells with integration enabled emit `ESC ] 7 ; file://host/path BEL`
+    /// whenever the directory changes. The host is dropped and the path is
+    /// percent-decoded.
+    ///
+    /// # Returns
+    ///
+    /// The directory path, or `None` if no OSC 7 sequence has been seen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b]7;file://myhost/home/user/my%20project\x07");
+    ///
+    /// assert_eq!(screen.current_directory(), Some("/home/user/my project"));
+    /// ```
+    pub fn current_directory(&self) -> Option<&str> {
+        self.state.current_directory.as_deref()
+    }
+
+    /// Returns the text of the most recent OSC 52 clipboard write.
+    ///
+    /// Applications copy with `ESC ] 52 ; <targets> ; <base64> BEL`. The
+    /// payload is decoded and recorded regardless of which selection (`c`,
+    /// `p`, `s`, ...) it targets, so nothing reaches the real clipboard.
+    /// Clipboard queries (`?`) and malformed payloads are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The decoded text, or `None` if no clipboard write has been seen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b]52;c;aGVsbG8=\x07");
+    ///
+    /// assert_eq!(screen.clipboard(), Some("hello".to_string()));
+    /// ```
+    pub fn clipboard(&self) -> Option<String> {
+        self.state.clipboard.clone()
+    }
+
+    /// Returns the cursor color most recently set via OSC 12.
+    ///
+    /// Themes set it with `ESC ] 12 ; <color> BEL`, where the color is
+    /// `#rgb`, `#rrggbb`, or `rgb:rr/gg/bb`, and restore the default with
+    /// `ESC ] 112 BEL`. Color queries (`?`) are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The color as RGBA (`0xRRGGBBAA`, alpha `0xFF`), or `None` if the
+    /// cursor color has not been set or was reset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b]12;#f5e0dc\x07");
+    /// assert_eq!(screen.cursor_color(), Some(0xF5E0DCFF));
+    ///
+    /// screen.feed(b"\x1b]112\x07");
+    /// assert_eq!(screen.cursor_color(), None);
+    /// ```
+    pub fn cursor_color(&self) -> Option<u32> {
+        self.state.cursor_color
+    }
+
+    /// Enables or disables recording of OSC sequences into the
+    /// [`osc_log`](Self::osc_log).
+    ///
+    /// Off by default. Turning recording off keeps what was already logged;
+    /// use [`clear_osc_log`](Self::clear_osc_log) to discard it.
+    pub fn set_record_osc(&mut self, enabled: bool) {
+        self.state.record_osc = enabled;
+    }
+
+    /// Returns every OSC sequence seen while recording was enabled.
+    ///
+    /// Each entry is the numeric command and the rest of the payload after
+    /// the first `;`, byte for byte, whether or not the sequence is one this
+    /// crate understands. This lets tests assert on private or app-specific
+    /// OSC numbers. Sequences without a numeric command are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.set_record_osc(true);
+    /// screen.feed(b"\x1b]7777;sync;id=42\x07");
+    ///
+    /// assert_eq!(screen.osc_log(), &[(7777, b"sync;id=42".to_vec())]);
+    /// ```
+    pub fn osc_log(&self) -> &[(u32, Vec<u8>)] {
+        &self.state.osc_log
+    }
+
+    /// Discards all recorded OSC sequences.
+    pub fn clear_osc_log(&mut self) {
+        self.state.osc_log.clear();
+    }
+
+    /// Enables or disables recording of non-Sixel DCS strings into the
+    /// [`dcs_log`](Self::dcs_log).
+    ///
+    /// Off by default. Sixel images are tracked in
+    /// [`sixel_regions`](Self::sixel_regions) either way and never logged.
+    pub fn set_record_dcs(&mut self, enabled: bool) {
+        self.state.record_dcs = enabled;
+    }
+
+    /// Returns every non-Sixel DCS string seen while recording was enabled.
+    ///
+    /// Each entry is the final byte of the introducer (the mode, e.g. `q`
+    /// for DECRQSS `ESC P $ q`), its numeric parameters, and the payload up
+    /// to the string terminator. Intermediate bytes such as `$` are not
+    /// kept. Useful for testing query/response flows like DECRQSS and
+    /// XTGETTCAP that this crate does not interpret.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.set_record_dcs(true);
+    ///
+    /// // DECRQSS reply reporting the SGR state
+    /// screen.feed(b"\x1bP1$r0;1m\x1b\\");
+    ///
+    /// assert_eq!(screen.dcs_log(), &[(b'r', vec![1], b"0;1m".to_vec())]);
+    /// assert!(screen.sixel_regions().is_empty());
+    /// ```
+    pub fn dcs_log(&self) -> &[(u8, Vec<i64>, Vec<u8>)] {
+        &self.state.dcs_log
+    }
+
+    /// Discards all recorded DCS strings.
+    pub fn clear_dcs_log(&mut self) {
+        self.state.dcs_log.clear();
+    }
+
+    /// Returns whether the application has enabled bracketed paste mode.
+    ///
+    /// Applications opt in with `ESC [ ? 2004 h` and opt out with
+    /// `ESC [ ? 2004 l`. While enabled, pasted text should be wrapped in
+    /// `ESC [ 200 ~` / `ESC [ 201 ~` markers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(!screen.bracketed_paste_enabled());
+    ///
+    /// screen.feed(b"\x1b[?2004h");
+    /// assert!(screen.bracketed_paste_enabled());
+    ///
+    /// screen.feed(b"\x1b[?2004l");
+    /// assert!(!screen.bracketed_paste_enabled());
+    /// ```
+    pub fn bracketed_paste_enabled(&self) -> bool {
+        self.private_mode(DecPrivateModes::BRACKETED_PASTE)
+    }
+
+    /// Returns whether a DEC private mode is currently set.
+    ///
+    /// Tracks every mode toggled with `ESC [ ? Pm h` / `ESC [ ? Pm l`, such as
+    /// autowrap (7), cursor visibility (25), mouse reporting (1000, 1006),
+    /// the alternate screen (1049) and bracketed paste (2004). Modes that
+    /// were never toggled report their default: 7 and 25 are on, all others
+    /// are off. Tracking a mode does not mean the screen emulates it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(screen.private_mode(25));
+    /// assert!(!screen.private_mode(1049));
+    ///
+    /// screen.feed(b"\x1b[?1049h\x1b[?25l");
+    /// assert!(screen.private_mode(1049));
+    /// assert!(!screen.private_mode(25));
+    /// ```
+    pub fn private_mode(&self, mode: u16) -> bool {
+        self.state.private_modes.get(mode)
+    }
+
+    /// Returns the mouse reporting protocol the application has enabled.
+    ///
+    /// Tracking (`?1000`, `?1002` or `?1003`) combined with `?1006` reports
+    /// [`MouseMode::Sgr`], tracking alone reports [`MouseMode::Normal`], and
+    /// `?9` alone reports [`MouseMode::X10`]. `?1006` without a tracking mode
+    /// does not enable reporting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{events::MouseMode, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[?1000h");
+    /// assert_eq!(screen.mouse_mode(), MouseMode::Normal);
+    ///
+    /// screen.feed(b"\x1b[?1006h");
+    /// assert_eq!(screen.mouse_mode(), MouseMode::Sgr);
+    /// ```
+    pub fn mouse_mode(&self) -> MouseMode {
+        let modes = &self.state.private_modes;
+        if DecPrivateModes::MOUSE_TRACKING
+            .iter()
+            .any(|&mode| modes.get(mode))
+        {
+            if modes.get(DecPrivateModes::MOUSE_SGR) {
+                MouseMode::Sgr
+            } else {
+                MouseMode::Normal
+            }
+        } else if modes.get(DecPrivateModes::MOUSE_X10) {
+            MouseMode::X10
+        } else {
+            MouseMode::Off
+        }
+    }
+
+    /// Returns the Kitty keyboard protocol flags the application has enabled.
+    ///
+    /// Applications push flags with `ESC [ > flags u` and pop them with
+    /// `ESC [ < u`; `ESC [ = flags ; mode u` changes the current entry. The
+    /// value is the top of that stack, or 0 when the protocol is not in use.
+    /// Bit 1 (`0b1`) asks for disambiguated escape codes and bit 8 (`0b1000`)
+    /// for every key to be reported as an escape code; see
+    /// [`encode_key_event_kitty`](crate::events::encode_key_event_kitty).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.kitty_keyboard_flags(), 0);
+    ///
+    /// screen.feed(b"\x1b[>1u");
+    /// assert_eq!(screen.kitty_keyboard_flags(), 1);
+    ///
+    /// screen.feed(b"\x1b[<u");
+    /// assert_eq!(screen.kitty_keyboard_flags(), 0);
+    /// ```
+    pub fn kitty_keyboard_flags(&self) -> u8 {
+        self.state.kitty_keyboard_stack.last().copied().unwrap_or(0)
+    }
+
+    /// Returns the screen dimensions.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (width, height) in columns and rows.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the screen width in columns.
+    ///
+    /// This is a convenience method equivalent to `self.size().0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.cols(), 80);
+    /// ```
+    pub fn cols(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the screen height in rows.
+    ///
+    /// This is a convenience method equivalent to `self.size().1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.rows(), 24);
+    /// ```
+    pub fn rows(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns an iterator over all rows in the screen.
+    ///
+    /// Each item in the iterator is a reference to a row (a slice of cells).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Line 1\r\nLine 2");
+    ///
+    /// for (row_idx, row) in screen.iter_rows().enumerate() {
+    ///     println!("Row {}: {} cells", row_idx, row.len());
+    ///     for (col_idx, cell) in row.iter().enumerate() {
+    ///         if cell.c != ' ' {
+    ///             println!("  Cell ({}, {}): '{}'", row_idx, col_idx, cell.c);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.state.cells.iter().map(|row| row.as_slice())
+    }
+
+    /// Returns an iterator over all cells in a specific row.
+    ///
+    /// Returns `None` if the row index is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mRed\x1b[32mGreen\x1b[34mBlue");
+    ///
+    /// // Use the iterator immediately to avoid lifetime issues
+    /// let colored_cells: Vec<_> = screen
+    ///     .iter_row(0)
+    ///     .unwrap()
+    ///     .enumerate()
+    ///     .filter(|(_, cell)| cell.fg.is_some())
+    ///     .collect();
+    ///
+    /// assert!(colored_cells.len() >= 3, "Should have colored cells");
+    /// ```
+    pub fn iter_row(&self, row: u16) -> Option<impl Iterator<Item = &Cell>> {
+        if row < self.height {
+            Some(self.state.cells[row as usize].iter())
+        } else {
+            None
+        }
+    }
+
+    /// Returns a structured snapshot of the entire screen grid.
+    ///
+    /// This method provides a complete copy of the screen state suitable for
+    /// deep comparison with other terminal emulators or for serialization.
+    ///
+    /// # Returns
+    ///
+    /// A `GridSnapshot` containing:
+    /// - Grid dimensions (width, height)
+    /// - Complete cell data (2D vector of cells)
+    /// - Current cursor position
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mHello");
+    ///
+    /// let snapshot = screen.snapshot();
+    /// assert_eq!(snapshot.width, 80);
+    /// assert_eq!(snapshot.height, 24);
+    /// assert_eq!(snapshot.cells[0][0].c, 'H');
+    /// assert_eq!(snapshot.cells[0][0].fg, Some(1)); // Red
+    /// assert_eq!(snapshot.cursor, (0, 5));
+    /// ```
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            width: self.width,
+            height: self.height,
+            cells: self.state.cells.clone(),
+            cursor: self.state.cursor_pos,
+        }
+    }
+
+    /// Saves the full emulator state so it can be restored later.
+    ///
+    /// Use this to render up to a common point once and then explore several
+    /// branches from there with [`restore`](Self::restore), without replaying
+    /// the setup.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1mBold");
+    ///
+    /// let checkpoint = screen.checkpoint();
+    /// assert_eq!(checkpoint.snapshot(), screen.snapshot());
+    /// ```
+    pub fn checkpoint(&self) -> ScreenCheckpoint {
+        ScreenCheckpoint {
+            state: self.state.clone(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Rolls the emulator back to a previously saved checkpoint.
+    ///
+    /// Cells, cursor, text attributes, modes and graphics regions are all
+    /// replaced, including the screen size if it changed since. Any escape
+    /// sequence that was only partially fed is discarded, and every row is
+    /// marked dirty.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - State captured by [`checkpoint`](Self::checkpoint)
+    pub fn restore(&mut self, checkpoint: &ScreenCheckpoint) {
+        self.parser = VTParser::new();
+        self.state = checkpoint.state.clone();
+        self.width = checkpoint.width;
+        self.height = checkpoint.height;
+        self.state.mark_all_dirty();
+    }
+
+    /// Returns all Sixel graphics regions currently on screen.
+    ///
+    /// This method provides access to all Sixel graphics that have been rendered
+    /// via DCS (Device Control String) sequences. Each region includes position
+    /// and dimension information.
+    ///
+    /// This is essential for verifying Sixel positioning in tests, particularly
+    /// for ensuring that graphics appear within designated preview areas.
+    ///
+    /// # Returns
+    ///
+    /// A slice of [`SixelRegion`] containing all detected Sixel graphics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// // ... render some Sixel graphics ...
+    ///
+    /// let regions = screen.sixel_regions();
+    /// for (i, region) in regions.iter().enumerate() {
+    ///     println!(
+    ///         "Region {}: position ({}, {}), size {}x{}",
+    ///         i, region.start_row, region.start_col, region.width, region.height
+    ///     );
+    /// }
+    /// ```
+    pub fn sixel_regions(&self) -> &[SixelRegion] {
+        &self.state.sixel_regions
+    }
+
+    /// Returns a mutable reference to the Sixel regions.
+    ///
+    /// This is primarily intended for testing purposes, allowing tests to
+    /// inject mock Sixel data for memory profiling and other tests.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the vector of Sixel regions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{screen::SixelRegion, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    ///
+    /// // Add a mock Sixel region for testing
+    /// screen.sixel_regions_mut().push(SixelRegion {
+    ///     start_row: 5,
+    ///     start_col: 10,
+    ///     width: 100,
+    ///     height: 50,
+    ///     data: vec![0u8; 1000],
+    /// });
+    ///
+    /// assert_eq!(screen.sixel_regions().len(), 1);
+    /// ```
+    pub fn sixel_regions_mut(&mut self) -> &mut Vec<SixelRegion> {
+        &mut self.state.sixel_regions
+    }
+
+    /// Checks if a Sixel region exists at the given position.
+    ///
+    /// This method checks if any Sixel region has its starting position
+    /// at the exact (row, col) coordinates provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row to check (0-indexed)
+    /// * `col` - Column to check (0-indexed)
+    ///
+    /// # Returns
+    ///
+    /// `true` if a Sixel region starts at the given position, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    ///
+    /// // Render a Sixel at position (5, 10) - ESC[5;10H moves to row 5, col 10 (1-based)
+    /// screen.feed(b"\x1b[5;10H"); // Move cursor
+    /// screen.feed(b"\x1bPq"); // Start Sixel
+    /// screen.feed(b"\"1;1;100;50#0~"); // Raster + data
+    /// screen.feed(b"\x1b\\"); // End Sixel
+    ///
+    /// // Check for Sixel at 0-based coordinates (4, 9)
+    /// assert!(screen.has_sixel_at(4, 9));
+    /// assert!(!screen.has_sixel_at(0, 0));
+    /// ```
+    pub fn has_sixel_at(&self, row: u16, col: u16) -> bool {
+        self.state
+            .sixel_regions
+            .iter()
+            .any(|region| region.start_row == row && region.start_col == col)
+    }
+
+    /// Checks whether every Sixel image lies entirely within the given bounds.
+    ///
+    /// Each image's footprint is taken from [`cell_footprint`](Self::cell_footprint).
+    /// Returns `true` when there are no Sixel images.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{regions::RegionBounds, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[5;10H\x1bPq\"1;1;200;50#0~\x1b\\");
+    ///
+    /// assert!(screen.sixels_within(&RegionBounds::new(0, 0, 80, 24)));
+    /// assert!(!screen.sixels_within(&RegionBounds::new(0, 0, 20, 24)));
+    /// ```
+    #[cfg(feature = "ipc")]
+    pub fn sixels_within(&self, bounds: &crate::regions::RegionBounds) -> bool {
+        self.state
+            .sixel_regions
+            .iter()
+            .all(|region| bounds.contains_bounds(&self.cell_footprint(region).into()))
+    }
+
+    /// Sets the size of a character cell in pixels.
+    ///
+    /// Sixel images are sized in pixels, so converting them to cells depends
+    /// on the font metrics of the terminal being emulated. Match this to the
+    /// cell size the application under test assumes. Defaults to 10x20.
+    /// Zero dimensions are treated as one pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Cell width in pixels
+    /// * `height` - Cell height in pixels
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.cell_pixels(), (10, 20));
+    ///
+    /// screen.set_cell_pixels(8, 16);
+    /// assert_eq!(screen.cell_pixels(), (8, 16));
+    /// ```
+    pub fn set_cell_pixels(&mut self, width: u32, height: u32) {
+        self.cell_pixels = (width, height);
+    }
+
+    /// Returns the configured character cell size in pixels, as (width, height).
+    pub fn cell_pixels(&self) -> (u32, u32) {
+        self.cell_pixels
+    }
+
+    /// Returns the cells covered by a Sixel image at the configured cell size.
+    ///
+    /// A partially covered cell counts as covered.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The Sixel image, usually from [`sixel_regions`](Self::sixel_regions)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{Rect, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[5;10H\x1bPq\"1;1;100;50#0~\x1b\\");
+    /// let image = screen.sixel_regions()[0].clone();
+    ///
+    /// assert_eq!(screen.cell_footprint(&image), Rect::new(9, 4, 10, 3));
+    /// screen.set_cell_pixels(8, 16);
+    /// assert_eq!(screen.cell_footprint(&image), Rect::new(9, 4, 13, 4));
+    /// ```
+    pub fn cell_footprint(&self, region: &SixelRegion) -> Rect {
+        region.cell_footprint(self.cell_pixels.0, self.cell_pixels.1)
+    }
+
+    /// Returns all Kitty graphics regions currently on screen.
+    ///
+    /// This method provides access to all Kitty graphics that have been rendered
+    /// via APC (Application Program Command) sequences. Each region includes position
+    /// and dimension information.
+    ///
+    /// # Returns
+    ///
+    /// A slice of [`KittyRegion`] containing all detected Kitty graphics.
+    ///
+    /// # Example
     ///
     /// ```rust
     /// use terminal_testlib::ScreenState;
@@ -1415,6 +3081,28 @@ impl ScreenState {
         &mut self.state.iterm2_regions
     }
 
+    /// Returns whether any inline graphics have been rendered.
+    ///
+    /// Covers Sixel, Kitty and iTerm2 images alike, for tests that check a
+    /// text-only mode really drew no images.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(!screen.has_any_graphics());
+    ///
+    /// screen.feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+    /// assert!(screen.has_any_graphics());
+    /// ```
+    pub fn has_any_graphics(&self) -> bool {
+        !self.state.sixel_regions.is_empty()
+            || !self.state.kitty_regions.is_empty()
+            || !self.state.iterm2_regions.is_empty()
+    }
+
     /// Returns the screen contents for debugging purposes.
     ///
     /// This is currently an alias for [`contents()`](Self::contents), but may
@@ -1427,6 +3115,54 @@ impl ScreenState {
         self.contents()
     }
 
+    /// Returns the screen contents with row numbers and a column ruler.
+    ///
+    /// Two header lines give the tens and units digit of each 0-based
+    /// column, and every row is prefixed with its 0-based row number, so
+    /// positions can be read off without counting. Trailing spaces are
+    /// trimmed from each line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(12, 2);
+    /// screen.feed(b"Hello\r\n  World");
+    ///
+    /// assert_eq!(
+    ///     screen.contents_with_ruler(),
+    ///     "  |          1\n  |012345678901\n0 |Hello\n1 |  World"
+    /// );
+    /// ```
+    pub fn contents_with_ruler(&self) -> String {
+        let gutter = self.height.saturating_sub(1).to_string().len();
+        let header = format!("{:>gutter$} |", "");
+
+        let tens: String = (0..self.width)
+            .map(|col| {
+                if col % 10 == 0 && col > 0 {
+                    char::from(b'0' + (col / 10 % 10) as u8)
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        let units: String = (0..self.width)
+            .map(|col| char::from(b'0' + (col % 10) as u8))
+            .collect();
+
+        let mut lines = vec![
+            format!("{}{}", header, tens).trim_end().to_string(),
+            format!("{}{}", header, units),
+        ];
+        for row in 0..self.height {
+            let text = self.row_contents(row);
+            lines.push(format!("{:>gutter$} |{}", row, text.trim_end()));
+        }
+        lines.join("\n")
+    }
+
     /// Checks if the screen contains the specified text.
     ///
     /// This is a convenience method that searches the entire screen contents
@@ -1456,6 +3192,211 @@ impl ScreenState {
     pub fn contains(&self, text: &str) -> bool {
         self.contents().contains(text)
     }
+
+    /// Finds every occurrence of text on the screen.
+    ///
+    /// Rows are scanned top to bottom and left to right, and matches never
+    /// overlap or span a row boundary. An empty `text` matches nothing.
+    ///
+    /// # Returns
+    ///
+    /// The (row, col) of the first cell of each match, in scan order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"ERROR: one\r\n  ERROR: two");
+    ///
+    /// assert_eq!(screen.find_all("ERROR"), vec![(0, 0), (1, 2)]);
+    /// assert!(screen.find_all("WARN").is_empty());
+    /// ```
+    pub fn find_all(&self, text: &str) -> Vec<(u16, u16)> {
+        let needle: Vec<char> = text.chars().collect();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches = Vec::new();
+        for (row, cells) in self.state.cells.iter().enumerate() {
+            let mut col = 0;
+            while col + needle.len() <= cells.len() {
+                let is_match = cells[col..col + needle.len()]
+                    .iter()
+                    .zip(&needle)
+                    .all(|(cell, &ch)| cell.c == ch);
+                if is_match {
+                    matches.push((row as u16, col as u16));
+                    col += needle.len();
+                } else {
+                    col += 1;
+                }
+            }
+        }
+        matches
+    }
+
+    /// Renders the screen as an SVG image, assuming 8x16 pixel cells.
+    ///
+    /// See [`to_svg_with_cell_size()`](Self::to_svg_with_cell_size).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 2);
+    /// screen.feed(b"\x1b[31mError\x1b[0m: disk full");
+    ///
+    /// let svg = screen.to_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">Error</text>"));
+    /// assert!(svg.contains(r##"fill="#CD0000""##));
+    /// ```
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_cell_size(8, 16)
+    }
+
+    /// Renders the screen as an SVG image with the given cell size in pixels.
+    ///
+    /// Each run of cells sharing the same attributes becomes one monospace
+    /// `<text>` element, stretched to exactly cover its cells. Non-default
+    /// backgrounds are drawn as rectangles behind the text, and the cursor
+    /// as a translucent rectangle on top. ANSI colors use the xterm palette,
+    /// with light gray text on black by default.
+    pub fn to_svg_with_cell_size(&self, cell_width: u32, cell_height: u32) -> String {
+        let width = self.width as u32 * cell_width;
+        let height = self.height as u32 * cell_height;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"monospace\" font-size=\"{}\">\n",
+            cell_height * 3 / 4,
+            w = width,
+            h = height,
+        );
+        svg.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            SVG_DEFAULT_BG
+        ));
+
+        for (row, cells) in self.state.cells.iter().enumerate() {
+            let y = row as u32 * cell_height;
+            let baseline = y + cell_height - cell_height / 5;
+
+            let mut start = 0;
+            while start < cells.len() {
+                let style =
+                    |cell: &Cell| (cell.fg, cell.bg, cell.bold, cell.italic, cell.underline);
+                let first = &cells[start];
+                let len = cells[start..]
+                    .iter()
+                    .take_while(|cell| style(cell) == style(first))
+                    .count();
+                let x = start as u32 * cell_width;
+                let run_width = len as u32 * cell_width;
+
+                if let Some(bg) = first.bg {
+                    svg.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                        x,
+                        y,
+                        run_width,
+                        cell_height,
+                        ansi_hex(bg)
+                    ));
+                }
+
+                let text: String = cells[start..start + len]
+                    .iter()
+                    .map(|cell| cell.c)
+                    .collect();
+                if !text.trim().is_empty() {
+                    let fill = first.fg.map_or(SVG_DEFAULT_FG.to_string(), ansi_hex);
+                    let mut attrs = String::new();
+                    if first.bold {
+                        attrs.push_str(" font-weight=\"bold\"");
+                    }
+                    if first.italic {
+                        attrs.push_str(" font-style=\"italic\"");
+                    }
+                    if first.underline {
+                        attrs.push_str(" text-decoration=\"underline\"");
+                    }
+                    svg.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" fill=\"{}\"{} textLength=\"{}\" \
+                         lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\">{}</text>\n",
+                        x,
+                        baseline,
+                        fill,
+                        attrs,
+                        run_width,
+                        xml_escape(&text)
+                    ));
+                }
+
+                start += len;
+            }
+        }
+
+        let (cursor_row, cursor_col) = self.cursor_position();
+        svg.push_str(&format!(
+            "<rect class=\"cursor\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+             fill=\"{}\" fill-opacity=\"0.5\"/>\n",
+            cursor_col as u32 * cell_width,
+            cursor_row as u32 * cell_height,
+            cell_width,
+            cell_height,
+            SVG_DEFAULT_FG
+        ));
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Default foreground for SVG output (xterm color 7).
+const SVG_DEFAULT_FG: &str = "#E5E5E5";
+/// Default background for SVG output.
+const SVG_DEFAULT_BG: &str = "#000000";
+
+/// Converts an ANSI 256-color index to an `#RRGGBB` string (xterm palette).
+fn ansi_hex(index: u8) -> String {
+    const BASE: [u32; 16] = [
+        0x000000, 0xCD0000, 0x00CD00, 0xCDCD00, 0x0000EE, 0xCD00CD, 0x00CDCD, 0xE5E5E5, 0x7F7F7F,
+        0xFF0000, 0x00FF00, 0xFFFF00, 0x5C5CFF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+    ];
+    let rgb = match index {
+        0..=15 => BASE[index as usize],
+        16..=231 => {
+            // 6x6x6 color cube
+            let level = |v: u8| if v == 0 { 0 } else { 55 + 40 * v as u32 };
+            let i = index - 16;
+            (level(i / 36) << 16) | (level(i / 6 % 6) << 8) | level(i % 6)
+        }
+        232..=255 => {
+            // Grayscale ramp
+            let gray = 8 + 10 * (index - 232) as u32;
+            (gray << 16) | (gray << 8) | gray
+        }
+    };
+    format!("#{:06X}", rgb)
+}
+
+/// Escapes text for use in XML content.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
 }
 
 #[cfg(test)]
@@ -1551,40 +3492,228 @@ mod tests {
         // Zero dimensions (invalid)
         assert_eq!(state.parse_raster_attributes(b"\"1;1;0;50"), None, "Should reject zero width");
         assert_eq!(
-            state.parse_raster_attributes(b"\"1;1;100;0"),
-            None,
-            "Should reject zero height"
+            state.parse_raster_attributes(b"\"1;1;100;0"),
+            None,
+            "Should reject zero height"
+        );
+        assert_eq!(
+            state.parse_raster_attributes(b"\"0;0"),
+            None,
+            "Should reject zero dimensions in abbreviated format"
+        );
+
+        // Non-numeric values
+        assert_eq!(state.parse_raster_attributes(b"\"abc;def"), None);
+
+        // Mixed numeric/non-numeric: parser stops at first non-numeric, non-semicolon
+        // "1;1;abc;def" becomes "1;1" which is valid 2-param format
+        // This is intentional - we parse up to the first non-numeric character
+        assert_eq!(state.parse_raster_attributes(b"\"1;1;abc;def"), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_parse_raster_edge_cases() {
+        let state = TerminalState::new(80, 24);
+
+        // Large dimensions
+        let data = b"\"1;1;4096;2048#0~";
+        assert_eq!(state.parse_raster_attributes(data), Some((4096, 2048)));
+
+        // Minimum valid dimensions
+        let data = b"\"1;1;1;1#0~";
+        assert_eq!(state.parse_raster_attributes(data), Some((1, 1)));
+
+        // Extra whitespace/characters after parameters
+        let data = b"\"1;1;100;50  \t#0~";
+        assert_eq!(state.parse_raster_attributes(data), Some((100, 50)));
+    }
+
+    #[test]
+    fn test_cells_reconstruct_contents() {
+        let mut screen = ScreenState::new(12, 3);
+        screen.feed(b"\x1b[31mred\x1b[0m plain\r\n\x1b[4mline two");
+
+        let cells = screen.cells();
+        assert_eq!(cells.len(), screen.rows() as usize);
+        assert!(cells.iter().all(|row| row.len() == screen.cols() as usize));
+
+        let rebuilt = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.c).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(rebuilt, screen.contents());
+
+        for (row, line) in cells.iter().enumerate() {
+            for (col, cell) in line.iter().enumerate() {
+                assert_eq!(Some(cell), screen.get_cell(row as u16, col as u16));
+            }
+        }
+        assert_eq!(cells[0][0].fg, Some(1));
+        assert_eq!(cells[0][4].fg, None);
+        assert!(cells[1][0].underline);
+    }
+
+    #[test]
+    fn test_sgr_curly_red_underline() {
+        let mut screen = ScreenState::new(30, 3);
+        let red = UnderlineColor::Rgb(255, 0, 0);
+
+        // Semicolon form for the color, colon form for the style
+        screen.feed(b"\x1b[4:3;58;2;255;0;0mA\x1b[0mB");
+        let cell = screen.get_cell(0, 0).unwrap();
+        assert_eq!(cell.underline_style, UnderlineStyle::Curly);
+        assert_eq!(cell.underline_color, Some(red));
+        assert!(cell.underline);
+        // The 3 in 4:3 is a sub-parameter, not SGR 3 (italic)
+        assert!(!cell.italic);
+        assert_eq!(*screen.get_cell(0, 1).unwrap(), Cell { c: 'B', ..Cell::default() });
+
+        // Colon form for both, with the empty color-space slot
+        screen.feed(b"\r\n\x1b[4:3m\x1b[58:2::255:0:0mC\x1b[59mD\x1b[24mE");
+        assert!(screen
+            .assert_cell_underline_style(1, 0, UnderlineStyle::Curly)
+            .is_ok());
+        assert!(screen.assert_cell_underline_color(1, 0, Some(red)).is_ok());
+        assert!(screen.assert_cell_underline_color(1, 1, None).is_ok());
+        assert!(screen
+            .assert_cell_underline_style(1, 1, UnderlineStyle::Curly)
+            .is_ok());
+        assert!(screen
+            .assert_cell_underline_style(1, 2, UnderlineStyle::None)
+            .is_ok());
+
+        // Remaining styles, and palette colors in both forms
+        screen.feed(b"\r\n\x1b[4m1\x1b[21m2\x1b[4:4m3\x1b[4:5;58;5;9m4\x1b[58:5:12;4:0m5");
+        let styles = [
+            UnderlineStyle::Single,
+            UnderlineStyle::Double,
+            UnderlineStyle::Dotted,
+            UnderlineStyle::Dashed,
+            UnderlineStyle::None,
+        ];
+        for (col, style) in styles.into_iter().enumerate() {
+            assert!(screen
+                .assert_cell_underline_style(2, col as u16, style)
+                .is_ok());
+        }
+        assert_eq!(
+            screen.get_cell(2, 3).unwrap().underline_color,
+            Some(UnderlineColor::Indexed(9))
         );
         assert_eq!(
-            state.parse_raster_attributes(b"\"0;0"),
-            None,
-            "Should reject zero dimensions in abbreviated format"
+            screen.get_cell(2, 4).unwrap().underline_color,
+            Some(UnderlineColor::Indexed(12))
         );
 
-        // Non-numeric values
-        assert_eq!(state.parse_raster_attributes(b"\"abc;def"), None);
+        let err = screen
+            .assert_cell_underline_style(0, 0, UnderlineStyle::Single)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Underline style mismatch at (0, 0) on 'A'"), "{}", err);
+        assert!(err.contains("Found:    Curly"), "{}", err);
+        assert!(screen.assert_cell_underline_color(5, 0, None).is_err());
+    }
 
-        // Mixed numeric/non-numeric: parser stops at first non-numeric, non-semicolon
-        // "1;1;abc;def" becomes "1;1" which is valid 2-param format
-        // This is intentional - we parse up to the first non-numeric character
-        assert_eq!(state.parse_raster_attributes(b"\"1;1;abc;def"), Some((1, 1)));
+    #[test]
+    fn test_sgr_truecolor_values_are_not_attributes() {
+        let mut screen = ScreenState::new(10, 1);
+        // 1 and 3 here are color components, not bold and italic
+        screen.feed(b"\x1b[38;2;1;3;4;48;5;2mX");
+        let cell = screen.get_cell(0, 0).unwrap();
+        assert!(!cell.bold && !cell.italic && !cell.underline);
+        assert_eq!(cell.fg, None);
+        assert_eq!(cell.bg, Some(2));
     }
 
+    #[cfg(feature = "ipc")]
     #[test]
-    fn test_parse_raster_edge_cases() {
-        let state = TerminalState::new(80, 24);
+    fn test_assert_block_eq_interior() {
+        use crate::regions::RegionBounds;
+
+        let mut screen = ScreenState::new(30, 6);
+        screen.feed(b"noise noise noise noise noise\r\n");
+        screen.feed(b"xx \x1b[2;4H[ Save  ]xx\r\n");
+        screen.feed(b"xx \x1b[3;4H[ Quit  ]xx");
+        let widget = RegionBounds::new(1, 3, 10, 3);
+
+        assert_eq!(screen.block_text(widget), vec!["[ Save  ]x", "[ Quit  ]x", "          "]);
+        assert!(screen
+            .assert_block_eq(widget, &["[ Save  ]x", "[ Quit  ]x"])
+            .is_ok());
+
+        let message = screen
+            .assert_block_eq(widget, &["[ Save  ]x", "[ Exit  ]x"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("Block mismatch in 2 cell(s) of 10x3 block at (1, 3)"),
+            "{}",
+            message
+        );
+        assert!(message.contains("(2, 5): expected 'E', found 'Q'"), "{}", message);
+        assert!(message.contains("Found:\n  |[ Save  ]x|\n  |[ Quit  ]x|"), "{}", message);
 
-        // Large dimensions
-        let data = b"\"1;1;4096;2048#0~";
-        assert_eq!(state.parse_raster_attributes(data), Some((4096, 2048)));
+        // Expected text must fit the bounds
+        assert!(screen.assert_block_eq(widget, &["[ Save  ]xx"]).is_err());
+        assert!(screen.assert_block_eq(widget, &["", "", "", "x"]).is_err());
+    }
 
-        // Minimum valid dimensions
-        let data = b"\"1;1;1;1#0~";
-        assert_eq!(state.parse_raster_attributes(data), Some((1, 1)));
+    #[cfg(feature = "ipc")]
+    #[test]
+    fn test_assert_block_eq_clipped_at_edge() {
+        use crate::regions::RegionBounds;
+
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"\x1b[2;8Habc\x1b[3;8Hdef");
+        // Extends two columns and one row past the bottom-right corner
+        let corner = RegionBounds::new(1, 7, 5, 3);
+
+        assert_eq!(screen.block_text(corner), vec!["abc", "def"]);
+        assert!(screen.assert_block_eq(corner, &["abc", "def"]).is_ok());
+        assert!(screen
+            .assert_block_eq(corner, &["abc  ", "def", "     "])
+            .is_ok());
+
+        let message = screen
+            .assert_block_eq(corner, &["abcd", "def"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("(1, 10): expected 'd', but the cell is off screen"),
+            "{}",
+            message
+        );
 
-        // Extra whitespace/characters after parameters
-        let data = b"\"1;1;100;50  \t#0~";
-        assert_eq!(state.parse_raster_attributes(data), Some((100, 50)));
+        // Entirely off screen
+        assert!(screen.block_text(RegionBounds::new(5, 20, 3, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_cell_footprint_follows_cell_pixels() {
+        let mut screen = ScreenState::new(80, 24);
+        // 120x96 pixels at row 2, col 4
+        screen.feed(b"\x1b[3;5H\x1bPq\"1;1;120;96#0~\x1b\\");
+        let image = screen.sixel_regions()[0].clone();
+
+        assert_eq!(screen.cell_pixels(), (10, 20));
+        assert_eq!(screen.cell_footprint(&image), Rect::new(4, 2, 12, 5));
+
+        screen.set_cell_pixels(8, 16);
+        assert_eq!(screen.cell_footprint(&image), Rect::new(4, 2, 15, 6));
+
+        // Region checks use the configured size too
+        #[cfg(feature = "ipc")]
+        {
+            let bounds = crate::regions::RegionBounds::new(0, 0, 18, 8);
+            assert!(!screen.sixels_within(&bounds));
+            screen.set_cell_pixels(10, 20);
+            assert!(screen.sixels_within(&bounds));
+        }
+
+        // Degenerate sizes do not divide by zero
+        screen.set_cell_pixels(0, 0);
+        assert_eq!(screen.cell_footprint(&image), Rect::new(4, 2, 120, 96));
     }
 
     #[test]
@@ -1690,4 +3819,865 @@ mod tests {
         assert_eq!(region.width, 200);
         assert_eq!(region.height, 150);
     }
+
+    #[test]
+    fn test_crlf_split_across_feeds() {
+        let mut single = ScreenState::new(20, 5);
+        single.feed(b"Line 1\r\nLine 2");
+
+        let mut split = ScreenState::new(20, 5);
+        split.feed(b"Line 1\r");
+        split.feed(b"\nLine 2");
+
+        assert_eq!(split.cursor_position(), single.cursor_position());
+        assert_eq!(split.contents(), single.contents());
+        assert_eq!(split.row_contents(1).trim_end(), "Line 2");
+    }
+
+    #[test]
+    fn test_cr_and_lf_in_separate_feeds() {
+        let mut single = ScreenState::new(20, 5);
+        single.feed(b"abc\r\ndef");
+
+        let mut split = ScreenState::new(20, 5);
+        split.feed(b"abc");
+        split.feed(b"\r");
+        split.feed(b"\n");
+        split.feed(b"def");
+
+        assert_eq!(split.cursor_position(), (1, 3));
+        assert_eq!(split.cursor_position(), single.cursor_position());
+        assert_eq!(split.contents(), single.contents());
+    }
+
+    #[test]
+    fn test_csi_split_across_feeds() {
+        let mut single = ScreenState::new(80, 24);
+        single.feed(b"\x1b[5;10H\x1b[31mX");
+
+        let mut split = ScreenState::new(80, 24);
+        split.feed(b"\x1b[");
+        split.feed(b"5;1");
+        split.feed(b"0H\x1b");
+        split.feed(b"[31");
+        split.feed(b"mX");
+
+        assert_eq!(split.cursor_position(), single.cursor_position());
+        assert_eq!(split.contents(), single.contents());
+        assert_eq!(split.get_cell(4, 9), single.get_cell(4, 9));
+        assert_eq!(split.get_cell(4, 9).unwrap().fg, Some(1));
+    }
+
+    /// Feeds `data` in `chunk_size` pieces, as successive PTY reads would.
+    fn chunked_feed(screen: &mut ScreenState, data: &[u8], chunk_size: usize) {
+        for chunk in data.chunks(chunk_size) {
+            screen.feed(chunk);
+        }
+    }
+
+    #[test]
+    fn test_chunked_feed_matches_single_feed() {
+        let data = b"\x1b[2;3H\x1b[1;31mRed\x1b[0m\r\n\x1b]7;file:///tmp\x07\x1b[?2004h\tTab\x1b[K";
+
+        let mut single = ScreenState::new(20, 5);
+        single.feed(data);
+
+        for chunk_size in 1..=data.len() {
+            let mut chunked = ScreenState::new(20, 5);
+            chunked_feed(&mut chunked, data, chunk_size);
+
+            assert_eq!(chunked.contents(), single.contents(), "chunk size {}", chunk_size);
+            assert_eq!(chunked.cursor_position(), single.cursor_position());
+            assert_eq!(chunked.get_cell(1, 2), single.get_cell(1, 2));
+            assert_eq!(chunked.current_directory(), Some("/tmp"));
+            assert!(chunked.bracketed_paste_enabled());
+        }
+    }
+
+    #[test]
+    fn test_osc7_current_directory() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.current_directory(), None);
+
+        screen.feed(b"\x1b]7;file://host/tmp\x07");
+        assert_eq!(screen.current_directory(), Some("/tmp"));
+
+        // Later reports replace earlier ones; empty host is allowed
+        screen.feed(b"\x1b]7;file:///var/log\x07");
+        assert_eq!(screen.current_directory(), Some("/var/log"));
+
+        // Malformed URIs are ignored
+        screen.feed(b"\x1b]7;not-a-uri\x07");
+        assert_eq!(screen.current_directory(), Some("/var/log"));
+    }
+
+    #[test]
+    fn test_assert_row_eq() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"  === Menu ===");
+
+        assert!(screen.assert_row_eq(0, "  === Menu ===").is_ok());
+        // Trailing spaces on either side are ignored
+        assert!(screen.assert_row_eq(0, "  === Menu ===   ").is_ok());
+        assert!(screen.assert_row_eq(1, "").is_ok());
+
+        match screen.assert_row_eq(0, "  === Main ===") {
+            Err(TermTestError::Parse(message)) => assert_eq!(
+                message,
+                "Row 0 mismatch at column 7\n  \
+                 Expected: \"  === Main ===\"\n  \
+                 Found:    \"  === Menu ===\"\n  \
+                 \x20                 ^"
+            ),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+
+        // A missing suffix points just past the shorter string
+        let message = screen
+            .assert_row_eq(0, "  === Menu")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("mismatch at column 10"));
+
+        assert!(screen.assert_row_eq(3, "").is_err());
+    }
+
+    #[test]
+    fn test_assert_screen_eq() {
+        let mut screen = ScreenState::new(12, 5);
+        screen.feed(b"+--------+\r\n| Status |\r\n|  idle  |\r\n+--------+");
+
+        let expected = "+--------+
+| Status |
+|  idle  |
++--------+";
+        assert!(screen.assert_screen_eq(expected).is_ok());
+        // Trailing blank lines and spaces are ignored
+        assert!(screen
+            .assert_screen_eq(&format!("{}   \n\n", expected))
+            .is_ok());
+
+        let wrong = "+--------+
+| Status |
+|  busy  |
++-------+";
+        match screen.assert_screen_eq(wrong) {
+            Err(TermTestError::Parse(message)) => assert_eq!(
+                message,
+                "Screen mismatch in 2 row(s)\n\
+                 Row 2 mismatch at column 3\n  \
+                 Expected: \"|  busy  |\"\n  \
+                 Found:    \"|  idle  |\"\n  \
+                 \x20             ^\n\
+                 Row 3 mismatch at column 8\n  \
+                 Expected: \"+-------+\"\n  \
+                 Found:    \"+--------+\"\n  \
+                 \x20                  ^"
+            ),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+
+        // Text below the last row can never match
+        let message = screen
+            .assert_screen_eq(&format!("{}\n\n\nextra", expected))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Row 6 mismatch at column 0"));
+    }
+
+    #[test]
+    fn test_row_text_and_nonblank_rows() {
+        let mut screen = ScreenState::new(20, 5);
+        assert_eq!(screen.nonblank_rows(), Vec::<u16>::new());
+
+        screen.feed(b"a  b   \r\n\r\n   indented\r\n        ");
+
+        // Interior and leading spaces survive, trailing padding does not
+        assert_eq!(screen.row_text(0), "a  b");
+        assert_eq!(screen.row_text(2), "   indented");
+        assert_eq!(screen.row_text(1), "");
+        assert_eq!(screen.row_contents(0), format!("{:<20}", "a  b"));
+
+        // Rows holding only spaces count as blank
+        assert_eq!(screen.nonblank_rows(), vec![0, 2]);
+
+        assert_eq!(screen.row_text(99), "");
+    }
+
+    #[test]
+    fn test_osc52_clipboard() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.clipboard(), None);
+
+        screen.feed(b"\x1b]52;c;Y29waWVkIHRleHQ=\x07");
+        assert_eq!(screen.clipboard(), Some("copied text".to_string()));
+
+        // Primary and select targets, ST terminator, unpadded payload
+        screen.feed(b"\x1b]52;p;cHJpbWFyeQ\x1b\\");
+        assert_eq!(screen.clipboard(), Some("primary".to_string()));
+        screen.feed(b"\x1b]52;s;c2VsZWN0\x07");
+        assert_eq!(screen.clipboard(), Some("select".to_string()));
+
+        // Queries, unknown targets, and bad base64 leave the last write alone
+        screen.feed(b"\x1b]52;c;?\x07");
+        screen.feed(b"\x1b]52;x;aGVsbG8=\x07");
+        screen.feed(b"\x1b]52;c;!!!!\x07");
+        assert_eq!(screen.clipboard(), Some("select".to_string()));
+    }
+
+    #[test]
+    fn test_has_any_graphics() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"plain text only");
+        assert!(!screen.has_any_graphics());
+
+        let images: [&[u8]; 3] = [
+            b"\x1bPq\"1;1;100;50#0~\x1b\\",
+            b"\x1b_Ga=T,s=10,v=10;AAAA\x1b\\",
+            b"\x1b]1337;File=inline=1;width=10:AAAA\x07",
+        ];
+        for image in images {
+            let mut screen = ScreenState::new(80, 24);
+            screen.feed(image);
+            assert!(screen.has_any_graphics(), "{:?}", String::from_utf8_lossy(image));
+        }
+    }
+
+    #[test]
+    fn test_osc12_cursor_color() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.cursor_color(), None);
+
+        screen.feed(b"\x1b]12;#f5e0dc\x07");
+        assert_eq!(screen.cursor_color(), Some(0xF5E0DCFF));
+
+        // Short hex doubles each digit
+        screen.feed(b"\x1b]12;#f80\x1b\\");
+        assert_eq!(screen.cursor_color(), Some(0xFF8800FF));
+
+        // X11 rgb: form with 2- and 4-digit channels
+        screen.feed(b"\x1b]12;rgb:cd/d6/f4\x07");
+        assert_eq!(screen.cursor_color(), Some(0xCDD6F4FF));
+        screen.feed(b"\x1b]12;rgb:ffff/8080/0000\x07");
+        assert_eq!(screen.cursor_color(), Some(0xFF8000FF));
+
+        // Queries and malformed specs leave the color alone
+        screen.feed(b"\x1b]12;?\x07");
+        screen.feed(b"\x1b]12;#12345\x07");
+        screen.feed(b"\x1b]12;rgb:ff/ff\x07");
+        assert_eq!(screen.cursor_color(), Some(0xFF8000FF));
+
+        screen.feed(b"\x1b]112\x07");
+        assert_eq!(screen.cursor_color(), None);
+    }
+
+    #[test]
+    fn test_osc_log_records_custom_sequences() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"\x1b]7777;ignored\x07");
+        assert!(screen.osc_log().is_empty());
+
+        screen.set_record_osc(true);
+        screen.feed(b"\x1b]7777;ping;seq=1\x07");
+        screen.feed(b"\x1b]1337;SetUserVar=mode=dGVzdA==\x1b\\");
+        screen.feed(b"\x1b]112\x07");
+        screen.feed(b"\x1b]not-a-number;x\x07");
+
+        // Known sequences are still handled as well as logged
+        screen.feed(b"\x1b]7;file://host/tmp\x07");
+        assert_eq!(screen.current_directory(), Some("/tmp"));
+
+        assert_eq!(
+            screen.osc_log(),
+            &[
+                (7777, b"ping;seq=1".to_vec()),
+                (1337, b"SetUserVar=mode=dGVzdA==".to_vec()),
+                (112, Vec::new()),
+                (7, b"file://host/tmp".to_vec()),
+            ]
+        );
+
+        screen.set_record_osc(false);
+        screen.feed(b"\x1b]7777;after\x07");
+        assert_eq!(screen.osc_log().len(), 4);
+
+        screen.clear_osc_log();
+        assert!(screen.osc_log().is_empty());
+    }
+
+    #[test]
+    fn test_strip_ansi_mixed_sequences() {
+        // SGR colors, including 256-color and truecolor forms
+        assert_eq!(
+            strip_ansi(b"\x1b[31merror\x1b[0m \x1b[38;5;208mwarn\x1b[38;2;1;2;3m!\x1b[m"),
+            "error warn!"
+        );
+
+        // Cursor moves, erases and private modes vanish
+        assert_eq!(strip_ansi(b"\x1b[2J\x1b[H\x1b[?25lTop\x1b[5;10HMid\x1b[K\x1b[?25h"), "TopMid");
+
+        // OSC with either terminator, DCS and charset designations
+        assert_eq!(
+            strip_ansi(
+                b"\x1b]133;A\x07$ \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\\r\n\
+                  \x1bPq\"1;1;10;10#0~\x1b\\\x1b(Bpic\x1b7\x1b8"
+            ),
+            "$ link\npic"
+        );
+
+        // Tabs and UTF-8 survive, other controls and cut-off sequences do not
+        assert_eq!(strip_ansi("a\tb\x07\x08ü\x1b[3".as_bytes()), "a\tbü");
+        assert_eq!(strip_ansi(b"tail\x1b]0;unterminated"), "tail");
+        assert_eq!(strip_ansi(b"plain"), "plain");
+    }
+
+    #[test]
+    fn test_scan_escape() {
+        assert_eq!(scan_escape(b"\x1b[1;31mX"), Some((EscapeSequence::Other, 7)));
+        assert_eq!(scan_escape(b"\x1b]0;hi\x07X"), Some((EscapeSequence::Osc(b"0;hi"), 7)));
+        assert_eq!(scan_escape(b"\x1b]0;hi\x1b\\X"), Some((EscapeSequence::Osc(b"0;hi"), 8)));
+        assert_eq!(scan_escape(b"\x1b_Gi=1\x1b\\X"), Some((EscapeSequence::Other, 8)));
+        assert_eq!(scan_escape(b"\x1b(BX"), Some((EscapeSequence::Other, 3)));
+        assert_eq!(scan_escape(b"\x1b7X"), Some((EscapeSequence::Other, 2)));
+
+        // Incomplete sequences
+        assert_eq!(scan_escape(b"\x1b"), None);
+        assert_eq!(scan_escape(b"\x1b[1;3"), None);
+        assert_eq!(scan_escape(b"\x1b]0;hi\x1b"), None);
+        assert_eq!(scan_escape(b"\x1bPq#0~"), None);
+    }
+
+    #[test]
+    fn test_dcs_log_records_queries_not_sixel() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"\x1bP$qm\x1b\\");
+        assert!(screen.dcs_log().is_empty());
+        assert!(screen.sixel_regions().is_empty());
+
+        screen.set_record_dcs(true);
+        // DECRQSS request for SGR, then the terminal's reply
+        screen.feed(b"\x1bP$qm\x1b\\");
+        screen.feed(b"\x1bP1$r0;38;5;208m\x1b\\");
+        // A real Sixel image is still a Sixel and not logged
+        screen.feed(b"\x1bPq\"1;1;20;20#0~\x1b\\");
+
+        assert_eq!(
+            screen.dcs_log(),
+            &[
+                (b'q', vec![], b"m".to_vec()),
+                (b'r', vec![1], b"0;38;5;208m".to_vec()),
+            ]
+        );
+        assert_eq!(screen.sixel_regions().len(), 1);
+
+        screen.clear_dcs_log();
+        assert!(screen.dcs_log().is_empty());
+    }
+
+    #[test]
+    fn test_parse_color_spec() {
+        assert_eq!(parse_color_spec("#000"), Some(0x000000FF));
+        assert_eq!(parse_color_spec("#fff"), Some(0xFFFFFFFF));
+        assert_eq!(parse_color_spec("rgb:f/0/8"), Some(0xFF0088FF));
+        assert_eq!(parse_color_spec("rgb:800/000/fff"), Some(0x8000FFFF));
+        assert_eq!(parse_color_spec("red"), None);
+        assert_eq!(parse_color_spec("rgb:12345/0/0"), None);
+        assert_eq!(parse_color_spec("#ggg"), None);
+    }
+
+    #[test]
+    fn test_osc_bel_and_st_terminators_match() {
+        // vtparse ends OSC strings on either terminator; every OSC feature
+        // must come out the same whichever one the program picked
+        let sequences: [&[u8]; 3] = [
+            b"7;file://host/tmp/project",
+            b"52;c;Y29waWVkIHRleHQ=",
+            b"1337;File=name=aW1n;width=4;height=2;inline=1:AAAA",
+        ];
+
+        for payload in sequences {
+            let render = |terminator: &[u8]| {
+                let mut screen = ScreenState::new(40, 4);
+                screen.feed(b"before ");
+                screen.feed(&[b"\x1b]", payload, terminator].concat());
+                screen.feed(b"after");
+                screen
+            };
+            let bel = render(b"\x07");
+            let st = render(b"\x1b\\");
+            let name = String::from_utf8_lossy(payload);
+
+            assert_eq!(bel.current_directory(), st.current_directory(), "{}", name);
+            assert_eq!(bel.clipboard(), st.clipboard(), "{}", name);
+            let regions = |screen: &ScreenState| {
+                screen
+                    .iterm2_regions()
+                    .iter()
+                    .map(|r| (r.start_row, r.start_col, r.width, r.height, r.data.clone()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(regions(&bel), regions(&st), "{}", name);
+
+            // The terminator must not leak into the text or move the cursor
+            assert_eq!(st.row_text(0), "before after", "{}", name);
+            assert_eq!(bel.contents(), st.contents(), "{}", name);
+            assert_eq!(bel.cursor_position(), st.cursor_position(), "{}", name);
+        }
+
+        // Guard against both paths silently ignoring the sequences
+        let mut screen = ScreenState::new(40, 4);
+        screen.feed(b"\x1b]7;file://host/tmp/project\x1b\\");
+        screen.feed(b"\x1b]52;c;Y29waWVkIHRleHQ=\x1b\\");
+        screen.feed(b"\x1b]1337;File=name=aW1n;width=4;height=2;inline=1:AAAA\x1b\\");
+        assert_eq!(screen.current_directory(), Some("/tmp/project"));
+        assert_eq!(screen.clipboard(), Some("copied text".to_string()));
+        assert_eq!(screen.iterm2_regions().len(), 1);
+        let image = &screen.iterm2_regions()[0];
+        assert_eq!((image.width, image.height), (4, 2));
+    }
+
+    #[test]
+    fn test_resize_keeps_overlapping_cells() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"\x1b[31mred\x1b[0m\r\nsecond\r\nthird row!");
+
+        screen.resize(30, 5);
+        assert_eq!(screen.size(), (30, 5));
+        assert_eq!(screen.row_text(0), "red");
+        assert_eq!(screen.row_text(2), "third row!");
+        assert_eq!(screen.get_cell(0, 0).unwrap().fg, Some(1));
+        // The cursor stays put, and the new area is writable
+        assert_eq!(screen.cursor_position(), (2, 9));
+        screen.feed(b"\x1b[5;12Hcorner");
+        assert_eq!(screen.row_text(4), "           corner");
+
+        // Default tab stops extend into the new columns
+        screen.feed(b"\x1b[4;1H\t\t\tx");
+        assert_eq!(screen.text_at(3, 24), Some('x'));
+
+        screen.resize(4, 2);
+        assert_eq!(screen.contents(), "red \nseco");
+        assert_eq!(screen.cursor_position(), (1, 3));
+        assert!(screen.dirty_rows().iter().all(|&row| row < 2));
+    }
+
+    #[test]
+    fn test_osc_st_split_across_feeds() {
+        // ESC at the end of one read, backslash at the start of the next
+        let mut screen = ScreenState::new(40, 4);
+        screen.feed(b"\x1b]7;file://host/srv\x1b");
+        screen.feed(b"\\ok");
+        assert_eq!(screen.current_directory(), Some("/srv"));
+        assert_eq!(screen.row_text(0), "ok");
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64(b""), Some(vec![]));
+        assert_eq!(decode_base64(b"YQ=="), Some(b"a".to_vec()));
+        assert_eq!(decode_base64(b"YWI="), Some(b"ab".to_vec()));
+        assert_eq!(decode_base64(b"YWJj"), Some(b"abc".to_vec()));
+        assert_eq!(decode_base64(b"YWJjZA"), Some(b"abcd".to_vec()));
+        assert_eq!(decode_base64(b"Y"), None);
+    }
+
+    #[test]
+    fn test_parse_osc7_path() {
+        assert_eq!(parse_osc7_path("file://h/a%20b/c"), Some("/a b/c".to_string()));
+        assert_eq!(parse_osc7_path("file://h/100%"), Some("/100%".to_string()));
+        assert_eq!(parse_osc7_path("file://hostonly"), None);
+        assert_eq!(parse_osc7_path("http://h/tmp"), None);
+    }
+
+    #[test]
+    fn test_default_tab_stops() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"ab\t");
+        assert_eq!(screen.cursor_position(), (0, 8));
+        screen.feed(b"\t");
+        assert_eq!(screen.cursor_position(), (0, 16));
+    }
+
+    #[test]
+    fn test_custom_tab_stop_hts() {
+        let mut screen = ScreenState::new(80, 24);
+
+        // Clear all stops, set one at column 20, return to column 0 and tab
+        screen.feed(b"\x1b[3g\x1b[1;21H\x1bH\r\t");
+        assert_eq!(screen.cursor_position(), (0, 20));
+
+        // No further stops: tab moves to the last column
+        screen.feed(b"\t");
+        assert_eq!(screen.cursor_position(), (0, 79));
+    }
+
+    #[test]
+    fn test_esc_with_intermediates_is_not_hts_or_index() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"\x1b[3g\x1b[1;21H");
+
+        // Charset and DEC sequences ending in H, D, E or M
+        screen.feed(b"\x1b(H\x1b#H\x1b(D\x1b(E\x1b(M");
+        assert_eq!(screen.cursor_position(), (0, 20));
+
+        // No stop was set at column 20
+        screen.feed(b"\r\t");
+        assert_eq!(screen.cursor_position(), (0, 79));
+    }
+
+    #[test]
+    fn test_tab_clear_at_cursor() {
+        let mut screen = ScreenState::new(80, 24);
+
+        // Clear the stop at column 8 only
+        screen.feed(b"\x1b[1;9H\x1b[g\r\t");
+        assert_eq!(screen.cursor_position(), (0, 16));
+
+        // Explicit TBC 0 behaves the same
+        screen.feed(b"\x1b[0g\r\t");
+        assert_eq!(screen.cursor_position(), (0, 24));
+    }
+
+    #[test]
+    fn test_bracketed_paste_mode() {
+        let mut screen = ScreenState::new(80, 24);
+        assert!(!screen.bracketed_paste_enabled());
+
+        screen.feed(b"\x1b[?2004h");
+        assert!(screen.bracketed_paste_enabled());
+
+        // Non-private mode 2004 is not DECSET and is ignored
+        screen.feed(b"\x1b[2004l");
+        assert!(screen.bracketed_paste_enabled());
+
+        // Combined with other private modes
+        screen.feed(b"\x1b[?25;2004l");
+        assert!(!screen.bracketed_paste_enabled());
+    }
+
+    #[test]
+    fn test_contents_with_ruler() {
+        let mut screen = ScreenState::new(25, 11);
+        screen.feed(b"\x1b[11;21Hx");
+
+        let dump = screen.contents_with_ruler();
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "   |          1         2");
+        assert_eq!(lines[1], "   |0123456789012345678901234");
+        assert_eq!(lines[2], " 0 |");
+        assert_eq!(lines[12], "10 |                    x");
+
+        // The marker sits under column 20 of the ruler
+        assert_eq!(lines[12].find('x'), Some("10 |".len() + 20));
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let mut screen = ScreenState::new(20, 4);
+        screen.feed(b"\x1b[?1049hMenu\r\n\x1b[31m> ");
+        let checkpoint = screen.checkpoint();
+        let before = screen.snapshot();
+
+        screen.feed(b"Open\x1b[0m\x1b[?1049l\r\nmore\r\nlines\r\nscroll");
+        assert_ne!(screen.snapshot(), before);
+
+        screen.restore(&checkpoint);
+        assert_eq!(screen.snapshot(), before);
+        assert_eq!(screen.snapshot(), checkpoint.snapshot());
+        assert!(screen.private_mode(1049));
+
+        // Attributes are restored too: red is still active
+        screen.feed(b"Save");
+        assert_eq!(screen.row_contents(1).trim_end(), "> Save");
+        assert_eq!(screen.get_cell(1, 2).map(|cell| cell.fg), Some(Some(1)));
+
+        // A checkpoint can be restored more than once
+        screen.restore(&checkpoint);
+        assert_eq!(screen.snapshot(), before);
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_kitty_keyboard_flag_stack() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.kitty_keyboard_flags(), 0);
+
+        screen.feed(b"\x1b[>1u");
+        screen.feed(b"\x1b[>9u");
+        assert_eq!(screen.kitty_keyboard_flags(), 9);
+
+        // Set bits, clear bits, then replace the current entry
+        screen.feed(b"\x1b[=2;2u");
+        assert_eq!(screen.kitty_keyboard_flags(), 11);
+        screen.feed(b"\x1b[=8;3u");
+        assert_eq!(screen.kitty_keyboard_flags(), 3);
+        screen.feed(b"\x1b[=1u");
+        assert_eq!(screen.kitty_keyboard_flags(), 1);
+
+        screen.feed(b"\x1b[<u");
+        assert_eq!(screen.kitty_keyboard_flags(), 1);
+        screen.feed(b"\x1b[<5u");
+        assert_eq!(screen.kitty_keyboard_flags(), 0);
+
+        // Queries and plain CSI u leave the flags alone
+        screen.feed(b"\x1b[>1u\x1b[?u\x1b[u");
+        assert_eq!(screen.kitty_keyboard_flags(), 1);
+        assert!(!screen.contents().contains('u'));
+    }
+
+    #[test]
+    fn test_mouse_mode() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.mouse_mode(), MouseMode::Off);
+
+        // SGR encoding alone does not enable reporting
+        screen.feed(b"\x1b[?1006h");
+        assert_eq!(screen.mouse_mode(), MouseMode::Off);
+
+        screen.feed(b"\x1b[?9h");
+        assert_eq!(screen.mouse_mode(), MouseMode::X10);
+
+        screen.feed(b"\x1b[?1003h");
+        assert_eq!(screen.mouse_mode(), MouseMode::Sgr);
+
+        screen.feed(b"\x1b[?1006l");
+        assert_eq!(screen.mouse_mode(), MouseMode::Normal);
+
+        screen.feed(b"\x1b[?1003;9l");
+        assert_eq!(screen.mouse_mode(), MouseMode::Off);
+    }
+
+    #[test]
+    fn test_private_mode_map() {
+        let mut screen = ScreenState::new(80, 24);
+        assert!(screen.private_mode(7));
+        assert!(screen.private_mode(25));
+        for mode in [1000, 1006, 1049, 2004] {
+            assert!(!screen.private_mode(mode));
+        }
+
+        screen.feed(b"\x1b[?1049h\x1b[?1000;1006h\x1b[?7l\x1b[?25l");
+        assert!(screen.private_mode(1049));
+        assert!(screen.private_mode(1000));
+        assert!(screen.private_mode(1006));
+        assert!(!screen.private_mode(7));
+        assert!(!screen.private_mode(25));
+        assert!(!screen.private_mode(2004));
+
+        // ANSI (non-private) modes don't touch the map
+        screen.feed(b"\x1b[1049l");
+        assert!(screen.private_mode(1049));
+
+        screen.feed(b"\x1b[?1000l\x1b[?25h\x1b[?2004h");
+        assert!(!screen.private_mode(1000));
+        assert!(screen.private_mode(1006));
+        assert!(screen.private_mode(25));
+        assert!(screen.bracketed_paste_enabled());
+
+        // Values outside the u16 range are ignored
+        screen.feed(b"\x1b[?70000h");
+        assert!(!screen.private_mode(70000u32 as u16));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"\x1b[31mError\x1b[0m <ok> & \x1b[1;44mdone");
+
+        let svg = screen.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(r#"width="160" height="48""#));
+
+        // Red run with its own fill, followed by default-colored escaped text
+        assert!(svg.contains(r##"fill="#CD0000" textLength="40""##));
+        assert!(svg.contains(">Error</text>"));
+        assert!(svg.contains("> &lt;ok&gt; &amp; </text>"));
+
+        // Bold text over a blue background rect
+        assert!(svg.contains(r##"<rect x="104" y="0" width="32" height="16" fill="#0000EE"/>"##));
+        assert!(svg.contains(r#"font-weight="bold""#));
+        assert!(svg.contains(">done</text>"));
+
+        // Cursor highlight just after the last character
+        assert!(svg.contains(r#"<rect class="cursor" x="136" y="0" width="8" height="16""#));
+    }
+
+    #[test]
+    fn test_to_svg_custom_cell_size() {
+        let mut screen = ScreenState::new(4, 2);
+        screen.feed(b"\x1b[38;5;196mab\r\n\x1b[38;5;244mcd");
+
+        let svg = screen.to_svg_with_cell_size(10, 20);
+        assert!(svg.contains(r#"width="40" height="40""#));
+        assert!(svg.contains(r##"fill="#FF0000" textLength="20""##));
+        assert!(svg.contains(r##"fill="#808080""##));
+        assert!(svg.contains(r#"<rect class="cursor" x="20" y="20" width="10" height="20""#));
+    }
+
+    #[test]
+    fn test_index_scrolls_at_bottom() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"one\r\ntwo\r\nthree");
+
+        // IND in the middle just moves down
+        screen.feed(b"\x1b[2;1H\x1bD");
+        assert_eq!(screen.cursor_position(), (2, 0));
+        assert_eq!(screen.row_contents(0).trim_end(), "one");
+
+        // IND at the bottom scrolls everything up a line
+        screen.feed(b"\x1b[3;4H\x1bD");
+        assert_eq!(screen.cursor_position(), (2, 3));
+        assert_eq!(screen.row_contents(0).trim_end(), "two");
+        assert_eq!(screen.row_contents(1).trim_end(), "three");
+        assert_eq!(screen.row_contents(2).trim_end(), "");
+
+        // NEL at the bottom scrolls and returns to column 0
+        screen.feed(b"four\x1bE");
+        assert_eq!(screen.cursor_position(), (2, 0));
+        assert_eq!(screen.row_contents(0).trim_end(), "three");
+        assert_eq!(screen.row_contents(1).trim_end(), "   four");
+        assert_eq!(screen.row_contents(2).trim_end(), "");
+    }
+
+    #[test]
+    fn test_line_feed_scrolls_at_bottom() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"one\r\ntwo\r\nthree\r\nfour");
+        assert_eq!(screen.cursor_position(), (2, 4));
+        assert_eq!(screen.row_contents(0).trim_end(), "two");
+        assert_eq!(screen.row_contents(1).trim_end(), "three");
+        assert_eq!(screen.row_contents(2).trim_end(), "four");
+
+        // VT and FF scroll the same way
+        screen.clear_dirty_rows();
+        screen.feed(b"\x0b\x0c");
+        assert_eq!(screen.row_contents(0).trim_end(), "four");
+        assert_eq!(screen.row_contents(1).trim_end(), "");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_reverse_index_scrolls_at_top() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"one\r\ntwo\r\nthree");
+
+        // RI away from the top just moves up
+        screen.feed(b"\x1bM");
+        assert_eq!(screen.cursor_position(), (1, 5));
+        assert_eq!(screen.row_contents(0).trim_end(), "one");
+
+        // RI at the top scrolls everything down, dropping the last line
+        screen.feed(b"\x1b[1;1H\x1bM");
+        assert_eq!(screen.cursor_position(), (0, 0));
+        assert_eq!(screen.row_contents(0).trim_end(), "");
+        assert_eq!(screen.row_contents(1).trim_end(), "one");
+        assert_eq!(screen.row_contents(2).trim_end(), "two");
+
+        // The new top line can be written like a pager scrolling back
+        screen.feed(b"zero");
+        assert_eq!(screen.row_contents(0).trim_end(), "zero");
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"aaaaa ab\r\n  ab\r\nxxxxxxxxxa");
+
+        // Zero, one and multiple matches
+        assert!(screen.find_all("zz").is_empty());
+        assert!(screen.find_all("").is_empty());
+        assert_eq!(screen.find_all("xa"), vec![(2, 8)]);
+        assert_eq!(screen.find_all("ab"), vec![(0, 6), (1, 2)]);
+
+        // Non-overlapping, scanning left to right
+        assert_eq!(screen.find_all("aa"), vec![(0, 0), (0, 2)]);
+    }
+
+    #[test]
+    fn test_find_all_does_not_span_rows() {
+        let mut screen = ScreenState::new(4, 2);
+        screen.feed(b"  ER\r\nROR");
+
+        assert!(screen.contents().contains("ER\nROR"));
+        assert!(screen.find_all("ERROR").is_empty());
+        assert!(screen.find_all("ER\nROR").is_empty());
+        assert_eq!(screen.find_all("ROR"), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn test_zero_size_screens() {
+        for (width, height) in [(0, 0), (0, 5), (5, 0)] {
+            let mut screen = ScreenState::new(width, height);
+            screen.feed(b"Hello\r\nWorld\tTab\x1bD\x1bE\x1bM");
+            screen.feed(b"\x1b[10;10H\x1b[5B\x1b[5C\x1b[2A\x1b[2D");
+            screen.feed(b"\x1b[31mred\x1b[0m");
+
+            let (row, col) = screen.cursor_position();
+            assert_eq!(screen.size(), (width, height));
+            assert!(row <= height.saturating_sub(1) && col <= width.saturating_sub(1));
+            assert!(!screen.contains("Hello"));
+            assert!(screen.find_all("World").is_empty());
+            assert_eq!(screen.get_cell(0, 0), None);
+        }
+    }
+
+    #[test]
+    fn test_cursor_moves_clamp_without_overflow() {
+        let mut screen = ScreenState::new(10, 5);
+        screen.feed(b"\x1b[65535B\x1b[65535C");
+        assert_eq!(screen.cursor_position(), (4, 9));
+    }
+
+    #[test]
+    fn test_dirty_rows_after_targeted_writes() {
+        let mut screen = ScreenState::new(20, 5);
+        assert!(screen.dirty_rows().is_empty());
+        assert!(!screen.has_dirty_rows());
+
+        // Cursor movement alone does not dirty anything
+        screen.feed(b"\x1b[4;3H\x1b[A\r\n");
+        assert!(!screen.has_dirty_rows());
+
+        screen.feed(b"\x1b[2;1Hfirst\x1b[5;10Hlast");
+        assert_eq!(screen.dirty_rows(), vec![1, 4]);
+
+        screen.clear_dirty_rows();
+        assert!(screen.dirty_rows().is_empty());
+
+        // Writing the same text again still counts as a touch
+        screen.feed(b"\x1b[2;1Hfirst");
+        assert_eq!(screen.dirty_rows(), vec![1]);
+        assert!(screen.contains("first"));
+    }
+
+    #[test]
+    fn test_dirty_rows_after_scroll() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"\x1b[3;1H");
+        assert!(!screen.has_dirty_rows());
+
+        screen.feed(b"\x1bD");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2]);
+
+        screen.clear_dirty_rows();
+        screen.feed(b"\x1b[1;1H\x1bM");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_dirty_rows_after_resize() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.resize(10, 3);
+        assert!(!screen.has_dirty_rows());
+
+        screen.resize(5, 2);
+        assert_eq!(screen.dirty_rows(), vec![0, 1]);
+
+        // Cursor movement and unhandled erase sequences mark nothing
+        screen.clear_dirty_rows();
+        screen.feed(b"\x1b[2;3H\x1b[2J\x1b[K");
+        assert!(!screen.has_dirty_rows());
+    }
 }
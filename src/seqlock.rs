@@ -47,16 +47,49 @@
 //! # }
 //! ```
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+use crate::clock::{Clock, SystemClock};
 use crate::ipc::{DaemonSharedMemory, IpcError, IpcResult};
 
+/// Memory protected by a seqlock that [`SeqlockVerifier`] can observe.
+///
+/// Implemented for [`DaemonSharedMemory`] and for [`MockSeqlockMemory`], which
+/// lets the verifier be exercised against a controllable writer.
+pub trait SeqlockMemory {
+    /// Read the writer's current sequence number, bypassing any cache.
+    fn raw_sequence_number(&self) -> u32;
+
+    /// Reload any cached state after a read.
+    fn refresh(&mut self) -> IpcResult<()>;
+
+    /// Read the protected contents as text.
+    fn grid_contents(&self) -> IpcResult<String>;
+}
+
+impl SeqlockMemory for DaemonSharedMemory {
+    fn raw_sequence_number(&self) -> u32 {
+        DaemonSharedMemory::raw_sequence_number(self)
+    }
+
+    fn refresh(&mut self) -> IpcResult<()> {
+        DaemonSharedMemory::refresh(self)
+    }
+
+    fn grid_contents(&self) -> IpcResult<String> {
+        DaemonSharedMemory::grid_contents(self)
+    }
+}
+
 /// Report from seqlock verification.
 ///
 /// Contains statistics about sequence number changes, torn read detections,
 /// and retry behavior during verification.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SeqlockReport {
     /// Total number of read attempts.
     pub total_reads: u64,
@@ -67,6 +100,9 @@ pub struct SeqlockReport {
     /// Number of times sequence was odd (write in progress).
     pub odd_sequence_detections: u64,
 
+    /// Number of times the sequence moved backward (buggy or restarted writer).
+    pub backward_sequence_detections: u64,
+
     /// Maximum number of retries for a single read.
     pub max_retry_count: u32,
 
@@ -90,6 +126,14 @@ impl SeqlockReport {
         self.odd_sequence_detections > 0
     }
 
+    /// Check if the sequence ever moved backward.
+    ///
+    /// A correct writer only increments the sequence, so this points at a
+    /// writer bug or a writer that restarted mid-test.
+    pub fn has_backward_sequences(&self) -> bool {
+        self.backward_sequence_detections > 0
+    }
+
     /// Calculate the percentage of reads that required retries.
     pub fn retry_percentage(&self) -> f64 {
         if self.total_reads == 0 {
@@ -98,8 +142,38 @@ impl SeqlockReport {
             (self.torn_read_detections as f64 / self.total_reads as f64) * 100.0
         }
     }
+
+    /// Serialize the report to JSON for CI dashboards and other tooling.
+    ///
+    /// Field names match the struct fields.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> IpcResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| IpcError::InvalidData(format!("Failed to serialize report: {}", e)))
+    }
+
+    /// Calculate the number of backward sequence moves as a percentage of reads.
+    pub fn backward_sequence_percentage(&self) -> f64 {
+        if self.total_reads == 0 {
+            0.0
+        } else {
+            (self.backward_sequence_detections as f64 / self.total_reads as f64) * 100.0
+        }
+    }
+}
+
+/// Whether a sequence number moved backward from `from` to `to`.
+///
+/// Distances of more than half the `u32` range are treated as backward moves
+/// rather than forward ones, so a counter wrapping past `u32::MAX` still
+/// counts as moving forward.
+fn moved_backward(from: u32, to: u32) -> bool {
+    to.wrapping_sub(from) > u32::MAX / 2
 }
 
+/// Default number of retries before [`SeqlockVerifier::synchronized_read`] gives up.
+const DEFAULT_MAX_RETRIES: u32 = 100;
+
 /// Seqlock verifier for detecting torn reads.
 ///
 /// This verifier tracks sequence number changes and detects torn reads
@@ -108,6 +182,10 @@ impl SeqlockReport {
 pub struct SeqlockVerifier {
     last_seq: AtomicU32,
     report: SeqlockReport,
+    /// Retries taken by all successful reads, for `avg_retry_count`.
+    total_retries: u64,
+    max_retries: u32,
+    clock: Arc<dyn Clock>,
 }
 
 impl SeqlockVerifier {
@@ -116,9 +194,35 @@ impl SeqlockVerifier {
         Self {
             last_seq: AtomicU32::new(0),
             report: SeqlockReport::default(),
+            total_retries: 0,
+            max_retries: DEFAULT_MAX_RETRIES,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Set how many retries a synchronized read may take before failing.
+    ///
+    /// Defaults to 100. Heavily contended buffers may need more.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the clock that times
+    /// [`verify_seqlock_pattern`](Self::verify_seqlock_pattern).
+    ///
+    /// Defaults to [`SystemClock`]. A [`FakeClock`](crate::FakeClock) runs
+    /// the whole monitoring window without sleeping, one read per poll.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get the configured retry limit.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
     /// Perform a synchronized read, retrying on torn reads.
     ///
     /// This method implements the seqlock read protocol:
@@ -127,7 +231,13 @@ impl SeqlockVerifier {
     /// 3. Read sequence number again and verify it hasn't changed
     /// 4. Retry if torn read detected
     ///
-    /// Returns the result along with the number of retries needed.
+    /// A sequence number lower than the one seen before the read, or than the
+    /// one from the previous successful read, is counted in
+    /// [`SeqlockReport::backward_sequence_detections`] instead of as a torn read.
+    ///
+    /// Returns the result along with the number of retries needed, or
+    /// [`IpcError::InvalidData`] once [`max_retries`](Self::max_retries) is
+    /// reached.
     ///
     /// # Example
     ///
@@ -152,30 +262,32 @@ impl SeqlockVerifier {
     /// # }
     /// # }
     /// ```
-    pub fn synchronized_read<F, T>(
-        &mut self,
-        shm: &mut DaemonSharedMemory,
-        reader: F,
-    ) -> IpcResult<(T, u32)>
+    pub fn synchronized_read<M, F, T>(&mut self, shm: &mut M, reader: F) -> IpcResult<(T, u32)>
     where
-        F: Fn(&DaemonSharedMemory) -> IpcResult<T>,
+        M: SeqlockMemory,
+        F: Fn(&M) -> IpcResult<T>,
     {
-        const MAX_RETRIES: u32 = 100;
         let mut retry_count = 0;
 
         loop {
             // Read sequence number before
             let seq_before = shm.raw_sequence_number();
 
+            // The writer restarted or misbehaved since the last successful read
+            if self.report.total_reads > 0
+                && moved_backward(self.last_seq.load(Ordering::SeqCst), seq_before)
+            {
+                self.report.backward_sequence_detections += 1;
+                self.last_seq.store(seq_before, Ordering::SeqCst);
+            }
+
             // If sequence is odd, a write is in progress
             if seq_before & 1 != 0 {
                 self.report.odd_sequence_detections += 1;
                 retry_count += 1;
 
-                if retry_count >= MAX_RETRIES {
-                    return Err(IpcError::InvalidData(
-                        format!("Seqlock retry limit exceeded after {} attempts", MAX_RETRIES)
-                    ));
+                if retry_count >= self.max_retries {
+                    return Err(self.retry_limit_error());
                 }
 
                 // Spin briefly and retry
@@ -192,13 +304,16 @@ impl SeqlockVerifier {
 
             // Check if sequence changed during read (torn read)
             if seq_before != seq_after {
-                self.report.torn_read_detections += 1;
+                if moved_backward(seq_before, seq_after) {
+                    self.report.backward_sequence_detections += 1;
+                    self.last_seq.store(seq_after, Ordering::SeqCst);
+                } else {
+                    self.report.torn_read_detections += 1;
+                }
                 retry_count += 1;
 
-                if retry_count >= MAX_RETRIES {
-                    return Err(IpcError::InvalidData(
-                        format!("Seqlock retry limit exceeded after {} attempts", MAX_RETRIES)
-                    ));
+                if retry_count >= self.max_retries {
+                    return Err(self.retry_limit_error());
                 }
 
                 // Retry the read
@@ -211,13 +326,11 @@ impl SeqlockVerifier {
                 self.report.max_retry_count = self.report.max_retry_count.max(retry_count);
             }
 
-            // Update average retry count
-            let total_retries = self.report.torn_read_detections + self.report.odd_sequence_detections;
-            self.report.avg_retry_count = if self.report.total_reads > 0 {
-                total_retries as f64 / self.report.total_reads as f64
-            } else {
-                0.0
-            };
+            // Update average retry count. Only re-attempts of a read count; a
+            // backward move between reads is reported but cost no retry.
+            self.total_retries += u64::from(retry_count);
+            self.report.avg_retry_count =
+                self.total_retries as f64 / self.report.total_reads as f64;
 
             // Store last seen sequence
             self.last_seq.store(seq_after, Ordering::SeqCst);
@@ -249,7 +362,7 @@ impl SeqlockVerifier {
     /// # }
     /// # }
     /// ```
-    pub fn is_write_in_progress(&self, shm: &DaemonSharedMemory) -> bool {
+    pub fn is_write_in_progress<M: SeqlockMemory>(&self, shm: &M) -> bool {
         let seq = shm.raw_sequence_number();
         seq & 1 != 0
     }
@@ -297,15 +410,15 @@ impl SeqlockVerifier {
     /// # }
     /// # }
     /// ```
-    pub fn verify_seqlock_pattern(
+    pub fn verify_seqlock_pattern<M: SeqlockMemory>(
         &mut self,
-        shm: &mut DaemonSharedMemory,
+        shm: &mut M,
         duration: Duration,
         poll_interval: Duration,
     ) -> IpcResult<SeqlockReport> {
-        let start = Instant::now();
+        let start = self.clock.now();
 
-        while start.elapsed() < duration {
+        while self.clock.elapsed_since(start) < duration {
             // Perform a synchronized read of the grid contents
             let (_grid, retries) = self.synchronized_read(shm, |shm| {
                 shm.grid_contents()
@@ -317,12 +430,19 @@ impl SeqlockVerifier {
             }
 
             // Sleep for the poll interval
-            std::thread::sleep(poll_interval);
+            self.clock.sleep(poll_interval);
         }
 
         Ok(self.report.clone())
     }
 
+    fn retry_limit_error(&self) -> IpcError {
+        IpcError::InvalidData(format!(
+            "Seqlock retry limit exceeded after {} attempts",
+            self.max_retries
+        ))
+    }
+
     /// Get the current verification report.
     ///
     /// Returns a reference to the accumulated statistics.
@@ -336,6 +456,7 @@ impl SeqlockVerifier {
     /// verification run.
     pub fn reset(&mut self) {
         self.report = SeqlockReport::default();
+        self.total_retries = 0;
         self.last_seq.store(0, Ordering::SeqCst);
     }
 }
@@ -346,6 +467,181 @@ impl Default for SeqlockVerifier {
     }
 }
 
+/// In-process seqlock writer for testing [`SeqlockVerifier`].
+///
+/// A background thread repeatedly rewrites a byte buffer under the seqlock
+/// protocol: it makes the sequence number odd, fills the first half of the
+/// buffer, waits `write_duration`, fills the second half, then makes the
+/// sequence even again and waits `write_interval` before the next write.
+/// Each write uses a single letter, so a torn read shows up as mixed letters.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "ipc")]
+/// # {
+/// use std::time::Duration;
+/// use terminal_testlib::seqlock::{MockSeqlockMemory, SeqlockMemory, SeqlockVerifier};
+///
+/// let mut shm = MockSeqlockMemory::new(64)
+///     .with_write_interval(Duration::from_millis(1))
+///     .start();
+/// let mut verifier = SeqlockVerifier::new();
+///
+/// let (contents, _retries) = verifier
+///     .synchronized_read(&mut shm, |shm| shm.grid_contents())
+///     .unwrap();
+/// assert!(contents.bytes().all(|b| b == contents.as_bytes()[0]));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MockSeqlockMemory {
+    state: Arc<MockSeqlockState>,
+    write_interval: Duration,
+    write_duration: Duration,
+    writer: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct MockSeqlockState {
+    sequence: AtomicU32,
+    buffer: Vec<AtomicU8>,
+    writes: AtomicU64,
+    stop: AtomicBool,
+}
+
+impl MockSeqlockMemory {
+    /// Create a buffer of `len` bytes with no writer running.
+    ///
+    /// Defaults to a write every millisecond with no pause mid-write.
+    pub fn new(len: usize) -> Self {
+        Self {
+            state: Arc::new(MockSeqlockState {
+                sequence: AtomicU32::new(0),
+                buffer: (0..len).map(|_| AtomicU8::new(b'a')).collect(),
+                writes: AtomicU64::new(0),
+                stop: AtomicBool::new(false),
+            }),
+            write_interval: Duration::from_millis(1),
+            write_duration: Duration::ZERO,
+            writer: None,
+        }
+    }
+
+    /// Set how long the writer waits between writes.
+    pub fn with_write_interval(mut self, interval: Duration) -> Self {
+        self.write_interval = interval;
+        self
+    }
+
+    /// Set how long each write holds the sequence number odd.
+    pub fn with_write_duration(mut self, duration: Duration) -> Self {
+        self.write_duration = duration;
+        self
+    }
+
+    /// Start the background writer.
+    ///
+    /// The first write happens after one `write_interval`.
+    pub fn start(mut self) -> Self {
+        if self.writer.is_none() {
+            let state = Arc::clone(&self.state);
+            let interval = self.write_interval;
+            let duration = self.write_duration;
+            self.writer = Some(std::thread::spawn(move || state.run_writer(interval, duration)));
+        }
+        self
+    }
+
+    /// Stop the background writer and wait for it to finish.
+    ///
+    /// A write in progress is completed first, so the sequence number is
+    /// even afterwards.
+    pub fn stop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            self.state.stop.store(true, Ordering::SeqCst);
+            writer.thread().unpark();
+            let _ = writer.join();
+        }
+    }
+
+    /// Number of writes completed so far.
+    pub fn write_count(&self) -> u64 {
+        self.state.writes.load(Ordering::SeqCst)
+    }
+
+    /// Reset the sequence number to zero, as a restarted writer would.
+    pub fn reset_sequence(&self) {
+        self.state.sequence.store(0, Ordering::SeqCst);
+    }
+}
+
+impl MockSeqlockState {
+    fn run_writer(&self, interval: Duration, duration: Duration) {
+        let half = self.buffer.len() / 2;
+        let mut letter = b'a';
+
+        while self.sleep_unless_stopped(interval) {
+            letter = if letter == b'z' { b'a' } else { letter + 1 };
+
+            self.sequence.fetch_add(1, Ordering::SeqCst);
+            for byte in &self.buffer[..half] {
+                byte.store(letter, Ordering::SeqCst);
+            }
+            if !duration.is_zero() {
+                std::thread::sleep(duration);
+            }
+            for byte in &self.buffer[half..] {
+                byte.store(letter, Ordering::SeqCst);
+            }
+            self.sequence.fetch_add(1, Ordering::SeqCst);
+
+            self.writes.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Wait out `interval`, returning `false` if asked to stop meanwhile.
+    fn sleep_unless_stopped(&self, interval: Duration) -> bool {
+        let deadline = Instant::now() + interval;
+        loop {
+            if self.stop.load(Ordering::SeqCst) {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            std::thread::park_timeout(deadline - now);
+        }
+    }
+}
+
+impl SeqlockMemory for MockSeqlockMemory {
+    fn raw_sequence_number(&self) -> u32 {
+        self.state.sequence.load(Ordering::SeqCst)
+    }
+
+    fn refresh(&mut self) -> IpcResult<()> {
+        Ok(())
+    }
+
+    fn grid_contents(&self) -> IpcResult<String> {
+        let bytes = self
+            .state
+            .buffer
+            .iter()
+            .map(|byte| byte.load(Ordering::SeqCst))
+            .collect();
+        String::from_utf8(bytes).map_err(|e| IpcError::InvalidData(e.to_string()))
+    }
+}
+
+impl Drop for MockSeqlockMemory {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,6 +696,18 @@ mod tests {
         assert_eq!(report.retry_percentage(), 5.0);
     }
 
+    #[test]
+    fn test_moved_backward() {
+        assert!(moved_backward(10, 4));
+        assert!(moved_backward(1000, 0));
+        assert!(!moved_backward(4, 10));
+        assert!(!moved_backward(10, 10));
+
+        // Wrapping past u32::MAX is still forward progress
+        assert!(!moved_backward(u32::MAX - 1, 2));
+        assert!(moved_backward(2, u32::MAX - 1));
+    }
+
     #[test]
     fn test_verifier_new() {
         let verifier = SeqlockVerifier::new();
@@ -427,6 +735,22 @@ mod tests {
         assert_eq!(verifier.last_seq.load(Ordering::SeqCst), 0);
     }
 
+    #[test]
+    fn test_verify_seqlock_pattern_on_fake_clock() {
+        let clock = crate::FakeClock::new();
+        let mut verifier = SeqlockVerifier::new().with_clock(clock.clone());
+        let mut shm = MockSeqlockMemory::new(16);
+
+        let real = Instant::now();
+        let report = verifier
+            .verify_seqlock_pattern(&mut shm, Duration::from_secs(60), Duration::from_secs(1))
+            .unwrap();
+
+        assert_eq!(report.total_reads, 60);
+        assert_eq!(clock.elapsed(), Duration::from_secs(60));
+        assert!(real.elapsed() < Duration::from_secs(5));
+    }
+
     #[test]
     fn test_report_access() {
         let verifier = SeqlockVerifier::new();
@@ -279,6 +279,8 @@ impl TerminalCapabilities {
 pub enum TerminalProfile {
     /// Basic VT100 terminal (monochrome, no mouse, minimal features)
     VT100,
+    /// Plain xterm (8 colors, `TERM=xterm`)
+    Xterm,
     /// xterm with 256 colors and basic mouse support
     Xterm256,
     /// Modern xterm with true color support
@@ -287,6 +289,8 @@ pub enum TerminalProfile {
     Screen,
     /// tmux terminal multiplexer (256 colors, limited mouse)
     Tmux,
+    /// Linux virtual console (8 colors, no mouse, no alternate screen)
+    Linux,
     /// Konsole KDE terminal emulator (true color, no Sixel)
     Konsole,
     /// GNOME Terminal (true color, limited features)
@@ -339,6 +343,22 @@ impl TerminalProfile {
                 term_name: "vt100".to_string(),
                 custom: HashMap::new(),
             },
+            Self::Xterm => TerminalCapabilities {
+                color_depth: ColorDepth::Colors8,
+                unicode_support: true,
+                wide_char_support: false,
+                mouse_protocol: MouseProtocol::VT200,
+                sixel_support: false,
+                iterm2_images: false,
+                kitty_graphics: false,
+                bracketed_paste: true,
+                synchronized_output: false,
+                alternate_screen: true,
+                set_title: true,
+                focus_events: true,
+                term_name: "xterm".to_string(),
+                custom: HashMap::new(),
+            },
             Self::Xterm256 => TerminalCapabilities {
                 color_depth: ColorDepth::Colors256,
                 unicode_support: true,
@@ -403,6 +423,22 @@ impl TerminalProfile {
                 term_name: "tmux-256color".to_string(),
                 custom: HashMap::new(),
             },
+            Self::Linux => TerminalCapabilities {
+                color_depth: ColorDepth::Colors8,
+                unicode_support: true,
+                wide_char_support: false,
+                mouse_protocol: MouseProtocol::None,
+                sixel_support: false,
+                iterm2_images: false,
+                kitty_graphics: false,
+                bracketed_paste: false,
+                synchronized_output: false,
+                alternate_screen: false,
+                set_title: false,
+                focus_events: false,
+                term_name: "linux".to_string(),
+                custom: HashMap::new(),
+            },
             Self::Konsole => TerminalCapabilities {
                 color_depth: ColorDepth::TrueColor,
                 unicode_support: true,
@@ -588,9 +624,11 @@ impl TerminalProfile {
     pub fn term_name(&self) -> &str {
         match self {
             Self::VT100 => "vt100",
+            Self::Xterm => "xterm",
             Self::Xterm256 | Self::XtermTrueColor => "xterm-256color",
             Self::Screen => "screen",
             Self::Tmux => "tmux-256color",
+            Self::Linux => "linux",
             Self::Konsole => "konsole-256color",
             Self::GnomeTerminal | Self::WindowsTerminal | Self::VSCode | Self::ITerm2 => {
                 "xterm-256color"
@@ -607,10 +645,12 @@ impl TerminalProfile {
     pub fn all() -> Vec<Self> {
         vec![
             Self::VT100,
+            Self::Xterm,
             Self::Xterm256,
             Self::XtermTrueColor,
             Self::Screen,
             Self::Tmux,
+            Self::Linux,
             Self::Konsole,
             Self::GnomeTerminal,
             Self::Alacritty,
@@ -642,10 +682,12 @@ impl TerminalProfile {
         let name_lower = name.to_lowercase();
         match name_lower.as_str() {
             "vt100" => Some(Self::VT100),
+            "xterm" => Some(Self::Xterm),
             "xterm-256color" | "xterm256" => Some(Self::Xterm256),
             "xterm-truecolor" | "xterm-direct" => Some(Self::XtermTrueColor),
             "screen" => Some(Self::Screen),
             "tmux" | "tmux-256color" => Some(Self::Tmux),
+            "linux" => Some(Self::Linux),
             "konsole" | "konsole-256color" => Some(Self::Konsole),
             "gnome" | "gnome-terminal" => Some(Self::GnomeTerminal),
             "alacritty" => Some(Self::Alacritty),
@@ -664,10 +706,12 @@ impl TerminalProfile {
     pub fn display_name(&self) -> &str {
         match self {
             Self::VT100 => "VT100",
+            Self::Xterm => "xterm",
             Self::Xterm256 => "xterm-256color",
             Self::XtermTrueColor => "xterm (true color)",
             Self::Screen => "GNU Screen",
             Self::Tmux => "tmux",
+            Self::Linux => "Linux console",
             Self::Konsole => "Konsole",
             Self::GnomeTerminal => "GNOME Terminal",
             Self::Alacritty => "Alacritty",
@@ -737,6 +781,8 @@ mod tests {
         assert_eq!(TerminalProfile::from_name("wezterm"), Some(TerminalProfile::WezTerm));
         assert_eq!(TerminalProfile::from_name("WEZTERM"), Some(TerminalProfile::WezTerm));
         assert_eq!(TerminalProfile::from_name("xterm-256color"), Some(TerminalProfile::Xterm256));
+        assert_eq!(TerminalProfile::from_name("xterm"), Some(TerminalProfile::Xterm));
+        assert_eq!(TerminalProfile::from_name("linux"), Some(TerminalProfile::Linux));
         assert_eq!(TerminalProfile::from_name("unknown"), None);
     }
 
@@ -745,6 +791,16 @@ mod tests {
         assert_eq!(TerminalProfile::WezTerm.term_name(), "wezterm");
         assert_eq!(TerminalProfile::Xterm256.term_name(), "xterm-256color");
         assert_eq!(TerminalProfile::VT100.term_name(), "vt100");
+        assert_eq!(TerminalProfile::Xterm.term_name(), "xterm");
+        assert_eq!(TerminalProfile::Linux.term_name(), "linux");
+
+        // Every profile's name maps back to a profile with the same TERM
+        for profile in TerminalProfile::all() {
+            let term = profile.term_name();
+            if let Some(found) = TerminalProfile::from_name(term) {
+                assert_eq!(found.term_name(), term);
+            }
+        }
     }
 
     #[test]
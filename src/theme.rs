@@ -7,6 +7,7 @@
 //! - Color palette definitions with support for popular themes
 //! - Extension traits for verifying colors at specific cell positions
 //! - Color scanning utilities for analyzing terminal output
+//! - xterm 256-color resolution and WCAG contrast helpers
 //!
 //! # Quick Start
 //!
@@ -43,8 +44,16 @@
 //! - **Dracula**: Popular dark theme with purple accents
 //! - **Nord**: Arctic, north-bluish color palette
 //! - **Monokai**: Classic syntax highlighting theme
-
-use crate::ipc::{CellAttributes, IpcError, IpcResult};
+//! - **Solarized Dark / Light**: Low-contrast precision colors
+//! - **Gruvbox Dark**: Retro groove with warm tones
+//! - **Tokyo Night**: Dark theme with neon blue accents
+//! - **Catppuccin Mocha**: Soothing pastel dark theme
+
+use crate::ipc::{CellAttributes, ColorLayer, IpcError, IpcResult};
+use crate::regions::{RegionBounds, UiRegionTestExt, UiRegionTester};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
 use std::time::Duration;
 
 /// Standard ANSI color indices.
@@ -118,6 +127,46 @@ impl AnsiColor {
     }
 }
 
+/// A color slot in a [`ColorPalette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaletteSlot {
+    /// One of the 16 ANSI colors.
+    Ansi(AnsiColor),
+    /// Default background color.
+    Background,
+    /// Default foreground color.
+    Foreground,
+    /// Cursor color.
+    Cursor,
+    /// Selection highlight color.
+    Selection,
+}
+
+impl fmt::Display for PaletteSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaletteSlot::Ansi(color) => write!(f, "{:?}", color),
+            PaletteSlot::Background => f.write_str("Background"),
+            PaletteSlot::Foreground => f.write_str("Foreground"),
+            PaletteSlot::Cursor => f.write_str("Cursor"),
+            PaletteSlot::Selection => f.write_str("Selection"),
+        }
+    }
+}
+
+/// A slot whose color differs between two palettes.
+///
+/// Produced by [`ColorPalette::diff`]. Colors are RGBA (`0xRRGGBBAA`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteDiff {
+    /// The slot that changed.
+    pub slot: PaletteSlot,
+    /// Color in the palette `diff` was called on.
+    pub old: u32,
+    /// Color in the palette passed to `diff`.
+    pub new: u32,
+}
+
 /// Color palette definition.
 ///
 /// Stores the 16 ANSI colors plus special terminal colors
@@ -284,18 +333,195 @@ impl ColorPalette {
         }
     }
 
+    /// Create the "solarized-dark" theme palette.
+    ///
+    /// Ethan Schoonover's precision colors on the dark base03 background.
+    /// Source: https://ethanschoonover.com/solarized
+    pub fn solarized_dark() -> Self {
+        Self {
+            name: "solarized-dark".to_string(),
+            colors: [
+                0x073642FF, // Black (base02)
+                0xDC322FFF, // Red
+                0x859900FF, // Green
+                0xB58900FF, // Yellow
+                0x268BD2FF, // Blue
+                0xD33682FF, // Magenta
+                0x2AA198FF, // Cyan
+                0xEEE8D5FF, // White (base2)
+                0x002B36FF, // Bright Black (base03)
+                0xCB4B16FF, // Bright Red (orange)
+                0x586E75FF, // Bright Green (base01)
+                0x657B83FF, // Bright Yellow (base00)
+                0x839496FF, // Bright Blue (base0)
+                0x6C71C4FF, // Bright Magenta (violet)
+                0x93A1A1FF, // Bright Cyan (base1)
+                0xFDF6E3FF, // Bright White (base3)
+            ],
+            background: 0x002B36FF,
+            foreground: 0x839496FF,
+            cursor: 0x93A1A1FF,
+            selection: 0x073642FF,
+        }
+    }
+
+    /// Create the "solarized-light" theme palette.
+    ///
+    /// Same 16 accent colors as Solarized Dark, on the light base3 background.
+    /// Source: https://ethanschoonover.com/solarized
+    pub fn solarized_light() -> Self {
+        Self {
+            name: "solarized-light".to_string(),
+            colors: [
+                0x073642FF, // Black (base02)
+                0xDC322FFF, // Red
+                0x859900FF, // Green
+                0xB58900FF, // Yellow
+                0x268BD2FF, // Blue
+                0xD33682FF, // Magenta
+                0x2AA198FF, // Cyan
+                0xEEE8D5FF, // White (base2)
+                0x002B36FF, // Bright Black (base03)
+                0xCB4B16FF, // Bright Red (orange)
+                0x586E75FF, // Bright Green (base01)
+                0x657B83FF, // Bright Yellow (base00)
+                0x839496FF, // Bright Blue (base0)
+                0x6C71C4FF, // Bright Magenta (violet)
+                0x93A1A1FF, // Bright Cyan (base1)
+                0xFDF6E3FF, // Bright White (base3)
+            ],
+            background: 0xFDF6E3FF,
+            foreground: 0x657B83FF,
+            cursor: 0x586E75FF,
+            selection: 0xEEE8D5FF,
+        }
+    }
+
+    /// Create the "gruvbox-dark" theme palette.
+    ///
+    /// Retro groove theme with warm, earthy tones.
+    /// Source: https://github.com/morhetz/gruvbox
+    pub fn gruvbox_dark() -> Self {
+        Self {
+            name: "gruvbox-dark".to_string(),
+            colors: [
+                0x282828FF, // Black
+                0xCC241DFF, // Red
+                0x98971AFF, // Green
+                0xD79921FF, // Yellow
+                0x458588FF, // Blue
+                0xB16286FF, // Magenta
+                0x689D6AFF, // Cyan
+                0xA89984FF, // White
+                0x928374FF, // Bright Black
+                0xFB4934FF, // Bright Red
+                0xB8BB26FF, // Bright Green
+                0xFABD2FFF, // Bright Yellow
+                0x83A598FF, // Bright Blue
+                0xD3869BFF, // Bright Magenta
+                0x8EC07CFF, // Bright Cyan
+                0xEBDBB2FF, // Bright White
+            ],
+            background: 0x282828FF,
+            foreground: 0xEBDBB2FF,
+            cursor: 0xEBDBB2FF,
+            selection: 0x504945FF,
+        }
+    }
+
+    /// Create the "tokyo-night" theme palette.
+    ///
+    /// Dark theme inspired by the lights of downtown Tokyo at night.
+    /// Source: https://github.com/folke/tokyonight.nvim
+    pub fn tokyo_night() -> Self {
+        Self {
+            name: "tokyo-night".to_string(),
+            colors: [
+                0x15161EFF, // Black
+                0xF7768EFF, // Red
+                0x9ECE6AFF, // Green
+                0xE0AF68FF, // Yellow
+                0x7AA2F7FF, // Blue
+                0xBB9AF7FF, // Magenta
+                0x7DCFFFFF, // Cyan
+                0xA9B1D6FF, // White
+                0x414868FF, // Bright Black
+                0xF7768EFF, // Bright Red
+                0x9ECE6AFF, // Bright Green
+                0xE0AF68FF, // Bright Yellow
+                0x7AA2F7FF, // Bright Blue
+                0xBB9AF7FF, // Bright Magenta
+                0x7DCFFFFF, // Bright Cyan
+                0xC0CAF5FF, // Bright White
+            ],
+            background: 0x1A1B26FF,
+            foreground: 0xC0CAF5FF,
+            cursor: 0xC0CAF5FF,
+            selection: 0x283457FF,
+        }
+    }
+
+    /// Create the "catppuccin-mocha" theme palette.
+    ///
+    /// The darkest Catppuccin flavor with soothing pastel accents.
+    /// Source: https://catppuccin.com
+    pub fn catppuccin_mocha() -> Self {
+        Self {
+            name: "catppuccin-mocha".to_string(),
+            colors: [
+                0x45475AFF, // Black (surface1)
+                0xF38BA8FF, // Red
+                0xA6E3A1FF, // Green
+                0xF9E2AFFF, // Yellow
+                0x89B4FAFF, // Blue
+                0xF5C2E7FF, // Magenta (pink)
+                0x94E2D5FF, // Cyan (teal)
+                0xBAC2DEFF, // White (subtext1)
+                0x585B70FF, // Bright Black (surface2)
+                0xF38BA8FF, // Bright Red
+                0xA6E3A1FF, // Bright Green
+                0xF9E2AFFF, // Bright Yellow
+                0x89B4FAFF, // Bright Blue
+                0xF5C2E7FF, // Bright Magenta (pink)
+                0x94E2D5FF, // Bright Cyan (teal)
+                0xA6ADC8FF, // Bright White (subtext0)
+            ],
+            background: 0x1E1E2EFF,
+            foreground: 0xCDD6F4FF,
+            cursor: 0xF5E0DCFF,
+            selection: 0x585B70FF,
+        }
+    }
+
     /// Get the color for an ANSI color index.
     pub fn ansi_color(&self, color: AnsiColor) -> u32 {
         self.colors[color.as_index() as usize]
     }
 
+    /// Resolve an xterm 256-color index (as used by `ESC[38;5;Nm`) to RGBA.
+    ///
+    /// Indices 0-15 come from this palette's own colors; 16-255 use the
+    /// standard 6x6x6 color cube and 24-step gray ramp (see [`rgb_from_256`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::theme::ColorPalette;
+    ///
+    /// let palette = ColorPalette::dracula();
+    /// assert_eq!(palette.xterm_256(1), 0xFF5555FF); // palette red
+    /// assert_eq!(palette.xterm_256(196), 0xFF0000FF); // cube red
+    /// ```
+    pub fn xterm_256(&self, index: u8) -> u32 {
+        rgb_from_256(index).unwrap_or_else(|| self.colors[index as usize])
+    }
+
     /// Check if a color matches an ANSI color in this palette.
     ///
     /// Compares the RGB channels (ignoring alpha) for an exact match.
     pub fn matches_ansi(&self, rgba: u32, color: AnsiColor) -> bool {
         let expected = self.ansi_color(color);
-        // Compare RGB only (mask out alpha)
-        (rgba & 0xFFFFFF00) == (expected & 0xFFFFFF00)
+        same_rgb(rgba, expected)
     }
 
     /// Check if a color matches an ANSI color with tolerance.
@@ -316,16 +542,480 @@ impl ColorPalette {
         let tol = tolerance as i32;
         (r1 - r2).abs() <= tol && (g1 - g2).abs() <= tol && (b1 - b2).abs() <= tol
     }
+
+    /// Find the ANSI color in this palette closest to an RGBA value.
+    ///
+    /// Distance is the squared Euclidean distance in RGB space (alpha is
+    /// ignored), so an exact match returns a distance of 0. Ties resolve to
+    /// the lower ANSI index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::theme::{AnsiColor, ColorPalette};
+    ///
+    /// let palette = ColorPalette::dracula();
+    /// // Dracula red (0xFF5555) darkened slightly by blending
+    /// let (color, distance) = palette.nearest_ansi(0xF85252FF);
+    /// assert_eq!(color, AnsiColor::Red);
+    /// assert_eq!(distance, 7 * 7 + 3 * 3 + 3 * 3);
+    /// ```
+    pub fn nearest_ansi(&self, rgba: u32) -> (AnsiColor, u32) {
+        let channel = |value: u32, shift: u32| ((value >> shift) & 0xFF) as i32;
+
+        let mut best = (AnsiColor::Black, u32::MAX);
+        for (index, &candidate) in self.colors.iter().enumerate() {
+            let distance = [24, 16, 8]
+                .iter()
+                .map(|&shift| {
+                    let delta = channel(rgba, shift) - channel(candidate, shift);
+                    (delta * delta) as u32
+                })
+                .sum::<u32>();
+
+            if distance < best.1 {
+                if let Some(color) = AnsiColor::from_index(index as u8) {
+                    best = (color, distance);
+                }
+            }
+        }
+        best
+    }
+
+    /// List the slots whose colors differ from `other`.
+    ///
+    /// Compares the 16 ANSI colors in index order, then background,
+    /// foreground, cursor and selection. Colors must match exactly,
+    /// including alpha. The palette names are not compared.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::theme::{AnsiColor, ColorPalette, PaletteSlot};
+    ///
+    /// let old = ColorPalette::slime();
+    /// let mut new = old.clone();
+    /// new.cursor = 0xFFFFFFFF;
+    ///
+    /// let diffs = old.diff(&new);
+    /// assert_eq!(diffs.len(), 1);
+    /// assert_eq!(diffs[0].slot, PaletteSlot::Cursor);
+    /// assert_eq!((diffs[0].old, diffs[0].new), (0x52FFB8FF, 0xFFFFFFFF));
+    /// ```
+    pub fn diff(&self, other: &ColorPalette) -> Vec<PaletteDiff> {
+        let ansi = self
+            .colors
+            .iter()
+            .zip(&other.colors)
+            .enumerate()
+            .filter_map(|(index, (&old, &new))| {
+                AnsiColor::from_index(index as u8).map(|color| (PaletteSlot::Ansi(color), old, new))
+            });
+        let special = [
+            (PaletteSlot::Background, self.background, other.background),
+            (PaletteSlot::Foreground, self.foreground, other.foreground),
+            (PaletteSlot::Cursor, self.cursor, other.cursor),
+            (PaletteSlot::Selection, self.selection, other.selection),
+        ];
+
+        ansi.chain(special)
+            .filter(|&(_, old, new)| old != new)
+            .map(|(slot, old, new)| PaletteDiff { slot, old, new })
+            .collect()
+    }
+
+    /// Assert that this palette has the same colors as `expected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::InvalidData`] listing every slot that differs,
+    /// one per line with the expected and actual colors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::theme::ColorPalette;
+    ///
+    /// let expected = ColorPalette::dracula();
+    /// let mut actual = expected.clone();
+    /// assert!(actual.assert_eq_verbose(&expected).is_ok());
+    ///
+    /// actual.background = 0x000000FF;
+    /// let err = actual.assert_eq_verbose(&expected).unwrap_err();
+    /// assert!(err.to_string().contains("Background: expected 0x282A36FF, got 0x000000FF"));
+    /// ```
+    pub fn assert_eq_verbose(&self, expected: &ColorPalette) -> IpcResult<()> {
+        let diffs = expected.diff(self);
+        if diffs.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = format!(
+            "Palette '{}' differs from expected '{}' in {} slot(s):",
+            self.name,
+            expected.name,
+            diffs.len()
+        );
+        for diff in &diffs {
+            message.push_str(&format!(
+                "\n  {}: expected 0x{:08X}, got 0x{:08X}",
+                diff.slot, diff.old, diff.new
+            ));
+        }
+        Err(IpcError::InvalidData(message))
+    }
+
+    /// Load a palette from an iTerm2 `.itermcolors` file.
+    ///
+    /// Reads the plist XML and maps `Ansi 0 Color` through `Ansi 15 Color`
+    /// plus `Background Color`, `Foreground Color`, `Cursor Color` and
+    /// `Selection Color` from their 0.0-1.0 float components to RGBA.
+    /// Alpha defaults to 0xFF unless the entry has an `Alpha Component`.
+    /// A missing cursor falls back to the foreground color, and a missing
+    /// selection to the default selection color.
+    ///
+    /// The palette name is taken from the file stem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if any ANSI color,
+    /// the background, or the foreground is missing or malformed.
+    pub fn from_itermcolors(path: impl AsRef<Path>) -> IpcResult<Self> {
+        let path = path.as_ref();
+        let xml = std::fs::read_to_string(path)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "itermcolors".to_string());
+        Self::parse_itermcolors(name, &xml)
+    }
+
+    fn parse_itermcolors(name: String, xml: &str) -> IpcResult<Self> {
+        let entry = |key: &str| -> IpcResult<Option<u32>> {
+            let open = format!("<key>{}</key>", key);
+            let Some(start) = xml.find(&open) else {
+                return Ok(None);
+            };
+            let rest = &xml[start + open.len()..];
+            let body = rest
+                .trim_start()
+                .strip_prefix("<dict>")
+                .and_then(|dict| dict.find("</dict>").map(|end| &dict[..end]))
+                .ok_or_else(|| {
+                    IpcError::InvalidData(format!("'{}' is not a color dictionary", key))
+                })?;
+            itermcolors_rgba(body)
+                .map(Some)
+                .ok_or_else(|| IpcError::InvalidData(format!("'{}' has invalid components", key)))
+        };
+        let required = |key: &str| -> IpcResult<u32> {
+            entry(key)?.ok_or_else(|| IpcError::InvalidData(format!("Missing '{}'", key)))
+        };
+
+        let mut colors = [0u32; 16];
+        for (index, slot) in colors.iter_mut().enumerate() {
+            *slot = required(&format!("Ansi {} Color", index))?;
+        }
+
+        let foreground = required("Foreground Color")?;
+        Ok(Self {
+            name,
+            colors,
+            background: required("Background Color")?,
+            foreground,
+            cursor: entry("Cursor Color")?.unwrap_or(foreground),
+            selection: entry("Selection Color")?.unwrap_or(0x444444FF),
+        })
+    }
+}
+
+/// Convert the body of an `.itermcolors` color dictionary to RGBA.
+///
+/// Returns `None` if a red, green or blue component is missing or not a number.
+fn itermcolors_rgba(dict: &str) -> Option<u32> {
+    let component = |key: &str| -> Option<f64> {
+        let open = format!("<key>{} Component</key>", key);
+        let rest = dict[dict.find(&open)? + open.len()..].trim_start();
+        let (tag, rest) = rest.strip_prefix('<')?.split_once('>')?;
+        let value = &rest[..rest.find("</")?];
+        if tag != "real" && tag != "integer" {
+            return None;
+        }
+        value.trim().parse().ok()
+    };
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u32;
+
+    let r = channel(component("Red")?);
+    let g = channel(component("Green")?);
+    let b = channel(component("Blue")?);
+    let a = component("Alpha").map(channel).unwrap_or(0xFF);
+    Some((r << 24) | (g << 16) | (b << 8) | a)
+}
+
+/// Serialized palette layout shared by the JSON and TOML loaders.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PaletteFile {
+    name: String,
+    colors: Vec<ColorValue>,
+    background: ColorValue,
+    foreground: ColorValue,
+    cursor: ColorValue,
+    selection: ColorValue,
+}
+
+/// A color as written in a palette file: either a raw `0xRRGGBBAA`
+/// integer or a `"#RRGGBB"` / `"#RRGGBBAA"` hex string.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum ColorValue {
+    Int(u32),
+    Hex(String),
+}
+
+#[cfg(feature = "serde")]
+impl ColorValue {
+    fn to_rgba(&self) -> IpcResult<u32> {
+        match self {
+            ColorValue::Int(value) => Ok(*value),
+            ColorValue::Hex(text) => {
+                let hex = text.strip_prefix('#').unwrap_or(text);
+                let parsed = u32::from_str_radix(hex, 16)
+                    .map_err(|_| IpcError::InvalidData(format!("Invalid color value '{}'", text)));
+                match hex.len() {
+                    6 => Ok((parsed? << 8) | 0xFF),
+                    8 => parsed,
+                    _ => Err(IpcError::InvalidData(format!(
+                        "Invalid color value '{}': expected #RRGGBB or #RRGGBBAA",
+                        text
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ColorPalette {
+    /// Load a palette from a TOML document.
+    ///
+    /// The document must contain `name`, a `colors` array of exactly 16
+    /// entries, and `background`, `foreground`, `cursor` and `selection`.
+    /// Colors may be `0xRRGGBBAA` integers or `"#RRGGBB"` strings (alpha
+    /// defaults to `0xFF`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::theme::ColorPalette;
+    ///
+    /// let toml = r##"
+    /// name = "custom"
+    /// colors = [
+    ///     "#000000", "#FF0000", "#00FF00", "#FFFF00",
+    ///     "#0000FF", "#FF00FF", "#00FFFF", "#C0C0C0",
+    ///     "#808080", "#FF8080", "#80FF80", "#FFFF80",
+    ///     "#8080FF", "#FF80FF", "#80FFFF", "#FFFFFF",
+    /// ]
+    /// background = 0x101010FF
+    /// foreground = "#E0E0E0"
+    /// cursor = "#FFFFFF"
+    /// selection = "#444444"
+    /// "##;
+    ///
+    /// let palette = ColorPalette::from_toml_str(toml).unwrap();
+    /// assert_eq!(palette.colors[1], 0xFF0000FF);
+    /// assert_eq!(palette.background, 0x101010FF);
+    /// ```
+    pub fn from_toml_str(input: &str) -> IpcResult<Self> {
+        let file: PaletteFile = toml::from_str(input)
+            .map_err(|e| IpcError::InvalidData(format!("Invalid palette TOML: {}", e)))?;
+        Self::from_palette_file(file)
+    }
+
+    /// Load a palette from a JSON document.
+    ///
+    /// Accepts the same structure as [`ColorPalette::from_toml_str`].
+    pub fn from_json_str(input: &str) -> IpcResult<Self> {
+        let file: PaletteFile = serde_json::from_str(input)
+            .map_err(|e| IpcError::InvalidData(format!("Invalid palette JSON: {}", e)))?;
+        Self::from_palette_file(file)
+    }
+
+    /// Serialize the palette to TOML.
+    ///
+    /// Colors are written as `"#RRGGBBAA"` strings so the output can be
+    /// read back with [`ColorPalette::from_toml_str`] without loss.
+    pub fn to_toml_string(&self) -> IpcResult<String> {
+        let hex = |rgba: u32| ColorValue::Hex(format!("#{:08X}", rgba));
+        let file = PaletteFile {
+            name: self.name.clone(),
+            colors: self.colors.iter().map(|&c| hex(c)).collect(),
+            background: hex(self.background),
+            foreground: hex(self.foreground),
+            cursor: hex(self.cursor),
+            selection: hex(self.selection),
+        };
+        toml::to_string(&file)
+            .map_err(|e| IpcError::InvalidData(format!("Failed to serialize palette: {}", e)))
+    }
+
+    fn from_palette_file(file: PaletteFile) -> IpcResult<Self> {
+        if file.colors.len() != 16 {
+            return Err(IpcError::InvalidData(format!(
+                "Palette '{}' must define exactly 16 colors, found {}",
+                file.name,
+                file.colors.len()
+            )));
+        }
+
+        let mut colors = [0u32; 16];
+        for (slot, value) in colors.iter_mut().zip(&file.colors) {
+            *slot = value.to_rgba()?;
+        }
+
+        Ok(Self {
+            name: file.name,
+            colors,
+            background: file.background.to_rgba()?,
+            foreground: file.foreground.to_rgba()?,
+            cursor: file.cursor.to_rgba()?,
+            selection: file.selection.to_rgba()?,
+        })
+    }
+}
+
+/// Convert an xterm 256-color index in the color cube or gray ramp to RGBA.
+///
+/// Indices 16-231 map to the 6x6x6 cube (channel levels 0, 95, 135, 175,
+/// 215, 255) and 232-255 to the gray ramp (8 to 238 in steps of 10).
+/// Returns `None` for 0-15, whose colors are defined by the terminal theme;
+/// use [`ColorPalette::xterm_256`] to resolve those.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::rgb_from_256;
+///
+/// assert_eq!(rgb_from_256(21), Some(0x0000FFFF));
+/// assert_eq!(rgb_from_256(232), Some(0x080808FF));
+/// assert_eq!(rgb_from_256(7), None);
+/// ```
+pub fn rgb_from_256(index: u8) -> Option<u32> {
+    const CUBE_LEVELS: [u32; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => None,
+        16..=231 => {
+            let i = (index - 16) as usize;
+            let r = CUBE_LEVELS[i / 36];
+            let g = CUBE_LEVELS[(i / 6) % 6];
+            let b = CUBE_LEVELS[i % 6];
+            Some((r << 24) | (g << 16) | (b << 8) | 0xFF)
+        }
+        232..=255 => {
+            let level = 8 + 10 * (index - 232) as u32;
+            Some((level << 24) | (level << 16) | (level << 8) | 0xFF)
+        }
+    }
+}
+
+/// Build an opaque RGBA color from red, green and blue channels.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::rgb;
+///
+/// assert_eq!(rgb(0xFF, 0x55, 0x55), 0xFF5555FF);
+/// ```
+pub const fn rgb(r: u8, g: u8, b: u8) -> u32 {
+    rgba(r, g, b, 0xFF)
 }
 
+/// Build an RGBA color (`0xRRGGBBAA`) from its four channels.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::rgba;
+///
+/// assert_eq!(rgba(0x12, 0x34, 0x56, 0x78), 0x12345678);
+/// ```
+pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> u32 {
+    (r as u32) << 24 | (g as u32) << 16 | (b as u32) << 8 | a as u32
+}
+
+/// Split an RGBA color into its (red, green, blue, alpha) channels.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::rgba_components;
+///
+/// assert_eq!(rgba_components(0x12345678), (0x12, 0x34, 0x56, 0x78));
+/// ```
+pub const fn rgba_components(rgba: u32) -> (u8, u8, u8, u8) {
+    ((rgba >> 24) as u8, (rgba >> 16) as u8, (rgba >> 8) as u8, rgba as u8)
+}
+
+/// Whether two RGBA colors have the same RGB channels, ignoring alpha.
+const fn same_rgb(a: u32, b: u32) -> bool {
+    (a & 0xFFFFFF00) == (b & 0xFFFFFF00)
+}
+
+/// Compute the WCAG 2.x contrast ratio between two RGBA colors.
+///
+/// Uses the relative-luminance formula from the WCAG specification and
+/// returns a value between 1.0 (no contrast) and 21.0 (black on white).
+/// The order of the arguments does not matter and alpha is ignored.
+///
+/// WCAG AA requires at least 4.5:1 for normal text and 3:1 for large text.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::contrast_ratio;
+///
+/// let ratio = contrast_ratio(0x000000FF, 0xFFFFFFFF);
+/// assert!((ratio - 21.0).abs() < 0.01);
+/// ```
+pub fn contrast_ratio(fg: u32, bg: u32) -> f64 {
+    let l1 = relative_luminance(fg);
+    let l2 = relative_luminance(bg);
+    let (lighter, darker) = if l1 >= l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// WCAG relative luminance of an RGBA color (0.0 for black, 1.0 for white).
+fn relative_luminance(rgba: u32) -> f64 {
+    let linear = |shift: u32| {
+        let c = ((rgba >> shift) & 0xFF) as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(24) + 0.7152 * linear(16) + 0.0722 * linear(8)
+}
 
 /// Result of scanning colors in a region.
+///
+/// Records each unique color in first-seen order along with how many
+/// scanned cells used it, so tests can assert on the dominant colors.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColorScan {
     /// Unique foreground colors found (RGBA format).
     pub foreground_colors: Vec<u32>,
     /// Unique background colors found (RGBA format).
     pub background_colors: Vec<u32>,
+    /// Number of cells using each foreground color.
+    pub foreground_counts: HashMap<u32, usize>,
+    /// Number of cells using each background color.
+    pub background_counts: HashMap<u32, usize>,
     /// Total cells scanned.
     pub cells_scanned: usize,
 }
@@ -336,18 +1026,22 @@ impl ColorScan {
         Self::default()
     }
 
-    /// Add a foreground color to the scan if not already present.
+    /// Record a foreground color, adding it to the unique set if new.
     pub fn add_foreground(&mut self, color: u32) {
-        if !self.foreground_colors.contains(&color) {
+        let count = self.foreground_counts.entry(color).or_insert(0);
+        if *count == 0 {
             self.foreground_colors.push(color);
         }
+        *count += 1;
     }
 
-    /// Add a background color to the scan if not already present.
+    /// Record a background color, adding it to the unique set if new.
     pub fn add_background(&mut self, color: u32) {
-        if !self.background_colors.contains(&color) {
+        let count = self.background_counts.entry(color).or_insert(0);
+        if *count == 0 {
             self.background_colors.push(color);
         }
+        *count += 1;
     }
 
     /// Increment the cell counter.
@@ -364,6 +1058,291 @@ impl ColorScan {
     pub fn unique_background_count(&self) -> usize {
         self.background_colors.len()
     }
+
+    /// Get how many times a color was recorded, as `(foreground, background)`.
+    ///
+    /// Returns `(0, 0)` for colors that never appeared.
+    pub fn color_frequency(&self, color: u32) -> (usize, usize) {
+        (
+            self.foreground_counts.get(&color).copied().unwrap_or(0),
+            self.background_counts.get(&color).copied().unwrap_or(0),
+        )
+    }
+
+    /// Get the most frequent foreground color.
+    ///
+    /// Ties resolve to the color seen first. Returns `None` if no
+    /// foreground colors were recorded.
+    pub fn most_common_foreground(&self) -> Option<u32> {
+        Self::most_common(&self.foreground_colors, &self.foreground_counts)
+    }
+
+    /// Get the most frequent background color.
+    ///
+    /// Ties resolve to the color seen first. Returns `None` if no
+    /// background colors were recorded.
+    pub fn most_common_background(&self) -> Option<u32> {
+        Self::most_common(&self.background_colors, &self.background_counts)
+    }
+
+    fn most_common(colors: &[u32], counts: &HashMap<u32, usize>) -> Option<u32> {
+        colors
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|color| counts.get(color).copied().unwrap_or(0))
+    }
+}
+
+/// Per-channel tolerance [`detect_palette`] allows when matching colors.
+const DETECT_PALETTE_TOLERANCE: u8 = 4;
+
+/// Guess which palette produced the colors in a scan.
+///
+/// Each candidate is scored by the share of scanned cell colors, foreground
+/// and background alike, that match one of its 16 ANSI entries within a
+/// small per-channel tolerance. Returns the best candidate's name and its
+/// score between 0.0 and 1.0, or `None` if the scan is empty or no
+/// candidate matches any color. Ties resolve to the earlier candidate.
+///
+/// # Example
+///
+/// ```rust
+/// use terminal_testlib::theme::{detect_palette, ColorPalette, ColorScan};
+///
+/// let dracula = ColorPalette::dracula();
+/// let mut scan = ColorScan::new();
+/// for &color in &dracula.colors {
+///     scan.add_foreground(color);
+///     scan.add_background(dracula.colors[0]);
+///     scan.increment_cells();
+/// }
+///
+/// let candidates = [ColorPalette::nord(), dracula, ColorPalette::monokai()];
+/// let (name, confidence) = detect_palette(&scan, &candidates).unwrap();
+/// assert_eq!(name, "dracula");
+/// assert_eq!(confidence, 1.0);
+/// ```
+pub fn detect_palette(scan: &ColorScan, candidates: &[ColorPalette]) -> Option<(String, f64)> {
+    let observed: Vec<(u32, usize)> = scan
+        .foreground_counts
+        .iter()
+        .chain(&scan.background_counts)
+        .map(|(&color, &count)| (color, count))
+        .collect();
+    let total: usize = observed.iter().map(|&(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut best: Option<(&ColorPalette, usize)> = None;
+    for palette in candidates {
+        let matched: usize = observed
+            .iter()
+            .filter(|&&(color, _)| {
+                (0..16)
+                    .filter_map(AnsiColor::from_index)
+                    .any(|ansi| palette.matches_ansi_approx(color, ansi, DETECT_PALETTE_TOLERANCE))
+            })
+            .map(|&(_, count)| count)
+            .sum();
+
+        let is_better = match best {
+            Some((_, best_matched)) => matched > best_matched,
+            None => matched > 0,
+        };
+        if is_better {
+            best = Some((palette, matched));
+        }
+    }
+
+    best.map(|(palette, matched)| (palette.name.clone(), matched as f64 / total as f64))
+}
+
+/// Glyph and colors of one cell captured by [`ColorDiagnostics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellColors {
+    /// Screen row (0-indexed).
+    pub row: u16,
+    /// Screen column (0-indexed).
+    pub col: u16,
+    /// Character displayed in the cell.
+    pub glyph: char,
+    /// Foreground color as RGBA.
+    pub fg: u32,
+    /// Background color as RGBA.
+    pub bg: u32,
+}
+
+/// Text and per-cell colors of a named region, checked against a palette.
+///
+/// Intended as a single artifact to attach when a color assertion fails in
+/// CI: [`to_markdown`](Self::to_markdown) renders the region text, a summary
+/// of the colors used, and each cell's glyph with its foreground, background
+/// and nearest ANSI color in the palette.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "scarab")]
+/// # {
+/// use terminal_testlib::{
+///     regions::UiRegionTester,
+///     scarab::ScarabTestHarness,
+///     theme::{ColorDiagnostics, ColorPalette},
+/// };
+///
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let harness = ScarabTestHarness::connect()?;
+/// let tester = UiRegionTester::new(80, 24).with_status_bar(1);
+///
+/// let palette = ColorPalette::slime();
+/// let report = ColorDiagnostics::capture(&harness, &tester, "status_bar", &palette)?;
+/// std::fs::write("target/status_bar_colors.md", report.to_markdown())?;
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColorDiagnostics {
+    /// Name of the region.
+    pub region: String,
+    /// Screen bounds of the region.
+    pub bounds: RegionBounds,
+    /// Palette used to name colors.
+    pub palette: ColorPalette,
+    /// Text of the region, one line per row.
+    pub lines: Vec<String>,
+    /// Every cell in the region, row by row.
+    pub cells: Vec<CellColors>,
+    /// Colors used across the region.
+    pub scan: ColorScan,
+}
+
+impl ColorDiagnostics {
+    /// Capture the text and colors of a region.
+    ///
+    /// Cells outside the grid are skipped, as in
+    /// [`ThemeTestExt::scan_colors_in_region`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::RegionNotFound`] if the tester has no region named
+    /// `region_name`, or an error if the grid cannot be read.
+    pub fn capture<H>(
+        harness: &H,
+        tester: &UiRegionTester,
+        region_name: &str,
+        palette: &ColorPalette,
+    ) -> IpcResult<Self>
+    where
+        H: ThemeTestExt + UiRegionTestExt,
+    {
+        let bounds = tester
+            .region_bounds(region_name)
+            .ok_or_else(|| IpcError::RegionNotFound(region_name.to_string()))?;
+        let lines = harness.region_lines(tester, region_name)?;
+
+        let mut cells = Vec::new();
+        let mut scan = ColorScan::new();
+        for (row, line) in (bounds.row..).zip(&lines) {
+            for (col, glyph) in (bounds.col..).zip(line.chars()) {
+                let Ok(attrs) = harness.cell_attrs_at(row, col) else {
+                    continue;
+                };
+                scan.add_foreground(attrs.fg);
+                scan.add_background(attrs.bg);
+                scan.increment_cells();
+                cells.push(CellColors {
+                    row,
+                    col,
+                    glyph,
+                    fg: attrs.fg,
+                    bg: attrs.bg,
+                });
+            }
+        }
+
+        Ok(Self {
+            region: region_name.to_string(),
+            bounds,
+            palette: palette.clone(),
+            lines,
+            cells,
+            scan,
+        })
+    }
+
+    /// Render the report as Markdown.
+    pub fn to_markdown(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Color report: region `{}` (palette `{}`)\n",
+            self.region, self.palette.name
+        );
+        let _ = writeln!(
+            out,
+            "Bounds: row {}, col {}, {}x{}\n",
+            self.bounds.row, self.bounds.col, self.bounds.width, self.bounds.height
+        );
+
+        let _ = writeln!(out, "## Text\n\n```text");
+        for line in &self.lines {
+            let _ = writeln!(out, "{}", line);
+        }
+        let _ = writeln!(out, "```\n");
+
+        let _ = writeln!(out, "## Colors\n");
+        let _ = writeln!(out, "| Color | Foreground cells | Background cells | Nearest ANSI |");
+        let _ = writeln!(out, "| --- | --- | --- | --- |");
+        let mut colors = self.scan.foreground_colors.clone();
+        for &color in &self.scan.background_colors {
+            if !colors.contains(&color) {
+                colors.push(color);
+            }
+        }
+        for color in colors {
+            let (fg_count, bg_count) = self.scan.color_frequency(color);
+            let _ = writeln!(
+                out,
+                "| `0x{:08X}` | {} | {} | {} |",
+                color,
+                fg_count,
+                bg_count,
+                self.nearest_label(color)
+            );
+        }
+
+        let _ = writeln!(out, "\n## Cells\n");
+        let _ = writeln!(out, "| Row | Col | Glyph | Foreground | Background |");
+        let _ = writeln!(out, "| --- | --- | --- | --- | --- |");
+        for cell in &self.cells {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | `0x{:08X}` {} | `0x{:08X}` {} |",
+                cell.row,
+                cell.col,
+                format!("{:?}", cell.glyph).replace('|', "\\|"),
+                cell.fg,
+                self.nearest_label(cell.fg),
+                cell.bg,
+                self.nearest_label(cell.bg)
+            );
+        }
+
+        out
+    }
+
+    /// Name the palette's nearest ANSI color, with the distance if inexact.
+    fn nearest_label(&self, color: u32) -> String {
+        match self.palette.nearest_ansi(color) {
+            (ansi, 0) => format!("{:?}", ansi),
+            (ansi, distance) => format!("{:?} (distance {})", ansi, distance),
+        }
+    }
 }
 
 /// Extension trait for theme verification.
@@ -390,25 +1369,67 @@ pub trait ThemeTestExt {
     /// Verify the terminal's current background matches expected.
     fn assert_background_color(&self, row: u16, col: u16, expected: u32) -> IpcResult<()> {
         let actual = self.cell_background(row, col)?;
-        if (actual & 0xFFFFFF00) == (expected & 0xFFFFFF00) {
+        if same_rgb(actual, expected) {
             Ok(())
         } else {
-            Err(IpcError::InvalidData(format!(
-                "Background color mismatch at ({}, {}): expected 0x{:08X}, got 0x{:08X}",
-                row, col, expected, actual
-            )))
+            Err(IpcError::ColorMismatch {
+                row,
+                col,
+                layer: ColorLayer::Background,
+                expected,
+                actual,
+            })
         }
     }
 
     /// Verify the terminal's current foreground matches expected.
     fn assert_foreground_color(&self, row: u16, col: u16, expected: u32) -> IpcResult<()> {
         let actual = self.cell_foreground(row, col)?;
-        if (actual & 0xFFFFFF00) == (expected & 0xFFFFFF00) {
+        if same_rgb(actual, expected) {
+            Ok(())
+        } else {
+            Err(IpcError::ColorMismatch {
+                row,
+                col,
+                layer: ColorLayer::Foreground,
+                expected,
+                actual,
+            })
+        }
+    }
+
+    /// Verify the foreground at a cell has the given red, green and blue
+    /// channels. Alpha is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terminal_testlib::{
+    ///     ipc::{CellAttributes, IpcResult},
+    ///     theme::{rgba, ThemeTestExt},
+    /// };
+    ///
+    /// struct OneCell(CellAttributes);
+    ///
+    /// impl ThemeTestExt for OneCell {
+    ///     fn cell_attrs_at(&self, _row: u16, _col: u16) -> IpcResult<CellAttributes> {
+    ///         Ok(self.0)
+    ///     }
+    /// }
+    ///
+    /// let cell = OneCell(CellAttributes { fg: rgba(0xFF, 0x55, 0x55, 0x80), ..Default::default() });
+    /// assert!(cell.assert_foreground_rgb(0, 0, 0xFF, 0x55, 0x55).is_ok());
+    /// assert!(cell.assert_foreground_rgb(0, 0, 0xFF, 0x00, 0x00).is_err());
+    /// ```
+    fn assert_foreground_rgb(&self, row: u16, col: u16, r: u8, g: u8, b: u8) -> IpcResult<()> {
+        let actual = self.cell_foreground(row, col)?;
+        let (actual_r, actual_g, actual_b, _) = rgba_components(actual);
+        if (actual_r, actual_g, actual_b) == (r, g, b) {
             Ok(())
         } else {
             Err(IpcError::InvalidData(format!(
-                "Foreground color mismatch at ({}, {}): expected 0x{:08X}, got 0x{:08X}",
-                row, col, expected, actual
+                "Foreground color mismatch at ({}, {}): expected rgb({}, {}, {}), got rgb({}, {}, {})",
+                row, col, r, g, b, actual_r, actual_g, actual_b
             )))
         }
     }
@@ -424,7 +1445,7 @@ pub trait ThemeTestExt {
         let actual = self.cell_foreground(row, col)?;
         let expected = palette.ansi_color(expected_ansi);
 
-        if (actual & 0xFFFFFF00) == (expected & 0xFFFFFF00) {
+        if same_rgb(actual, expected) {
             Ok(())
         } else {
             Err(IpcError::InvalidData(format!(
@@ -434,6 +1455,80 @@ pub trait ThemeTestExt {
         }
     }
 
+    /// Verify a cell's foreground is nearest to an ANSI color in the palette.
+    ///
+    /// Unlike [`assert_ansi_color`](Self::assert_ansi_color), this tolerates
+    /// truecolor output that is close to, but not exactly, the palette color
+    /// (e.g. after gamma correction or blending). The nearest palette entry
+    /// must be `expected_ansi` and its squared RGB distance must not exceed
+    /// `max_distance`.
+    fn assert_nearest_ansi(
+        &self,
+        row: u16,
+        col: u16,
+        palette: &ColorPalette,
+        expected_ansi: AnsiColor,
+        max_distance: u32,
+    ) -> IpcResult<()> {
+        let actual = self.cell_foreground(row, col)?;
+        let (nearest, distance) = palette.nearest_ansi(actual);
+
+        if nearest == expected_ansi && distance <= max_distance {
+            Ok(())
+        } else {
+            Err(IpcError::InvalidData(format!(
+                "Color 0x{:08X} at ({}, {}) using palette '{}': expected nearest {:?} within distance {}, got {:?} at distance {}",
+                actual, row, col, palette.name, expected_ansi, max_distance, nearest, distance
+            )))
+        }
+    }
+
+    /// Verify a cell's foreground/background contrast meets a minimum ratio.
+    ///
+    /// The ratio is computed with [`contrast_ratio`], so `4.5` checks WCAG AA
+    /// for normal text and `7.0` checks AAA.
+    fn assert_contrast_at_least(&self, row: u16, col: u16, ratio: f64) -> IpcResult<()> {
+        let (fg, bg) = self.capture_cell_colors(row, col)?;
+        let actual = contrast_ratio(fg, bg);
+
+        if actual >= ratio {
+            Ok(())
+        } else {
+            Err(IpcError::InvalidData(format!(
+                "Insufficient contrast at ({}, {}): fg 0x{:08X} on bg 0x{:08X} has ratio {:.2}:1, expected at least {:.2}:1",
+                row, col, fg, bg, actual, ratio
+            )))
+        }
+    }
+
+    /// Get the cursor color set by the application via OSC 12.
+    ///
+    /// Returns `None` if no cursor color has been set. The default
+    /// implementation reports that the cursor color is unavailable;
+    /// harnesses that can see the terminal output override it.
+    fn cursor_color(&self) -> IpcResult<Option<u32>> {
+        Err(IpcError::InvalidData(
+            "Cursor color is not available from this harness".to_string(),
+        ))
+    }
+
+    /// Verify the cursor color matches the palette's `cursor` color.
+    ///
+    /// Alpha is ignored. Fails if the application never set a cursor color.
+    fn assert_cursor_color(&self, palette: &ColorPalette) -> IpcResult<()> {
+        match self.cursor_color()? {
+            Some(actual) if same_rgb(actual, palette.cursor) => Ok(()),
+            Some(actual) => Err(IpcError::InvalidData(format!(
+                "Cursor color mismatch: expected 0x{:08X}, got 0x{:08X}",
+                palette.cursor, actual
+            ))),
+            None => Err(IpcError::InvalidData(format!(
+                "Cursor color was never set: expected 0x{:08X}",
+                palette.cursor
+            ))),
+        }
+    }
+
     /// Capture the current color state at a position for snapshot testing.
     ///
     /// Returns (foreground, background) as RGBA values.
@@ -489,7 +1584,7 @@ pub trait ThemeTestExt {
 
         loop {
             match self.cell_foreground(row, col) {
-                Ok(actual) if (actual & 0xFFFFFF00) == (expected_fg & 0xFFFFFF00) => {
+                Ok(actual) if same_rgb(actual, expected_fg) => {
                     return Ok(());
                 }
                 _ => {}
@@ -573,6 +1668,213 @@ mod tests {
         assert_eq!(red, 0xF92672FF);
     }
 
+    #[test]
+    fn test_solarized_palettes() {
+        let dark = ColorPalette::solarized_dark();
+        assert_eq!(dark.name, "solarized-dark");
+        assert_eq!(dark.background, 0x002B36FF);
+        assert_eq!(dark.ansi_color(AnsiColor::Yellow), 0xB58900FF);
+
+        let light = ColorPalette::solarized_light();
+        assert_eq!(light.name, "solarized-light");
+        assert_eq!(light.background, 0xFDF6E3FF);
+
+        // Both variants share the same accent colors
+        assert_eq!(light.colors, dark.colors);
+    }
+
+    #[test]
+    fn test_gruvbox_dark_palette() {
+        let palette = ColorPalette::gruvbox_dark();
+        assert_eq!(palette.name, "gruvbox-dark");
+
+        // Check signature bright yellow
+        let yellow = palette.ansi_color(AnsiColor::BrightYellow);
+        assert_eq!(yellow, 0xFABD2FFF);
+        assert_eq!(palette.background, 0x282828FF);
+    }
+
+    #[test]
+    fn test_tokyo_night_palette() {
+        let palette = ColorPalette::tokyo_night();
+        assert_eq!(palette.name, "tokyo-night");
+
+        // Check signature blue
+        let blue = palette.ansi_color(AnsiColor::Blue);
+        assert_eq!(blue, 0x7AA2F7FF);
+        assert_eq!(palette.background, 0x1A1B26FF);
+    }
+
+    #[test]
+    fn test_catppuccin_mocha_palette() {
+        let palette = ColorPalette::catppuccin_mocha();
+        assert_eq!(palette.name, "catppuccin-mocha");
+
+        // Check signature pink and rosewater cursor
+        let magenta = palette.ansi_color(AnsiColor::Magenta);
+        assert_eq!(magenta, 0xF5C2E7FF);
+        assert_eq!(palette.cursor, 0xF5E0DCFF);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_palette_toml_round_trip() {
+        let palette = ColorPalette::dracula();
+        let toml = palette.to_toml_string().unwrap();
+        let loaded = ColorPalette::from_toml_str(&toml).unwrap();
+        assert_eq!(loaded, palette);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_palette_from_json_mixed_formats() {
+        let mut colors: Vec<String> = (0..16).map(|_| "\"#000000\"".to_string()).collect();
+        colors[1] = "4283782655".to_string(); // 0xFF5555FF
+        colors[4] = "\"#BD93F9\"".to_string();
+        let json = format!(
+            r##"{{"name":"mixed","colors":[{}],"background":"#282A36",
+                "foreground":"#F8F8F2CC","cursor":4294967295,"selection":"#44475A"}}"##,
+            colors.join(",")
+        );
+
+        let palette = ColorPalette::from_json_str(&json).unwrap();
+        assert_eq!(palette.name, "mixed");
+        assert_eq!(palette.ansi_color(AnsiColor::Red), 0xFF5555FF);
+        assert_eq!(palette.ansi_color(AnsiColor::Blue), 0xBD93F9FF);
+        assert_eq!(palette.foreground, 0xF8F8F2CC);
+        assert_eq!(palette.cursor, 0xFFFFFFFF);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_palette_wrong_color_count() {
+        let toml = r##"
+            name = "short"
+            colors = ["#000000", "#FFFFFF"]
+            background = "#000000"
+            foreground = "#FFFFFF"
+            cursor = "#FFFFFF"
+            selection = "#444444"
+        "##;
+
+        let err = ColorPalette::from_toml_str(toml).unwrap_err();
+        assert!(err.to_string().contains("exactly 16 colors, found 2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_palette_invalid_hex() {
+        let mut colors = vec!["\"#000000\""; 16];
+        colors[3] = "\"#12345\"";
+        let json = format!(
+            r##"{{"name":"bad","colors":[{}],"background":"#000000",
+                "foreground":"#FFFFFF","cursor":"#FFFFFF","selection":"#444444"}}"##,
+            colors.join(",")
+        );
+
+        let err = ColorPalette::from_json_str(&json).unwrap_err();
+        assert!(err.to_string().contains("'#12345'"));
+    }
+
+    #[test]
+    fn test_itermcolors_rgba() {
+        let dict = "<key>Blue Component</key><real>0.5</real>\
+                    <key>Green Component</key><real>0</real>\
+                    <key>Red Component</key><integer>1</integer>";
+        assert_eq!(itermcolors_rgba(dict), Some(0xFF0080FF));
+
+        let with_alpha = format!("<key>Alpha Component</key><real>0.5</real>{}", dict);
+        assert_eq!(itermcolors_rgba(&with_alpha), Some(0xFF008080));
+
+        assert_eq!(itermcolors_rgba("<key>Red Component</key><real>1</real>"), None);
+    }
+
+    #[test]
+    fn test_itermcolors_missing_color() {
+        let xml = "<plist><dict><key>Ansi 0 Color</key><dict>\
+                   <key>Red Component</key><real>0</real>\
+                   <key>Green Component</key><real>0</real>\
+                   <key>Blue Component</key><real>0</real>\
+                   </dict></dict></plist>";
+        let err = ColorPalette::parse_itermcolors("partial".to_string(), xml).unwrap_err();
+        assert!(err.to_string().contains("Ansi 1 Color"));
+    }
+
+    #[test]
+    fn test_nearest_ansi_exact_and_near_miss() {
+        let palette = ColorPalette::nord();
+
+        // Exact palette color
+        let (color, distance) = palette.nearest_ansi(0xBF616AFF);
+        assert_eq!(color, AnsiColor::Red);
+        assert_eq!(distance, 0);
+
+        // Slightly off blue (gamma shifted), alpha ignored
+        let (color, distance) = palette.nearest_ansi(0x84A3BE80);
+        assert_eq!(color, AnsiColor::Blue);
+        assert_eq!(distance, 3 * 3 + 2 * 2 + 3 * 3);
+    }
+
+    #[test]
+    fn test_nearest_ansi_clearly_wrong_color() {
+        let palette = ColorPalette::slime();
+
+        // Pure blue is nowhere near slime green
+        let (color, distance) = palette.nearest_ansi(0x0000FFFF);
+        assert_ne!(color, AnsiColor::Green);
+        assert!(distance > 10_000);
+    }
+
+    #[test]
+    fn test_nearest_ansi_tie_prefers_lower_index() {
+        // Nord repeats Bright Cyan as Bright Green
+        let palette = ColorPalette::nord();
+        let (color, _) = palette.nearest_ansi(0x8FBCBBFF);
+        assert_eq!(color, AnsiColor::BrightGreen);
+    }
+
+    #[test]
+    fn test_contrast_ratio_black_on_white() {
+        let ratio = contrast_ratio(0x000000FF, 0xFFFFFFFF);
+        assert!((ratio - 21.0).abs() < 1e-9);
+
+        // Symmetric and alpha-independent
+        assert_eq!(ratio, contrast_ratio(0xFFFFFF00, 0x00000080));
+    }
+
+    #[test]
+    fn test_contrast_ratio_low_contrast() {
+        // Same color has no contrast
+        assert!((contrast_ratio(0x777777FF, 0x777777FF) - 1.0).abs() < 1e-9);
+
+        // Dark gray on black fails AA
+        let ratio = contrast_ratio(0x444444FF, 0x000000FF);
+        assert!(ratio < 4.5, "ratio was {}", ratio);
+    }
+
+    #[test]
+    fn test_xterm_256_cube_color() {
+        // 16 + 36*5 + 6*3 + 1 = 215: r=255, g=175, b=95
+        assert_eq!(rgb_from_256(215), Some(0xFFAF5FFF));
+        assert_eq!(rgb_from_256(16), Some(0x000000FF));
+        assert_eq!(rgb_from_256(231), Some(0xFFFFFFFF));
+        assert_eq!(ColorPalette::nord().xterm_256(215), 0xFFAF5FFF);
+    }
+
+    #[test]
+    fn test_xterm_256_gray_ramp() {
+        assert_eq!(rgb_from_256(244), Some(0x808080FF));
+        assert_eq!(rgb_from_256(255), Some(0xEEEEEEFF));
+    }
+
+    #[test]
+    fn test_xterm_256_uses_palette_for_base_colors() {
+        let palette = ColorPalette::dracula();
+        assert_eq!(rgb_from_256(15), None);
+        assert_eq!(palette.xterm_256(15), palette.ansi_color(AnsiColor::BrightWhite));
+        assert_eq!(palette.xterm_256(4), 0xBD93F9FF);
+    }
+
     #[test]
     fn test_matches_ansi() {
         let palette = ColorPalette::slime();
@@ -627,11 +1929,83 @@ mod tests {
         assert_eq!(scan.cells_scanned, 2);
     }
 
+    #[test]
+    fn test_color_scan_frequencies() {
+        let mut scan = ColorScan::new();
+
+        // A 3x3 region: mostly theme background with a two-cell accent
+        for i in 0..9 {
+            scan.add_foreground(if i == 4 { 0xFF0000FF } else { 0xFFFFFFFF });
+            scan.add_background(if i < 2 { 0x00FF00FF } else { 0x000000FF });
+            scan.increment_cells();
+        }
+
+        assert_eq!(scan.color_frequency(0x000000FF), (0, 7));
+        assert_eq!(scan.color_frequency(0x00FF00FF), (0, 2));
+        assert_eq!(scan.color_frequency(0xFFFFFFFF), (8, 0));
+        assert_eq!(scan.color_frequency(0x123456FF), (0, 0));
+        assert_eq!(scan.most_common_foreground(), Some(0xFFFFFFFF));
+        assert_eq!(scan.most_common_background(), Some(0x000000FF));
+
+        // Unique colors keep first-seen order
+        assert_eq!(scan.background_colors, vec![0x00FF00FF, 0x000000FF]);
+    }
+
+    #[test]
+    fn test_color_scan_most_common_tie_and_empty() {
+        let mut scan = ColorScan::new();
+        assert_eq!(scan.most_common_foreground(), None);
+
+        scan.add_foreground(0x111111FF);
+        scan.add_foreground(0x222222FF);
+        assert_eq!(scan.most_common_foreground(), Some(0x111111FF));
+    }
+
+    #[test]
+    fn test_detect_palette_scores() {
+        let candidates = [ColorPalette::nord(), ColorPalette::dracula()];
+        assert_eq!(detect_palette(&ColorScan::new(), &candidates), None);
+
+        let mut scan = ColorScan::new();
+        scan.add_foreground(0xFF5555FF); // dracula red
+        scan.add_foreground(0xFF5555FF);
+        scan.add_foreground(0x53F77EFF); // dracula green, off by 3
+        scan.add_foreground(0x123456FF); // in neither palette
+        assert_eq!(detect_palette(&scan, &candidates), Some(("dracula".to_string(), 0.75)));
+        assert_eq!(detect_palette(&scan, &[]), None);
+
+        let mut unmatched = ColorScan::new();
+        unmatched.add_background(0x123456FF);
+        assert_eq!(detect_palette(&unmatched, &candidates), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_color_scan_serialize() {
+        let mut scan = ColorScan::new();
+        scan.add_foreground(0xFFFFFFFF);
+        scan.add_foreground(0xFFFFFFFF);
+        scan.add_background(0x000000FF);
+        scan.increment_cells();
+        scan.increment_cells();
+
+        let json: serde_json::Value = serde_json::to_value(&scan).unwrap();
+        assert_eq!(json["cells_scanned"], 2);
+        assert_eq!(json["foreground_colors"], serde_json::json!([0xFFFFFFFFu32]));
+        assert_eq!(json["foreground_counts"]["4294967295"], 2);
+        assert_eq!(json["background_counts"]["255"], 1);
+    }
+
     #[test]
     fn test_all_palettes_have_16_colors() {
         assert_eq!(ColorPalette::slime().colors.len(), 16);
         assert_eq!(ColorPalette::dracula().colors.len(), 16);
         assert_eq!(ColorPalette::nord().colors.len(), 16);
         assert_eq!(ColorPalette::monokai().colors.len(), 16);
+        assert_eq!(ColorPalette::solarized_dark().colors.len(), 16);
+        assert_eq!(ColorPalette::solarized_light().colors.len(), 16);
+        assert_eq!(ColorPalette::gruvbox_dark().colors.len(), 16);
+        assert_eq!(ColorPalette::tokyo_night().colors.len(), 16);
+        assert_eq!(ColorPalette::catppuccin_mocha().colors.len(), 16);
     }
 }
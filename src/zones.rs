@@ -6,7 +6,8 @@
 //!
 //! # OSC 133 Markers
 //!
-//! - **A**: Fresh line (start of prompt)
+//! - **A**: Fresh line (start of prompt), optionally with `k=` giving the
+//!   [`PromptKind`]
 //! - **B**: Start of command input
 //! - **C**: End of command (execution starting)
 //! - **D**: End of output (with optional exit code)
@@ -43,9 +44,16 @@
 //! # }
 //! ```
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::ipc::IpcResult;
+use crate::{
+    ipc::{IpcError, IpcResult},
+    screen::{parse_osc7_path, scan_escape, EscapeSequence},
+};
+
+/// Longest unfinished escape sequence [`Osc133Parser::parse`] holds back for
+/// the next call. Anything longer is not a marker and is dropped.
+const MAX_CARRY: usize = 4096;
 
 /// Represents a semantic zone from OSC 133.
 ///
@@ -65,6 +73,28 @@ pub struct SemanticZone {
     pub end_col: u16,
     /// Exit code (only for Output zones after D marker).
     pub exit_code: Option<i32>,
+    /// Working directory reported via OSC 7 before the zone started, if any.
+    pub cwd: Option<String>,
+    /// Time between the C and D markers being parsed (only for Output zones).
+    pub duration: Option<Duration>,
+    /// Kind of prompt from the A marker's `k=` key (only for Prompt zones).
+    pub prompt_kind: Option<PromptKind>,
+}
+
+impl SemanticZone {
+    /// Check whether a cell lies inside this zone.
+    ///
+    /// Zones follow the output stream rather than forming a rectangle: they run
+    /// from the start position up to, but not including, the end position,
+    /// wrapping across full rows in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row position (0-indexed)
+    /// * `col` - Column position (0-indexed)
+    pub fn contains(&self, row: u16, col: u16) -> bool {
+        (self.start_row, self.start_col) <= (row, col) && (row, col) < (self.end_row, self.end_col)
+    }
 }
 
 /// Type of semantic zone.
@@ -78,13 +108,65 @@ pub enum ZoneType {
     Output,
 }
 
+/// One finished command from a shell session, as returned by
+/// [`SemanticZoneExt::command_history`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRecord {
+    /// Command line text from the Command zone, trimmed.
+    pub command: String,
+    /// Exit code from the D marker, if the shell reported one.
+    pub exit_code: Option<i32>,
+    /// Text of the Output zone, with trailing spaces on each line and
+    /// surrounding blank lines removed.
+    pub output: String,
+    /// Working directory reported via OSC 7 before the command started, if any.
+    pub cwd: Option<String>,
+}
+
+/// Kind of prompt announced by the `k=` key of an OSC 133 A marker.
+///
+/// Markers without a `k=` key (or with an unknown value) are [`Initial`](Self::Initial).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PromptKind {
+    /// `k=i` - Primary prompt (e.g. `PS1`)
+    #[default]
+    Initial,
+    /// `k=c` - Editable continuation prompt for multi-line input
+    Continuation,
+    /// `k=s` - Non-editable secondary prompt (e.g. `PS2`)
+    Secondary,
+    /// `k=r` - Right-side prompt
+    Right,
+}
+
+impl PromptKind {
+    /// Parse the prompt kind from the parameters of an A marker.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters after "133;" (e.g., "A", "A;k=s")
+    fn from_params(params: &str) -> Self {
+        params
+            .split(';')
+            .skip(1)
+            .find_map(|key| key.strip_prefix("k="))
+            .map(|kind| match kind {
+                "c" => PromptKind::Continuation,
+                "s" => PromptKind::Secondary,
+                "r" => PromptKind::Right,
+                _ => PromptKind::Initial,
+            })
+            .unwrap_or_default()
+    }
+}
+
 /// Marker type for OSC 133 sequences.
 ///
 /// Each marker represents a transition point in the command execution lifecycle.
 ///
 /// # OSC 133 Sequence Format
 ///
-/// - `\x1b]133;A\x07` - Fresh line/start of prompt
+/// - `\x1b]133;A\x07` - Fresh line/start of prompt (`A;k=s` etc. add a [`PromptKind`])
 /// - `\x1b]133;B\x07` - Start of command
 /// - `\x1b]133;C\x07` - End of command/start of output
 /// - `\x1b]133;D;0\x07` - End of output with exit code 0
@@ -125,6 +207,30 @@ impl Osc133Marker {
     }
 }
 
+/// Find where an escape sequence was cancelled before its end.
+///
+/// `sequence` starts with `ESC`. CAN and SUB abort the sequence and are
+/// consumed; an `ESC` that does not begin an ST terminator starts a new
+/// sequence. Returns the offset to resume parsing from.
+fn cancelled_at(sequence: &[u8]) -> Option<usize> {
+    (1..sequence.len()).find_map(|i| match sequence[i] {
+        0x18 | 0x1a => Some(i + 1),
+        0x1b if sequence.get(i + 1).is_some_and(|&next| next != b'\\') => Some(i),
+        _ => None,
+    })
+}
+
+/// Context recorded alongside each parsed OSC 133 marker.
+#[derive(Debug, Clone)]
+struct MarkerInfo {
+    /// Working directory in effect when the marker was seen.
+    cwd: Option<String>,
+    /// Prompt kind from the `k=` key (A markers only).
+    prompt_kind: Option<PromptKind>,
+    /// When the marker was parsed.
+    seen_at: Instant,
+}
+
 /// Parser for OSC 133 sequences in terminal output.
 ///
 /// This parser tracks OSC 133 markers encountered during terminal parsing
@@ -143,10 +249,23 @@ impl Osc133Marker {
 ///
 /// let zones = parser.zones();
 /// assert_eq!(zones.len(), 3); // Prompt, Command, Output
+/// assert_eq!(parser.command_text(1), Some("ls"));
 /// ```
 #[derive(Debug)]
 pub struct Osc133Parser {
     markers: Vec<(Osc133Marker, u16, u16)>, // (marker, row, col)
+    /// Context captured for each marker, parallel to `markers`.
+    marker_info: Vec<MarkerInfo>,
+    /// Working directory from the most recent OSC 7 sequence.
+    current_directory: Option<String>,
+    /// Command lines captured between B and C markers, in order.
+    commands: Vec<String>,
+    /// Bytes seen since the last B marker, while waiting for C.
+    pending_command: Option<Vec<u8>>,
+    /// Unfinished escape sequence at the end of the previous `parse` call.
+    carry: Vec<u8>,
+    /// Position (row, col) reached at the end of the previous `parse` call.
+    position: (u16, u16),
 }
 
 impl Osc133Parser {
@@ -154,6 +273,12 @@ impl Osc133Parser {
     pub fn new() -> Self {
         Self {
             markers: Vec::new(),
+            marker_info: Vec::new(),
+            current_directory: None,
+            commands: Vec::new(),
+            pending_command: None,
+            carry: Vec::new(),
+            position: (0, 0),
         }
     }
 
@@ -163,54 +288,47 @@ impl Osc133Parser {
     /// their positions. The position tracking assumes the data represents a
     /// sequential stream of terminal output.
     ///
+    /// Each marker is also timestamped when parsed, so feeding output as it
+    /// arrives lets [`zones`](Self::zones) report how long each command ran.
+    ///
+    /// Data may be split at any byte: an escape sequence cut off at the end of
+    /// one call is held back and completed by the next, and positions carry
+    /// over between calls. As in a terminal, CAN, SUB or a new `ESC` cancels
+    /// a sequence in progress, and an unfinished sequence longer than 4 KiB
+    /// is dropped rather than held back.
+    ///
     /// # Arguments
     ///
     /// * `data` - Raw terminal data that may contain OSC 133 sequences
     pub fn parse(&mut self, data: &[u8]) {
-        let mut row: u16 = 0;
-        let mut col: u16 = 0;
+        let mut buffer = std::mem::take(&mut self.carry);
+        buffer.extend_from_slice(data);
+        let data = buffer.as_slice();
+
+        let (mut row, mut col) = self.position;
         let mut i = 0;
 
         while i < data.len() {
-            // Look for OSC start: ESC ]
-            if i + 1 < data.len() && data[i] == 0x1b && data[i + 1] == b']' {
-                i += 2;
-
-                // Look for "133;"
-                if i + 4 < data.len()
-                    && data[i] == b'1'
-                    && data[i + 1] == b'3'
-                    && data[i + 2] == b'3'
-                    && data[i + 3] == b';'
-                {
-                    i += 4;
-
-                    // Find the terminator (BEL or ST)
-                    let mut end = i;
-                    while end < data.len() {
-                        if data[end] == 0x07 {
-                            // BEL
-                            break;
-                        }
-                        if end + 1 < data.len() && data[end] == 0x1b && data[end + 1] == b'\\' {
-                            // ST
-                            break;
-                        }
-                        end += 1;
-                    }
+            if data[i] == 0x1b {
+                let scanned = scan_escape(&data[i..]);
+                let span = scanned.map_or(data.len() - i, |(_, len)| len);
+                if let Some(resume) = cancelled_at(&data[i..i + span]) {
+                    i += resume;
+                    continue;
+                }
 
-                    // Parse the parameters
-                    if end > i {
-                        if let Ok(params) = std::str::from_utf8(&data[i..end]) {
-                            if let Some(marker) = Osc133Marker::from_params(params) {
-                                self.markers.push((marker, row, col));
-                            }
-                        }
+                let Some((sequence, len)) = scanned else {
+                    // Unfinished sequence; complete it on the next call
+                    if data.len() - i <= MAX_CARRY {
+                        self.carry = data[i..].to_vec();
                     }
-
-                    i = end + 1;
-                    continue;
+                    break;
+                };
+                if let EscapeSequence::Osc(payload) = sequence {
+                    self.handle_osc(payload, row, col);
                 }
+                i += len;
+                continue;
             }
 
             // Track position for visible characters
@@ -218,29 +336,100 @@ impl Osc133Parser {
                 b'\n' => {
                     row += 1;
                     col = 0;
+                    if let Some(command) = self.pending_command.as_mut() {
+                        command.push(b'\n');
+                    }
                 }
                 b'\r' => {
                     col = 0;
                 }
-                0x1b => {
-                    // Skip other escape sequences
-                    i += 1;
-                    if i < data.len() && data[i] == b'[' {
-                        // CSI sequence
-                        i += 1;
-                        while i < data.len() && data[i] >= 0x20 && data[i] < 0x40 {
-                            i += 1;
-                        }
-                    }
-                }
                 _ if data[i] >= 0x20 => {
                     col += 1;
+                    if let Some(command) = self.pending_command.as_mut() {
+                        command.push(data[i]);
+                    }
                 }
                 _ => {}
             }
 
             i += 1;
         }
+
+        self.position = (row, col);
+    }
+
+    /// Record an OSC 133 marker at (row, col), or an OSC 7 directory change.
+    fn handle_osc(&mut self, payload: &[u8], row: u16, col: u16) {
+        if let Some(params) = payload.strip_prefix(b"133;") {
+            let Ok(params) = std::str::from_utf8(params) else {
+                return;
+            };
+            if let Some(marker) = Osc133Marker::from_params(params) {
+                self.record_command_marker(marker);
+                self.markers.push((marker, row, col));
+                let prompt_kind =
+                    (marker == Osc133Marker::FreshLine).then(|| PromptKind::from_params(params));
+                self.marker_info.push(MarkerInfo {
+                    cwd: self.current_directory.clone(),
+                    prompt_kind,
+                    seen_at: Instant::now(),
+                });
+            }
+        } else if let Some(uri) = payload.strip_prefix(b"7;") {
+            // OSC 7 working directory report
+            if let Some(path) = std::str::from_utf8(uri).ok().and_then(parse_osc7_path) {
+                self.current_directory = Some(path);
+            }
+        }
+    }
+
+    /// Update the command capture state for a newly seen marker.
+    ///
+    /// A B marker starts capturing; a C marker that directly follows it completes
+    /// the command line. Any other marker discards an unfinished capture.
+    fn record_command_marker(&mut self, marker: Osc133Marker) {
+        let pending = self.pending_command.take();
+        match marker {
+            Osc133Marker::CommandStart => self.pending_command = Some(Vec::new()),
+            Osc133Marker::CommandExecuted => {
+                if let Some(bytes) = pending {
+                    self.commands.push(String::from_utf8_lossy(&bytes).trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the command lines captured between B and C markers.
+    ///
+    /// Each entry is the literal text printed between a B marker and the C marker
+    /// that follows it, with surrounding whitespace (such as the echoed newline
+    /// from pressing Enter) trimmed. Entries are in the same order as the
+    /// [`ZoneType::Command`] zones returned by [`zones`](Self::zones).
+    pub fn commands(&self) -> &[String] {
+        &self.commands
+    }
+
+    /// Get the command line for the zone at `zone_index` in [`zones`](Self::zones).
+    ///
+    /// # Arguments
+    ///
+    /// * `zone_index` - Index into the vector returned by [`zones`](Self::zones)
+    ///
+    /// # Returns
+    ///
+    /// The captured command text, or `None` if the index is out of range or the
+    /// zone is not a [`ZoneType::Command`] zone.
+    pub fn command_text(&self, zone_index: usize) -> Option<&str> {
+        let zones = self.zones();
+        if zones.get(zone_index)?.zone_type != ZoneType::Command {
+            return None;
+        }
+        let command_index = zones[..zone_index]
+            .iter()
+            .filter(|zone| zone.zone_type == ZoneType::Command)
+            .count();
+        self.commands.get(command_index).map(String::as_str)
     }
 
     /// Get all detected zones.
@@ -256,6 +445,7 @@ impl Osc133Parser {
         for i in 0..self.markers.len().saturating_sub(1) {
             let (marker, start_row, start_col) = self.markers[i];
             let (next_marker, end_row, end_col) = self.markers[i + 1];
+            let cwd = self.marker_info[i].cwd.clone();
 
             let zone_type = match (marker, next_marker) {
                 (Osc133Marker::FreshLine, Osc133Marker::CommandStart) => Some(ZoneType::Prompt),
@@ -270,6 +460,13 @@ impl Osc133Parser {
                         end_row,
                         end_col,
                         exit_code,
+                        cwd: cwd.clone(),
+                        duration: Some(
+                            self.marker_info[i + 1]
+                                .seen_at
+                                .duration_since(self.marker_info[i].seen_at),
+                        ),
+                        prompt_kind: None,
                     });
                     None
                 }
@@ -284,6 +481,13 @@ impl Osc133Parser {
                     end_row,
                     end_col,
                     exit_code: None,
+                    cwd,
+                    duration: None,
+                    prompt_kind: if zone_type == ZoneType::Prompt {
+                        self.marker_info[i].prompt_kind
+                    } else {
+                        None
+                    },
                 });
             }
         }
@@ -296,6 +500,20 @@ impl Osc133Parser {
     /// Resets the parser state, removing all tracked markers and zones.
     pub fn clear(&mut self) {
         self.markers.clear();
+        self.marker_info.clear();
+        self.current_directory = None;
+        self.commands.clear();
+        self.pending_command = None;
+        self.carry.clear();
+        self.position = (0, 0);
+    }
+
+    /// Get the working directory from the most recent OSC 7 sequence.
+    ///
+    /// Returns `None` if no OSC 7 sequence has been parsed since creation or
+    /// the last [`clear`](Self::clear).
+    pub fn current_directory(&self) -> Option<&str> {
+        self.current_directory.as_deref()
     }
 
     /// Get the raw markers list (for debugging).
@@ -397,6 +615,56 @@ pub trait SemanticZoneExt {
     ///
     /// Returns a timeout error if the command doesn't complete within the timeout.
     fn wait_for_command_complete(&mut self, timeout: Duration) -> IpcResult<Option<i32>>;
+
+    /// Wait for command completion and check its exit code.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - Exit code the command should finish with
+    /// * `timeout` - Maximum time to wait
+    ///
+    /// # Errors
+    ///
+    /// Returns a timeout error if the command doesn't complete within the timeout,
+    /// or [`IpcError::ExitCodeMismatch`] if it finishes with a different exit code
+    /// or without reporting one.
+    fn wait_for_exit_code(&mut self, expected: i32, timeout: Duration) -> IpcResult<()> {
+        match self.wait_for_command_complete(timeout)? {
+            Some(actual) if actual == expected => Ok(()),
+            actual => Err(IpcError::ExitCodeMismatch { expected, actual }),
+        }
+    }
+
+    /// Get every finished command in order.
+    ///
+    /// Each Command zone is paired with the Output zone that directly follows
+    /// it. Commands still running (no D marker yet) are not included.
+    fn command_history(&self) -> IpcResult<Vec<CommandRecord>> {
+        let zones = self.zones()?;
+        let mut history = Vec::new();
+
+        for pair in zones.windows(2) {
+            let (command, output) = (&pair[0], &pair[1]);
+            if command.zone_type != ZoneType::Command || output.zone_type != ZoneType::Output {
+                continue;
+            }
+
+            let output_text = self.zone_text(output)?;
+            let output_text = output_text
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n");
+            history.push(CommandRecord {
+                command: self.zone_text(command)?.trim().to_string(),
+                exit_code: output.exit_code,
+                output: output_text.trim_matches('\n').to_string(),
+                cwd: command.cwd.clone(),
+            });
+        }
+
+        Ok(history)
+    }
 }
 
 #[cfg(test)]
@@ -469,6 +737,208 @@ mod tests {
         assert_eq!(zones[2].exit_code, Some(0));
     }
 
+    #[test]
+    fn test_command_text_echo_hello() {
+        let mut parser = Osc133Parser::new();
+
+        let data = b"\x1b]133;A\x07$ \x1b]133;B\x07echo hello\r\n\x1b]133;C\x07hello\r\n\x1b]133;D;0\x07";
+        parser.parse(data);
+
+        assert_eq!(parser.commands(), &["echo hello".to_string()]);
+        assert_eq!(parser.command_text(1), Some("echo hello"));
+        assert_eq!(parser.command_text(0), None); // Prompt zone
+        assert_eq!(parser.command_text(2), None); // Output zone
+        assert_eq!(parser.command_text(3), None); // Out of range
+    }
+
+    #[test]
+    fn test_command_text_multiple_commands() {
+        let mut parser = Osc133Parser::new();
+
+        parser.parse(b"\x1b]133;A\x07$ \x1b]133;B\x07ls -la\x1b]133;C\x07\x1b]133;D;0\x07");
+        parser.parse(b"\x1b]133;A\x07$ \x1b]133;B\x07\x1b[1mcat\x1b[0m f.txt\x1b]133;C\x07");
+
+        assert_eq!(parser.commands(), &["ls -la".to_string(), "cat f.txt".to_string()]);
+        assert_eq!(parser.command_text(1), Some("ls -la"));
+        assert_eq!(parser.command_text(4), Some("cat f.txt"));
+    }
+
+    #[test]
+    fn test_command_text_st_terminator() {
+        let mut parser = Osc133Parser::new();
+
+        parser.parse(b"\x1b]133;B\x1b\\make test\x1b]133;C\x1b\\");
+        assert_eq!(parser.commands(), &["make test".to_string()]);
+    }
+
+    #[test]
+    fn test_osc7_cwd_attached_to_zones() {
+        let mut parser = Osc133Parser::new();
+
+        let data = concat!(
+            "\x1b]7;file://host/tmp\x07",
+            "\x1b]133;A\x07$ \x1b]133;B\x07ls\x1b]133;C\x07\nfile.txt\n\x1b]133;D;0\x07",
+            "\x1b]7;file://host/home/user\x1b\\",
+            "\x1b]133;A\x07$ \x1b]133;B\x07pwd\x1b]133;C\x07",
+        )
+        .as_bytes();
+        parser.parse(data);
+
+        let zones = parser.zones();
+        assert_eq!(zones.len(), 5);
+        assert!(zones[..3].iter().all(|z| z.cwd.as_deref() == Some("/tmp")));
+        assert_eq!(zones[4].zone_type, ZoneType::Command);
+        assert_eq!(zones[4].cwd.as_deref(), Some("/home/user"));
+        assert_eq!(parser.current_directory(), Some("/home/user"));
+
+        // OSC 7 payload does not count as visible columns
+        assert_eq!(parser.markers()[0], (Osc133Marker::FreshLine, 0, 0));
+    }
+
+    #[test]
+    fn test_output_zone_duration() {
+        let mut parser = Osc133Parser::new();
+
+        // Simulate `sleep 0.2`: C arrives, then D arrives 200ms later
+        parser.parse(b"\x1b]133;A\x07$ \x1b]133;B\x07sleep 0.2\r\n\x1b]133;C\x07");
+        std::thread::sleep(Duration::from_millis(200));
+        parser.parse(b"\x1b]133;D;0\x07");
+
+        let zones = parser.zones();
+        assert_eq!(zones.len(), 3);
+        assert_eq!(zones[0].duration, None);
+        assert_eq!(zones[1].duration, None);
+
+        let duration = zones[2].duration.expect("Output zone should have a duration");
+        assert!(duration >= Duration::from_millis(200), "duration was {:?}", duration);
+    }
+
+    #[test]
+    fn test_zones_without_osc7_have_no_cwd() {
+        let mut parser = Osc133Parser::new();
+        parser.parse(b"\x1b]133;B\x07ls\x1b]133;C\x07");
+
+        assert_eq!(parser.zones()[0].cwd, None);
+        assert_eq!(parser.current_directory(), None);
+    }
+
+    #[test]
+    fn test_command_text_requires_c_marker() {
+        let mut parser = Osc133Parser::new();
+
+        // B followed by a new prompt (command aborted) is not a command
+        parser.parse(b"\x1b]133;B\x07partial\x1b]133;A\x07");
+        assert!(parser.commands().is_empty());
+
+        parser.clear();
+        assert!(parser.commands().is_empty());
+    }
+
+    #[test]
+    fn test_marker_split_across_parse_calls() {
+        let mut parser = Osc133Parser::new();
+
+        // "\x1b]133;D;0\x07" cut down the middle
+        parser.parse(b"\x1b]133;C\x07done\n\x1b]13");
+        assert_eq!(parser.markers().len(), 1);
+        parser.parse(b"3;D;0\x07");
+
+        assert_eq!(parser.markers().len(), 2);
+        assert_eq!(parser.markers()[1], (Osc133Marker::CommandFinished(Some(0)), 1, 0));
+        assert_eq!(parser.zones()[0].exit_code, Some(0));
+
+        // ST terminator split between its two bytes
+        parser.parse(b"\x1b]133;A\x1b");
+        parser.parse(b"\\$ ");
+        assert_eq!(parser.markers()[2].0, Osc133Marker::FreshLine);
+    }
+
+    #[test]
+    fn test_cancelled_sequence_is_not_carried() {
+        let mut parser = Osc133Parser::new();
+
+        // CAN aborts the first marker; the text after it is visible
+        parser.parse(b"\x1b]133;A\x18$ ");
+        // A new ESC abandons an unterminated OSC
+        parser.parse(b"\x1b]133;B\x1b]133;C\x07");
+
+        assert_eq!(parser.markers(), &[(Osc133Marker::CommandExecuted, 0, 2)]);
+        assert!(parser.carry.is_empty());
+    }
+
+    #[test]
+    fn test_unterminated_sequence_carry_is_capped() {
+        let mut parser = Osc133Parser::new();
+
+        let mut runaway = b"\x1b]133;".to_vec();
+        runaway.resize(MAX_CARRY + 1, b'x');
+        parser.parse(&runaway);
+        assert!(parser.carry.is_empty());
+
+        // Later markers are parsed instead of extending the dropped string
+        parser.parse(b"\x07\x1b]133;A\x07");
+        assert_eq!(parser.markers().len(), 1);
+        assert_eq!(parser.markers()[0].0, Osc133Marker::FreshLine);
+    }
+
+    #[test]
+    fn test_parse_matches_at_every_split_point() {
+        let data = b"\x1b]133;A\x07$ \x1b]133;B\x07\x1b[1mls\x1b[0m\x1b]133;C\x07\nfile.txt\n\x1b]133;D;0\x07";
+
+        let mut whole = Osc133Parser::new();
+        whole.parse(data);
+
+        for split in 0..=data.len() {
+            let mut parser = Osc133Parser::new();
+            parser.parse(&data[..split]);
+            parser.parse(&data[split..]);
+
+            assert_eq!(parser.markers(), whole.markers(), "split at byte {}", split);
+            assert_eq!(parser.commands(), whole.commands(), "split at byte {}", split);
+        }
+    }
+
+    #[test]
+    fn test_prompt_kind_from_aux_keys() {
+        let mut parser = Osc133Parser::new();
+        parser.parse(b"\x1b]133;A\x07$ \x1b]133;B\x07for x in 1 2\r\n");
+        parser.parse(b"\x1b]133;A;k=s\x07> \x1b]133;B\x07do echo $x; done\r\n");
+        parser.parse(b"\x1b]133;A;aid=7;k=i\x07$ \x1b]133;B\x07");
+
+        let kinds: Vec<_> = parser
+            .zones()
+            .iter()
+            .filter(|zone| zone.zone_type == ZoneType::Prompt)
+            .map(|zone| zone.prompt_kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some(PromptKind::Initial),
+                Some(PromptKind::Secondary),
+                Some(PromptKind::Initial)
+            ]
+        );
+
+        // Aux keys do not change the marker itself
+        let fresh_lines = parser
+            .markers()
+            .iter()
+            .filter(|(marker, _, _)| *marker == Osc133Marker::FreshLine)
+            .count();
+        assert_eq!(fresh_lines, 3);
+    }
+
+    #[test]
+    fn test_prompt_kind_values() {
+        assert_eq!(PromptKind::from_params("A"), PromptKind::Initial);
+        assert_eq!(PromptKind::from_params("A;k=i"), PromptKind::Initial);
+        assert_eq!(PromptKind::from_params("A;k=c"), PromptKind::Continuation);
+        assert_eq!(PromptKind::from_params("A;k=s"), PromptKind::Secondary);
+        assert_eq!(PromptKind::from_params("A;k=r"), PromptKind::Right);
+        assert_eq!(PromptKind::from_params("A;k=?"), PromptKind::Initial);
+    }
+
     #[test]
     fn test_parser_clear() {
         let mut parser = Osc133Parser::new();
@@ -522,6 +992,9 @@ mod tests {
             end_row: 0,
             end_col: 5,
             exit_code: None,
+            cwd: None,
+            duration: None,
+            prompt_kind: Some(PromptKind::Initial),
         };
 
         let zone2 = SemanticZone {
@@ -531,11 +1004,29 @@ mod tests {
             end_row: 0,
             end_col: 5,
             exit_code: None,
+            cwd: None,
+            duration: None,
+            prompt_kind: Some(PromptKind::Initial),
         };
 
         assert_eq!(zone1, zone2);
     }
 
+    #[test]
+    fn test_semantic_zone_contains() {
+        // Output runs from (0, 2) after the command to (2, 0) where D arrived
+        let mut parser = Osc133Parser::new();
+        parser.parse(b"\x1b]133;B\x07ls\x1b]133;C\x07\r\nfile.txt\r\n\x1b]133;D;0\x07");
+        let output = &parser.zones()[1];
+        assert_eq!((output.start_row, output.start_col), (0, 2));
+
+        assert!(!output.contains(0, 1));
+        assert!(output.contains(0, 2));
+        assert!(output.contains(1, 0));
+        assert!(output.contains(1, 79));
+        assert!(!output.contains(2, 0));
+    }
+
     #[test]
     fn test_zone_types() {
         assert_ne!(ZoneType::Prompt, ZoneType::Command);
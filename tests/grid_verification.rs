@@ -3,7 +3,7 @@
 //! These tests verify the API for exposing screen/grid state for verification,
 //! enabling comparison between different terminal emulator implementations.
 
-use terminal_testlib::{Cell, ScreenState};
+use terminal_testlib::{Cell, ScreenState, UnderlineColor, UnderlineStyle};
 
 #[test]
 fn test_rows_cols_accessors() {
@@ -358,6 +358,8 @@ fn test_cell_public_fields() {
         bold: true,
         italic: true,
         underline: true,
+        underline_style: UnderlineStyle::Curly,
+        underline_color: Some(UnderlineColor::Rgb(255, 0, 0)),
     };
 
     assert_eq!(cell.c, 'A');
@@ -366,6 +368,8 @@ fn test_cell_public_fields() {
     assert!(cell.bold);
     assert!(cell.italic);
     assert!(cell.underline);
+    assert_eq!(cell.underline_style, UnderlineStyle::Curly);
+    assert_eq!(cell.underline_color, Some(UnderlineColor::Rgb(255, 0, 0)));
 }
 
 #[test]
@@ -287,4 +287,42 @@ mod scarab_integration_tests {
         // but the important part is that the API works)
         let _ = harness.assert_not_in_region(&tester, "status_bar", "UNLIKELY_STRING_XYZ");
     }
+
+    #[test]
+    fn test_region_assertions_with_mock() {
+        use terminal_testlib::ipc::MockSharedMemory;
+
+        let grid = "[1] main  [2] logs\n\nsome content\n\nNORMAL 14:05";
+        let shm = MockSharedMemory::new(20, 5)
+            .with_grid(grid)
+            .with_cursor(2, 3);
+        let harness = ScarabTestHarness::with_mock(shm);
+        let tester = UiRegionTester::new(20, 5)
+            .with_tab_bar(1)
+            .with_status_bar(1);
+
+        let tabs = harness.region_lines(&tester, "tab_bar").unwrap();
+        assert_eq!(tabs, vec!["[1] main  [2] logs  ".to_string()]);
+
+        assert!(harness
+            .assert_region_contains(&tester, "status_bar", "NORMAL")
+            .is_ok());
+        assert!(harness
+            .assert_region_contains(&tester, "status_bar", "main")
+            .is_err());
+        assert!(harness
+            .assert_not_in_region(&tester, "tab_bar", "content")
+            .is_ok());
+        assert!(harness
+            .assert_region_matches(&tester, "status_bar", r"\d{2}:\d{2}")
+            .is_ok());
+
+        let content = harness.content_area_contents(&tester).unwrap();
+        assert!(content.contains("some content"));
+
+        assert!(harness.assert_cursor_in_content(&tester).is_ok());
+        assert!(harness
+            .assert_cursor_in_region(&tester, "status_bar")
+            .is_err());
+    }
 }
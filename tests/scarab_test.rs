@@ -6,9 +6,11 @@
 
 #[cfg(all(feature = "scarab", target_family = "unix"))]
 mod scarab_tests {
+    use std::os::unix::net::UnixListener;
     use std::path::PathBuf;
     use std::time::Duration;
 
+    use terminal_testlib::ipc::{test_support::ShmSegment, CellAttributes, IpcError};
     use terminal_testlib::scarab::{ScarabConfig, ScarabTestHarness};
 
     #[test]
@@ -79,6 +81,21 @@ mod scarab_tests {
         assert_eq!(config.default_timeout, Duration::from_secs(30));
     }
 
+    #[test]
+    fn test_config_builder_retries() {
+        let defaults = ScarabConfig::default();
+        assert_eq!(defaults.connect_retries, 5);
+        assert_eq!(defaults.retry_interval, Duration::from_millis(50));
+
+        let config = ScarabConfig::builder()
+            .connect_retries(2)
+            .retry_interval(Duration::from_millis(10))
+            .build();
+
+        assert_eq!(config.connect_retries, 2);
+        assert_eq!(config.retry_interval, Duration::from_millis(10));
+    }
+
     #[test]
     fn test_config_builder_prompt_patterns() {
         let config = ScarabConfig::builder()
@@ -149,6 +166,112 @@ mod scarab_tests {
         std::env::remove_var("SCARAB_TEST_RTL");
     }
 
+    #[test]
+    fn test_connect_gives_up_with_last_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("missing.sock");
+        let config = ScarabConfig::builder()
+            .socket_path(&socket)
+            .connect_retries(2)
+            .retry_interval(Duration::from_millis(10))
+            .build();
+
+        let start = std::time::Instant::now();
+        let result = ScarabTestHarness::with_config(config);
+
+        // Two retries: 10ms + 20ms of backoff
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert!(matches!(result, Err(IpcError::SocketNotFound(path)) if path == socket));
+    }
+
+    #[test]
+    fn test_connect_retries_until_daemon_is_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("scarab.sock");
+        let shm = scarab_segment("retry");
+
+        // The "daemon" binds its socket only after the first attempt has failed
+        let listener_path = socket.clone();
+        let daemon = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            let listener = UnixListener::bind(listener_path).unwrap();
+            listener.accept().unwrap()
+        });
+
+        let config = ScarabConfig::builder()
+            .socket_path(&socket)
+            .shm_path(&shm.name)
+            .connect_retries(10)
+            .retry_interval(Duration::from_millis(20))
+            .build();
+        let harness = ScarabTestHarness::with_config(config).unwrap();
+
+        assert_eq!(harness.dimensions(), (4, 1));
+        assert!(harness.contains("scra").unwrap());
+        daemon.join().unwrap();
+    }
+
+    #[test]
+    fn test_daemon_zones_from_output_log() {
+        use terminal_testlib::zones::{SemanticZoneExt, ZoneType};
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("scarab.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let shm = scarab_segment("zones");
+        let output = ShmSegment::output_log(
+            "zones_out",
+            0x5343_5241,
+            256,
+            b"\x1b]133;A\x07$ \x1b]133;B\x07ls\x1b]133;C\x07\r\nscra\r\n\x1b]133;D;3\x07",
+        );
+
+        let config = ScarabConfig::builder()
+            .socket_path(&socket)
+            .shm_path(&shm.name)
+            .output_shm_path(&output.name)
+            .build();
+        let mut harness = ScarabTestHarness::with_config(config).unwrap();
+        let _daemon = listener.accept().unwrap();
+
+        let zones = harness.zones().unwrap();
+        assert_eq!(zones.len(), 3);
+        assert_eq!(zones[2].zone_type, ZoneType::Output);
+        assert_eq!(
+            harness
+                .wait_for_command_complete(Duration::from_secs(1))
+                .unwrap(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_daemon_without_output_log() {
+        use terminal_testlib::zones::SemanticZoneExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("scarab.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let shm = scarab_segment("no_log");
+
+        let config = ScarabConfig::builder()
+            .socket_path(&socket)
+            .shm_path(&shm.name)
+            .output_shm_path("/rtl_scarab_missing_log")
+            .build();
+        let harness = ScarabTestHarness::with_config(config).unwrap();
+        let _daemon = listener.accept().unwrap();
+
+        // Connecting succeeds; only the raw stream is unavailable
+        assert!(harness.contains("scra").unwrap());
+        assert!(matches!(harness.zones(), Err(IpcError::InvalidData(_))));
+    }
+
+    /// Shared memory segment with a Scarab header and a 4x1 grid.
+    fn scarab_segment(tag: &str) -> ShmSegment {
+        ShmSegment::grid(tag, 0x5343_5241, 4, 1, |i| b"scra"[i], |_| CellAttributes::default())
+    }
+
     // Integration tests that require a running scarab-daemon
     #[test]
     #[ignore = "requires running scarab-daemon - run with SCARAB_TEST_RTL=1"]
@@ -233,4 +356,539 @@ mod scarab_tests {
         // This should succeed if a shell is running
         assert!(result.is_ok() || result.is_err(), "Should return a result");
     }
+
+    // Tests driving the harness through in-memory shared memory
+    mod mock {
+        use std::time::Duration;
+
+        use terminal_testlib::ipc::{
+            CellAttributes, CellFlags, ColorLayer, IpcError, MockSharedMemory,
+        };
+        use terminal_testlib::regions::{UiRegionTestExt, UiRegionTester};
+        use terminal_testlib::scarab::{ScarabTestHarness, SeqlockTestExt};
+        use terminal_testlib::theme::{AnsiColor, ColorDiagnostics, ColorPalette, ThemeTestExt};
+        use terminal_testlib::zones::{SemanticZone, SemanticZoneExt, ZoneType};
+
+        const RED: u32 = 0xFF0000FF;
+        const BLUE: u32 = 0x0000FFFF;
+
+        fn mock_harness() -> ScarabTestHarness {
+            let error = CellAttributes {
+                fg: RED,
+                bg: BLUE,
+                flags: CellFlags::BOLD.bits(),
+                reserved: 0,
+            };
+            let mut shm = MockSharedMemory::new(20, 3).with_grid("error: boom\nok");
+            for col in 0..5 {
+                shm.set_cell_attrs(0, col, error);
+            }
+            ScarabTestHarness::with_mock(shm)
+        }
+
+        #[test]
+        fn test_mock_grid_and_contains() {
+            let harness = mock_harness();
+
+            assert_eq!(harness.dimensions(), (20, 3));
+            assert!(harness.contains("boom").unwrap());
+            assert!(harness.assert_contains("ok").is_ok());
+            assert!(harness.assert_contains("missing").is_err());
+        }
+
+        #[test]
+        fn test_mock_assert_row_eq() {
+            let harness = mock_harness();
+
+            assert!(harness.assert_row_eq(0, "error: boom").is_ok());
+            assert!(harness.assert_row_eq(2, "").is_ok());
+            match harness.assert_row_eq(1, "ko") {
+                Err(IpcError::InvalidData(message)) => {
+                    assert!(message.contains("Row 1 mismatch at column 0"));
+                    assert!(message.contains("Found:    \"ok\""));
+                }
+                other => panic!("Expected InvalidData, got {:?}", other),
+            }
+            assert!(harness.assert_row_eq(3, "").is_err());
+        }
+
+        #[test]
+        fn test_mock_zones_from_osc133_stream() {
+            let shm = MockSharedMemory::new(20, 3)
+                .with_grid("$ ls\nfile.txt\n$")
+                .with_raw_output(b"\x1b]133;A\x07$ \x1b]133;B\x07ls\x1b]133;C\x07\r\nfile.txt\r\n");
+            let mut harness = ScarabTestHarness::with_mock(shm);
+
+            let zones = harness.zones().unwrap();
+            assert_eq!(zones.len(), 2);
+            assert_eq!(zones[1].zone_type, ZoneType::Command);
+            assert_eq!(harness.zone_text(&zones[1]).unwrap(), "ls");
+            assert_eq!(harness.zone_at(0, 3).unwrap().unwrap().zone_type, ZoneType::Command);
+            assert!(harness.last_output_zone().unwrap().is_none());
+
+            // The command has not finished yet
+            assert!(matches!(
+                harness.wait_for_command_complete(Duration::from_millis(50)),
+                Err(IpcError::Timeout(_))
+            ));
+
+            harness
+                .mock_mut()
+                .unwrap()
+                .push_raw_output(b"\x1b]133;D;2\x07");
+            assert_eq!(
+                harness
+                    .wait_for_command_complete(Duration::from_secs(1))
+                    .unwrap(),
+                Some(2)
+            );
+
+            let output = harness.last_output_zone().unwrap().unwrap();
+            assert_eq!((output.start_row, output.end_row), (0, 2));
+            assert_eq!(output.exit_code, Some(2));
+            assert!(harness.assert_zone_at(1, 0, ZoneType::Output).is_ok());
+        }
+
+        #[test]
+        fn test_mock_wait_for_exit_code() {
+            let prompt = b"\x1b]133;A\x07$ \x1b]133;B\x07make\x1b]133;C\x07\r\n";
+            let shm = MockSharedMemory::new(20, 3).with_raw_output(prompt);
+            let mut harness = ScarabTestHarness::with_mock(shm);
+
+            // Still running
+            assert!(matches!(
+                harness.wait_for_exit_code(0, Duration::from_millis(50)),
+                Err(IpcError::Timeout(_))
+            ));
+
+            harness
+                .mock_mut()
+                .unwrap()
+                .push_raw_output(b"\x1b]133;D;0\x07");
+            assert!(harness
+                .wait_for_exit_code(0, Duration::from_secs(1))
+                .is_ok());
+
+            match harness.wait_for_exit_code(1, Duration::from_secs(1)) {
+                Err(IpcError::ExitCodeMismatch { expected, actual }) => {
+                    assert_eq!((expected, actual), (1, Some(0)));
+                }
+                other => panic!("Expected ExitCodeMismatch, got {:?}", other),
+            }
+
+            // A D marker without an exit code never matches
+            let shm = MockSharedMemory::new(20, 3).with_raw_output(
+                b"\x1b]133;A\x07$ \x1b]133;B\x07true\x1b]133;C\x07\r\n\x1b]133;D\x07",
+            );
+            let mut harness = ScarabTestHarness::with_mock(shm);
+            assert!(matches!(
+                harness.wait_for_exit_code(0, Duration::from_secs(1)),
+                Err(IpcError::ExitCodeMismatch { actual: None, .. })
+            ));
+        }
+
+        #[test]
+        fn test_mock_command_history() {
+            let stream = concat!(
+                "\x1b]7;file://host/tmp/work\x07",
+                "\x1b]133;A\x07$ \x1b]133;B\x07echo hi\x1b]133;C\x07\r\nhi\r\n\x1b]133;D;0\x07",
+                "\x1b]133;A\x07$ \x1b]133;B\x07false\x1b]133;C\x07\r\n\x1b]133;D;1\x07",
+                "\x1b]133;A\x07$ \x1b]133;B\x07sleep 5\x1b]133;C\x07\r\n",
+            );
+            let shm = MockSharedMemory::new(20, 4)
+                .with_grid("$ echo hi\nhi\n$ false\n$ sleep 5")
+                .with_raw_output(stream.as_bytes());
+            let harness = ScarabTestHarness::with_mock(shm);
+
+            // The running `sleep` has no D marker yet and is left out
+            let history = harness.command_history().unwrap();
+            assert_eq!(history.len(), 2);
+
+            assert_eq!(history[0].command, "echo hi");
+            assert_eq!(history[0].exit_code, Some(0));
+            assert_eq!(history[0].output, "hi");
+            assert_eq!(history[0].cwd.as_deref(), Some("/tmp/work"));
+
+            assert_eq!(history[1].command, "false");
+            assert_eq!(history[1].exit_code, Some(1));
+            assert_eq!(history[1].output, "");
+        }
+
+        #[test]
+        fn test_mock_zone_text_multibyte() {
+            // Exactly as wide as the text so rows carry no padding
+            let shm = MockSharedMemory::new(12, 3).with_grid("$ echo café!\nrésumé naïve\nok");
+            let harness = ScarabTestHarness::with_mock(shm);
+
+            let zone = SemanticZone {
+                zone_type: ZoneType::Output,
+                start_row: 0,
+                start_col: 7,
+                end_row: 1,
+                end_col: 6,
+                exit_code: Some(0),
+                cwd: None,
+                duration: None,
+                prompt_kind: None,
+            };
+            assert_eq!(harness.zone_text(&zone).unwrap(), "café!\nrésumé");
+
+            // End columns past the line are clamped to its length
+            let zone = SemanticZone {
+                start_row: 1,
+                start_col: 7,
+                end_col: 50,
+                ..zone
+            };
+            assert_eq!(harness.zone_text(&zone).unwrap(), "naïve");
+        }
+
+        #[test]
+        fn test_mock_color_report() {
+            let harness = mock_harness();
+            let tester = UiRegionTester::new(20, 3).with_tab_bar(1);
+            let mut palette = ColorPalette::slime();
+            palette.colors[AnsiColor::Red.as_index() as usize] = RED;
+            palette.colors[AnsiColor::Blue.as_index() as usize] = BLUE;
+
+            let report = harness.color_report(&tester, "tab_bar", &palette).unwrap();
+            assert!(report.contains("# Color report: region `tab_bar` (palette `slime`)"));
+            assert!(report.contains("```text\nerror: boom         \n```"));
+            assert!(report.contains("| `0xFF0000FF` | 5 | 0 | Red |"));
+            assert!(report.contains("| `0x0000FFFF` | 0 | 5 | Blue |"));
+            assert!(report.contains("| 0 | 0 | 'e' | `0xFF0000FF` Red | `0x0000FFFF` Blue |"));
+            assert!(report.contains("| 0 | 5 | ':' | `0x00000000` "));
+
+            let diagnostics =
+                ColorDiagnostics::capture(&harness, &tester, "tab_bar", &palette).unwrap();
+            assert_eq!(diagnostics.cells.len(), 20);
+            assert_eq!(diagnostics.scan.color_frequency(RED), (5, 0));
+
+            assert!(matches!(
+                harness.color_report(&tester, "sidebar", &palette),
+                Err(IpcError::RegionNotFound(_))
+            ));
+        }
+
+        #[test]
+        fn test_mock_region_cells() {
+            let selected = CellAttributes { fg: RED, bg: BLUE, ..Default::default() };
+            let mut shm = MockSharedMemory::new(12, 3).with_grid(" 1  2  3\n$ ls");
+            shm.set_cell_attrs(0, 4, selected);
+            let harness = ScarabTestHarness::with_mock(shm);
+            let tester = UiRegionTester::new(12, 3).with_tab_bar(1);
+
+            let cells = harness.region_cells(&tester, "tab_bar").unwrap();
+            assert_eq!(cells.len(), 1);
+            assert_eq!(cells[0].len(), 12);
+
+            // Text and color of the selected tab in one read
+            assert_eq!(cells[0][4], ('2', selected));
+            let highlighted: Vec<char> = cells[0]
+                .iter()
+                .filter(|(_, attrs)| attrs.bg == BLUE)
+                .map(|&(glyph, _)| glyph)
+                .collect();
+            assert_eq!(highlighted, vec!['2']);
+            assert_eq!(cells[0][1], ('1', CellAttributes::default()));
+
+            assert!(matches!(
+                harness.region_cells(&tester, "sidebar"),
+                Err(IpcError::RegionNotFound(_))
+            ));
+        }
+
+        #[test]
+        fn test_mock_assert_cursor_color() {
+            let palette = ColorPalette::catppuccin_mocha();
+            let shm = MockSharedMemory::new(20, 3).with_raw_output(b"\x1b]12;#f5e0dc\x07");
+            let mut harness = ScarabTestHarness::with_mock(shm);
+            assert_eq!(harness.cursor_color().unwrap(), Some(0xF5E0DCFF));
+            harness.assert_cursor_color(&palette).unwrap();
+
+            // A later X11-style spec wins
+            harness
+                .mock_mut()
+                .unwrap()
+                .push_raw_output(b"\x1b]12;rgb:ff/00/00\x1b\\");
+            assert_eq!(harness.cursor_color().unwrap(), Some(RED));
+            assert!(harness.assert_cursor_color(&palette).is_err());
+
+            // Resetting leaves nothing to compare against
+            harness.mock_mut().unwrap().push_raw_output(b"\x1b]112\x07");
+            assert_eq!(harness.cursor_color().unwrap(), None);
+            let err = harness.assert_cursor_color(&palette).unwrap_err();
+            assert!(err.to_string().contains("never set"));
+        }
+
+        #[test]
+        fn test_mock_color_assertions() {
+            let harness = mock_harness();
+
+            assert!(harness.assert_cell_fg(0, 0, RED).is_ok());
+            assert!(harness.assert_cell_bg(0, 4, BLUE).is_ok());
+            assert!(matches!(
+                harness.assert_cell_fg(0, 5, RED),
+                Err(IpcError::ColorMismatch {
+                    row: 0,
+                    col: 5,
+                    layer: ColorLayer::Foreground,
+                    ..
+                })
+            ));
+            match harness.assert_background_color(1, 0, BLUE) {
+                Err(IpcError::ColorMismatch { layer, expected, actual, .. }) => {
+                    assert_eq!(layer, ColorLayer::Background);
+                    assert_eq!((expected, actual), (BLUE, 0));
+                }
+                other => panic!("Expected ColorMismatch, got {:?}", other),
+            }
+            assert!(matches!(
+                harness.assert_cell_fg(3, 0, RED),
+                Err(IpcError::CellOutOfBounds { row: 3, .. })
+            ));
+            assert!(harness.assert_cell_style_exact(0, 0, CellFlags::BOLD).is_ok());
+            assert!(harness.assert_cell_unstyled(1, 0).is_ok());
+
+            // ThemeTestExt through the same harness
+            let palette = ColorPalette::slime();
+            assert!(harness.assert_foreground_color(0, 0, RED).is_ok());
+            assert!(harness.assert_nearest_ansi(0, 0, &palette, AnsiColor::Red, u32::MAX).is_ok());
+            assert!(harness.assert_contrast_at_least(0, 0, 2.0).is_ok());
+        }
+
+        #[test]
+        fn test_mock_updates_between_assertions() {
+            let mut harness = mock_harness();
+            let before = harness.shared_memory().sequence_number();
+
+            let shm = harness.mock_mut().unwrap();
+            shm.set_grid("done");
+            shm.set_cursor(0, 4);
+
+            assert!(harness.shared_memory().sequence_number() != before);
+            assert!(harness.contains("done").unwrap());
+            assert_eq!(harness.cursor_position().unwrap(), (0, 4));
+        }
+
+        #[test]
+        fn test_mock_wait_for_cursor_at() {
+            let mut harness = mock_harness();
+            let timeout = Duration::from_millis(20);
+
+            // Focus moves across fields between refreshes
+            harness.mock_mut().unwrap().set_cursor(1, 0);
+            assert!(harness.wait_for_cursor_at(1, 0, timeout).is_ok());
+
+            harness.mock_mut().unwrap().set_cursor(2, 7);
+            assert!(harness.wait_for_cursor_at(2, 7, timeout).is_ok());
+
+            match harness.wait_for_cursor_at(0, 3, timeout) {
+                Err(IpcError::CursorTimeout { expected, last_seen, .. }) => {
+                    assert_eq!(expected, (0, 3));
+                    assert_eq!(last_seen, (2, 7));
+                }
+                other => panic!("expected CursorTimeout, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_mock_wait_for_text_on_fake_clock() {
+            let clock = terminal_testlib::FakeClock::new();
+            let mut harness = mock_harness().with_clock(clock.clone());
+            let timeout = Duration::from_secs(300);
+
+            let real = std::time::Instant::now();
+            match harness.wait_for_text("never shown", timeout) {
+                Err(IpcError::Timeout(t)) => assert_eq!(t, timeout),
+                other => panic!("expected Timeout, got {:?}", other),
+            }
+            assert_eq!(clock.elapsed(), timeout);
+            assert!(real.elapsed() < Duration::from_secs(5));
+
+            // A frame already published is picked up without advancing time
+            harness.mock_mut().unwrap().set_grid("never shown");
+            harness.wait_for_text("never shown", timeout).unwrap();
+            assert_eq!(clock.elapsed(), timeout);
+        }
+
+        #[test]
+        fn test_mock_wait_for_region_nonempty_and_change() {
+            let clock = terminal_testlib::FakeClock::new();
+            let shm = MockSharedMemory::new(20, 3).with_grid("files");
+            let mut harness = ScarabTestHarness::with_mock(shm).with_clock(clock.clone());
+            let tester = UiRegionTester::new(20, 3).with_status_bar(1);
+            let timeout = Duration::from_secs(30);
+
+            // The status bar has not loaded yet
+            match harness.wait_for_region_nonempty(&tester, "status_bar", timeout) {
+                Err(IpcError::RegionTimeout { region, last_seen, timeout: t }) => {
+                    assert_eq!(region, "status_bar");
+                    assert_eq!(last_seen, " ".repeat(20));
+                    assert_eq!(t, timeout);
+                }
+                other => panic!("expected RegionTimeout, got {:?}", other),
+            }
+            assert_eq!(clock.elapsed(), timeout);
+
+            // The pane fills in after the first wait gave up
+            harness.mock_mut().unwrap().set_grid("files\n\nLoading...");
+            let loading = harness
+                .wait_for_region_nonempty(&tester, "status_bar", timeout)
+                .unwrap();
+            assert_eq!(loading.trim_end(), "Loading...");
+
+            // Same text as the baseline is not a change
+            let err = harness
+                .wait_for_region_change(&tester, "status_bar", &loading, timeout)
+                .unwrap_err();
+            assert!(err.to_string().contains("last seen:\nLoading..."), "{}", err);
+
+            harness.mock_mut().unwrap().set_grid("files\n\nReady");
+            let ready = harness
+                .wait_for_region_change(&tester, "status_bar", &loading, timeout)
+                .unwrap();
+            assert_eq!(ready.trim_end(), "Ready");
+
+            assert!(matches!(
+                harness.wait_for_region_nonempty(&tester, "sidebar", timeout),
+                Err(IpcError::RegionNotFound(_))
+            ));
+        }
+
+        #[test]
+        fn test_mock_verify_resize_redraw() {
+            let clock = terminal_testlib::FakeClock::new();
+            let timeout = Duration::from_secs(2);
+
+            // An app that redraws on SIGWINCH
+            let shm = MockSharedMemory::new(80, 24).with_redraw_on_resize(true);
+            let mut harness = ScarabTestHarness::with_mock(shm).with_clock(clock.clone());
+            let mut tester = UiRegionTester::new(80, 24).with_status_bar(1);
+            harness
+                .verify_resize_redraw(&mut tester, 100, 30, timeout)
+                .unwrap();
+            assert_eq!(harness.dimensions(), (100, 30));
+            assert_eq!(tester.screen_dimensions(), (100, 30));
+            assert_eq!(clock.elapsed(), Duration::ZERO);
+
+            // The daemon stores the new size but the app never redraws
+            let shm = MockSharedMemory::new(80, 24);
+            let mut harness = ScarabTestHarness::with_mock(shm).with_clock(clock.clone());
+            match harness.verify_resize_redraw(&mut tester, 120, 40, timeout) {
+                Err(IpcError::ResizeNotRedrawn { cols, rows, timeout: t }) => {
+                    assert_eq!((cols, rows, t), (120, 40, timeout));
+                }
+                other => panic!("expected ResizeNotRedrawn, got {:?}", other),
+            }
+            assert_eq!(harness.dimensions(), (120, 40));
+            assert_eq!(tester.screen_dimensions(), (100, 30));
+            assert_eq!(clock.elapsed(), timeout);
+
+            // Plain verify_resize only checks the size
+            harness.verify_resize(&mut tester, 120, 40).unwrap();
+            assert_eq!(tester.screen_dimensions(), (120, 40));
+        }
+
+        #[test]
+        fn test_mock_wait_for_any() {
+            let mut harness = mock_harness();
+            let timeout = Duration::from_millis(20);
+
+            assert_eq!(harness.wait_for_any(&["success", "boom"], timeout).unwrap(), 1);
+            // Earlier candidates win when both are present
+            assert_eq!(harness.wait_for_any(&["ok", "error"], timeout).unwrap(), 0);
+            assert!(matches!(
+                harness.wait_for_any(&["success", "done"], timeout),
+                Err(IpcError::Timeout(_))
+            ));
+        }
+
+        #[test]
+        fn test_mock_wait_for_sequence_reports_failed_step() {
+            let mut harness = mock_harness();
+            let step = Duration::from_millis(20);
+
+            assert!(harness
+                .wait_for_sequence_with_timeouts(&[("error", step), ("ok", step)])
+                .is_ok());
+
+            match harness.wait_for_sequence_with_timeouts(&[
+                ("error", step),
+                ("menu", step),
+                ("ok", step),
+            ]) {
+                Err(IpcError::SequenceTimeout { step: index, text, total, timeout }) => {
+                    assert_eq!(index, 1);
+                    assert_eq!(text, "menu");
+                    assert_eq!(total, 3);
+                    assert_eq!(timeout, step);
+                }
+                other => panic!("expected SequenceTimeout, got {:?}", other),
+            }
+
+            // The shared-timeout variant reports the pending step as well
+            let err = harness
+                .wait_for_sequence(&["boom", "ok", "menu"], step)
+                .unwrap_err();
+            assert!(matches!(err, IpcError::SequenceTimeout { step: 2, .. }));
+            assert!(err.to_string().contains("2 of 3 steps succeeded"));
+        }
+
+        #[test]
+        fn test_mock_scan_matches_per_cell_path() {
+            let harness = mock_harness();
+
+            // Extends past the grid edge; out-of-bounds cells are skipped
+            let scan = harness.scan_colors_in_region(0, 2, 5, 30).unwrap();
+
+            let mut expected = terminal_testlib::theme::ColorScan::new();
+            for row in 0..3 {
+                for col in 2..20 {
+                    let attrs = harness.cell_attrs_at(row, col).unwrap();
+                    expected.add_foreground(attrs.fg);
+                    expected.add_background(attrs.bg);
+                    expected.increment_cells();
+                }
+            }
+
+            assert_eq!(scan.cells_scanned, 54);
+            assert_eq!(scan.foreground_colors, expected.foreground_colors);
+            assert_eq!(scan.background_colors, expected.background_colors);
+            assert_eq!(scan.foreground_counts, expected.foreground_counts);
+            assert_eq!(scan.color_frequency(RED), (3, 0));
+
+            let outside = harness.scan_colors_in_region(5, 0, 6, 3).unwrap();
+            assert_eq!(outside.cells_scanned, 0);
+        }
+
+        #[test]
+        fn test_mock_changed_cells_since() {
+            let mut harness = mock_harness();
+            let before = harness.snapshot().unwrap();
+            assert!(harness.changed_cells_since(&before).unwrap().is_empty());
+
+            // Only the second line updates
+            harness.mock_mut().unwrap().set_grid("error: boom\nok!");
+
+            let changed = harness.changed_cells_since(&before).unwrap();
+            assert_eq!(changed.len(), 1);
+            assert_eq!((changed[0].row, changed[0].col), (1, 2));
+            assert_eq!(changed[0].new_char, '!');
+        }
+
+        #[test]
+        fn test_mock_has_no_daemon_connection() {
+            let mut harness = mock_harness();
+
+            assert!(harness.refresh().is_ok());
+            assert!(harness.send_input("ls\n").is_err());
+            assert!(harness.shared_memory().as_daemon_shm().is_none());
+            assert!(harness.shared_memory_mut().is_none());
+            assert!(harness.verify_seqlock(Duration::from_millis(10)).is_err());
+            // Resizing goes to the mock grid instead
+            harness.resize(100, 30).unwrap();
+            assert_eq!(harness.dimensions(), (100, 30));
+        }
+    }
 }
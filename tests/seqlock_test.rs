@@ -2,7 +2,13 @@
 
 #[cfg(feature = "ipc")]
 mod tests {
-    use terminal_testlib::seqlock::{SeqlockReport, SeqlockVerifier};
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use terminal_testlib::ipc::IpcError;
+    use terminal_testlib::seqlock::{
+        MockSeqlockMemory, SeqlockMemory, SeqlockReport, SeqlockVerifier,
+    };
 
     #[test]
     fn test_seqlock_report_construction() {
@@ -10,6 +16,7 @@ mod tests {
         assert_eq!(report.total_reads, 0);
         assert_eq!(report.torn_read_detections, 0);
         assert_eq!(report.odd_sequence_detections, 0);
+        assert_eq!(report.backward_sequence_detections, 0);
         assert_eq!(report.max_retry_count, 0);
         assert_eq!(report.avg_retry_count, 0.0);
         assert!(!report.has_torn_reads());
@@ -137,4 +144,197 @@ mod tests {
     // Note: Tests that require actual shared memory access should be in
     // integration tests with a real daemon running. These unit tests verify
     // the internal logic and data structures.
+
+    fn is_uniform(contents: &str) -> bool {
+        contents.bytes().all(|b| b == contents.as_bytes()[0])
+    }
+
+    #[test]
+    fn test_torn_reads_under_write_pressure() {
+        let mut shm = MockSeqlockMemory::new(256)
+            .with_write_interval(Duration::from_millis(1))
+            .start();
+        let mut verifier = SeqlockVerifier::new();
+
+        // A read that spans a sizeable part of the write interval is regularly
+        // overtaken by a write and has to retry
+        for _ in 0..50 {
+            let (contents, _retries) = verifier
+                .synchronized_read(&mut shm, |shm| {
+                    let contents = shm.grid_contents();
+                    std::thread::sleep(Duration::from_micros(300));
+                    contents
+                })
+                .unwrap();
+            assert!(is_uniform(&contents), "torn contents returned: {}", contents);
+        }
+
+        let report = verifier.report();
+        assert_eq!(report.total_reads, 50);
+        assert!(report.torn_read_detections > 0);
+        assert!(shm.write_count() > 0);
+    }
+
+    #[test]
+    fn test_no_torn_reads_when_writes_are_rare() {
+        let mut shm = MockSeqlockMemory::new(256)
+            .with_write_interval(Duration::from_secs(60))
+            .start();
+        let mut verifier = SeqlockVerifier::new();
+
+        let report = verifier
+            .verify_seqlock_pattern(&mut shm, Duration::from_millis(50), Duration::from_millis(1))
+            .unwrap();
+
+        assert!(report.total_reads > 0);
+        assert_eq!(report.torn_read_detections, 0);
+        assert_eq!(report.odd_sequence_detections, 0);
+        assert_eq!(shm.write_count(), 0);
+    }
+
+    /// Mock whose writer has run and stopped, leaving a non-zero sequence.
+    fn settled_mock() -> MockSeqlockMemory {
+        let mut shm = MockSeqlockMemory::new(64)
+            .with_write_interval(Duration::ZERO)
+            .start();
+        while shm.write_count() < 3 {
+            std::thread::yield_now();
+        }
+        shm.stop();
+        shm
+    }
+
+    #[test]
+    fn test_backward_sequence_during_read() {
+        let mut shm = settled_mock();
+        let mut verifier = SeqlockVerifier::new();
+        let restarted = Cell::new(false);
+
+        // The writer restarts while the first attempt is reading
+        verifier
+            .synchronized_read(&mut shm, |shm| {
+                if !restarted.replace(true) {
+                    shm.reset_sequence();
+                }
+                shm.grid_contents()
+            })
+            .unwrap();
+
+        let report = verifier.report();
+        assert_eq!(report.backward_sequence_detections, 1);
+        assert_eq!(report.torn_read_detections, 0);
+        assert!(report.has_backward_sequences());
+        assert_eq!(report.backward_sequence_percentage(), 100.0);
+        assert_eq!(report.avg_retry_count, 1.0);
+    }
+
+    #[test]
+    fn test_backward_sequence_between_reads() {
+        let mut shm = settled_mock();
+        let mut verifier = SeqlockVerifier::new();
+
+        verifier
+            .synchronized_read(&mut shm, |shm| shm.grid_contents())
+            .unwrap();
+        assert!(!verifier.report().has_backward_sequences());
+
+        shm.reset_sequence();
+        verifier
+            .synchronized_read(&mut shm, |shm| shm.grid_contents())
+            .unwrap();
+        verifier
+            .synchronized_read(&mut shm, |shm| shm.grid_contents())
+            .unwrap();
+
+        let report = verifier.report();
+        assert_eq!(report.total_reads, 3);
+        assert_eq!(report.backward_sequence_detections, 1);
+        assert_eq!(report.torn_read_detections, 0);
+        // Noticing the restart did not make any read retry
+        assert_eq!(report.avg_retry_count, 0.0);
+    }
+
+    #[test]
+    fn test_max_retries_default_and_builder() {
+        assert_eq!(SeqlockVerifier::new().max_retries(), 100);
+        assert_eq!(SeqlockVerifier::new().with_max_retries(5).max_retries(), 5);
+    }
+
+    #[test]
+    fn test_tiny_retry_limit_exceeded() {
+        let mut shm = MockSeqlockMemory::new(256)
+            .with_write_interval(Duration::ZERO)
+            .start();
+        let mut verifier = SeqlockVerifier::new().with_max_retries(1);
+
+        // Writes never pause, so a slow read is always overtaken
+        let err = verifier
+            .synchronized_read(&mut shm, |shm| {
+                std::thread::sleep(Duration::from_millis(1));
+                shm.grid_contents()
+            })
+            .unwrap_err();
+
+        assert!(matches!(err, IpcError::InvalidData(_)));
+        assert!(err.to_string().contains("after 1 attempts"), "{}", err);
+    }
+
+    #[test]
+    fn test_generous_retry_limit_succeeds() {
+        let mut shm = MockSeqlockMemory::new(256)
+            .with_write_interval(Duration::from_micros(100))
+            .start();
+        let mut verifier = SeqlockVerifier::new().with_max_retries(10_000);
+
+        for _ in 0..20 {
+            let (contents, _retries) = verifier
+                .synchronized_read(&mut shm, |shm| {
+                    std::thread::sleep(Duration::from_micros(50));
+                    shm.grid_contents()
+                })
+                .unwrap();
+            assert!(is_uniform(&contents));
+        }
+
+        assert_eq!(verifier.report().total_reads, 20);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_to_json() {
+        let mut report = SeqlockReport::new();
+        report.total_reads = 200;
+        report.torn_read_detections = 10;
+        report.backward_sequence_detections = 1;
+        report.max_retry_count = 2;
+
+        let json = report.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["total_reads"], 200);
+        assert_eq!(value["torn_read_detections"], 10);
+        assert_eq!(value["odd_sequence_detections"], 0);
+        assert_eq!(value["backward_sequence_detections"], 1);
+        assert_eq!(value["max_retry_count"], 2);
+        assert_eq!(value["avg_retry_count"], 0.0);
+    }
+
+    #[test]
+    fn test_unsynchronized_read_sees_write_in_progress() {
+        let mut shm = MockSeqlockMemory::new(256)
+            .with_write_interval(Duration::ZERO)
+            .with_write_duration(Duration::from_millis(50))
+            .start();
+        let verifier = SeqlockVerifier::new();
+
+        while !verifier.is_write_in_progress(&shm) {
+            std::thread::yield_now();
+        }
+        let contents = shm.grid_contents().unwrap();
+        assert!(!is_uniform(&contents));
+
+        shm.stop();
+        assert!(!verifier.is_write_in_progress(&shm));
+        assert!(is_uniform(&shm.grid_contents().unwrap()));
+    }
 }
@@ -4,7 +4,10 @@
 
 use terminal_testlib::{
     ipc::{CellAttributes, IpcResult},
-    theme::{AnsiColor, ColorPalette, ColorScan, ThemeTestExt},
+    theme::{
+        detect_palette, rgb, rgba, rgba_components, AnsiColor, ColorPalette, ColorScan,
+        PaletteDiff, PaletteSlot, ThemeTestExt,
+    },
 };
 
 /// Mock implementation for testing ThemeTestExt
@@ -303,6 +306,118 @@ fn test_theme_test_ext_scan_region() {
     assert!(scan.foreground_colors.contains(&0x0000FFFF));
 }
 
+#[test]
+fn test_theme_test_ext_scan_region_frequencies() {
+    let palette = ColorPalette::nord();
+    let mut terminal = MockTerminal::new(4, 5);
+
+    // Theme background everywhere except a one-cell accent
+    for row in 0..4 {
+        for col in 0..5 {
+            let bg = if row == 1 && col == 2 {
+                palette.selection
+            } else {
+                palette.background
+            };
+            let attrs = CellAttributes {
+                fg: palette.foreground,
+                bg,
+                flags: 0,
+                reserved: 0,
+            };
+            terminal.set_cell(row, col, attrs);
+        }
+    }
+
+    let scan = terminal.scan_colors_in_region(0, 0, 3, 4).unwrap();
+
+    assert_eq!(scan.cells_scanned, 20);
+    assert_eq!(scan.most_common_background(), Some(palette.background));
+    assert_eq!(scan.color_frequency(palette.background), (0, 19));
+    assert_eq!(scan.color_frequency(palette.selection), (0, 1));
+    assert_eq!(scan.color_frequency(palette.foreground), (20, 0));
+}
+
+#[test]
+fn test_palette_diff_single_color() {
+    let slime = ColorPalette::slime();
+    assert!(slime.diff(&slime.clone()).is_empty());
+    assert!(slime.assert_eq_verbose(&ColorPalette::slime()).is_ok());
+
+    let mut tweaked = slime.clone();
+    tweaked.name = "slime-tweaked".to_string();
+    tweaked.colors[AnsiColor::BrightCyan.as_index() as usize] = 0x00FFFFFF;
+
+    assert_eq!(
+        slime.diff(&tweaked),
+        vec![PaletteDiff {
+            slot: PaletteSlot::Ansi(AnsiColor::BrightCyan),
+            old: 0x76FF03FF,
+            new: 0x00FFFFFF,
+        }]
+    );
+
+    let message = tweaked.assert_eq_verbose(&slime).unwrap_err().to_string();
+    assert!(message.contains("'slime-tweaked' differs from expected 'slime' in 1 slot(s)"));
+    assert!(message.contains("BrightCyan: expected 0x76FF03FF, got 0x00FFFFFF"));
+}
+
+#[test]
+fn test_palette_diff_lists_every_slot() {
+    let diffs = ColorPalette::slime().diff(&ColorPalette::dracula());
+    let slots: Vec<PaletteSlot> = diffs.iter().map(|diff| diff.slot).collect();
+
+    // Both palettes share bright white; everything else differs
+    assert_eq!(slots.len(), 19);
+    assert_eq!(slots[0], PaletteSlot::Ansi(AnsiColor::Black));
+    assert!(!slots.contains(&PaletteSlot::Ansi(AnsiColor::BrightWhite)));
+    assert_eq!(
+        &slots[15..],
+        &[
+            PaletteSlot::Background,
+            PaletteSlot::Foreground,
+            PaletteSlot::Cursor,
+            PaletteSlot::Selection
+        ]
+    );
+}
+
+#[test]
+fn test_detect_palette_from_scanned_grid() {
+    let dracula = ColorPalette::dracula();
+    let mut terminal = MockTerminal::new(4, 16);
+
+    // Every ANSI color as foreground on the theme background
+    for row in 0..4 {
+        for col in 0..16 {
+            let attrs = CellAttributes {
+                fg: dracula.colors[(row + col) % 16],
+                bg: dracula.background,
+                flags: 0,
+                reserved: 0,
+            };
+            terminal.set_cell(row, col, attrs);
+        }
+    }
+
+    let scan = terminal.scan_colors_in_region(0, 0, 3, 15).unwrap();
+    let candidates = [
+        ColorPalette::slime(),
+        ColorPalette::nord(),
+        ColorPalette::monokai(),
+        ColorPalette::solarized_dark(),
+        ColorPalette::solarized_light(),
+        ColorPalette::gruvbox_dark(),
+        ColorPalette::tokyo_night(),
+        ColorPalette::catppuccin_mocha(),
+        dracula,
+    ];
+
+    let (name, confidence) = detect_palette(&scan, &candidates).unwrap();
+    assert_eq!(name, "dracula");
+    assert_eq!(confidence, 1.0);
+}
+
 #[test]
 fn test_theme_test_ext_scan_region_out_of_bounds() {
     let terminal = MockTerminal::new(5, 5);
@@ -357,3 +472,135 @@ fn test_custom_palette() {
     // Test matching
     assert!(palette.matches_ansi(0xFF0000FF, AnsiColor::Red)); // Alpha ignored
 }
+
+#[test]
+fn test_palette_from_itermcolors() {
+    // Dracula exported from iTerm2; float components must round back to the hex values
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/theme/dracula.itermcolors");
+    let palette = ColorPalette::from_itermcolors(path).unwrap();
+
+    assert_eq!(palette.name, "dracula");
+    assert_eq!(palette.ansi_color(AnsiColor::Black), 0x282A36FF);
+    assert_eq!(palette.ansi_color(AnsiColor::Blue), 0xBD93F9FF);
+    assert_eq!(palette.ansi_color(AnsiColor::BrightGreen), 0x69FF94FF);
+    assert_eq!(palette.background, 0x282A36FF);
+    assert_eq!(palette.foreground, 0xF8F8F2FF);
+    assert_eq!(palette.selection, 0x44475AFF);
+
+    // Same colors as the built-in Dracula palette (which carries a non-opaque bright cyan)
+    let builtin = ColorPalette::dracula();
+    for i in 0..16 {
+        let ansi = AnsiColor::from_index(i).unwrap();
+        assert!(builtin.matches_ansi(palette.ansi_color(ansi), ansi));
+    }
+}
+
+#[test]
+fn test_palette_from_missing_itermcolors() {
+    assert!(ColorPalette::from_itermcolors("/nonexistent/theme.itermcolors").is_err());
+}
+
+#[test]
+fn test_assert_nearest_ansi() {
+    let palette = ColorPalette::dracula();
+    let mut terminal = MockTerminal::new(1, 3);
+
+    // Exact, near-miss (blended), and clearly wrong colors
+    terminal.set_cell(0, 0, CellAttributes { fg: 0xFF5555FF, ..Default::default() });
+    terminal.set_cell(0, 1, CellAttributes { fg: 0xF85A52FF, ..Default::default() });
+    terminal.set_cell(0, 2, CellAttributes { fg: 0x50FA7BFF, ..Default::default() });
+
+    assert!(terminal
+        .assert_nearest_ansi(0, 0, &palette, AnsiColor::Red, 0)
+        .is_ok());
+    assert!(terminal
+        .assert_nearest_ansi(0, 1, &palette, AnsiColor::Red, 100)
+        .is_ok());
+    assert!(terminal
+        .assert_nearest_ansi(0, 1, &palette, AnsiColor::Red, 10)
+        .is_err());
+
+    let err = terminal
+        .assert_nearest_ansi(0, 2, &palette, AnsiColor::Red, 1000)
+        .unwrap_err();
+    assert!(err.to_string().contains("got Green"));
+}
+
+#[test]
+fn test_assert_contrast_at_least() {
+    let mut terminal = MockTerminal::new(1, 2);
+    let black_on_white = CellAttributes {
+        fg: 0x000000FF,
+        bg: 0xFFFFFFFF,
+        ..Default::default()
+    };
+    let gray_on_gray = CellAttributes {
+        fg: 0x767676FF,
+        bg: 0x808080FF,
+        ..Default::default()
+    };
+    terminal.set_cell(0, 0, black_on_white);
+    terminal.set_cell(0, 1, gray_on_gray);
+
+    assert!(terminal.assert_contrast_at_least(0, 0, 20.9).is_ok());
+    assert!(terminal.assert_contrast_at_least(0, 0, 4.5).is_ok());
+
+    let err = terminal.assert_contrast_at_least(0, 1, 4.5).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Insufficient contrast at (0, 1)"));
+    assert!(message.contains(":1"));
+}
+
+#[test]
+fn test_rgb_constructors_round_trip() {
+    assert_eq!(rgb(0xBD, 0x93, 0xF9), 0xBD93F9FF);
+    assert_eq!(rgba(0xBD, 0x93, 0xF9, 0x80), 0xBD93F980);
+    assert_eq!(rgb(0, 0, 0), 0x000000FF);
+
+    for &(r, g, b, a) in &[(0, 0, 0, 0), (1, 2, 3, 4), (0xFF, 0x80, 0x01, 0xFE)] {
+        assert_eq!(rgba_components(rgba(r, g, b, a)), (r, g, b, a));
+        assert_eq!(rgba_components(rgb(r, g, b)), (r, g, b, 0xFF));
+    }
+
+    // Constructors agree with the palette's RGBA layout
+    let palette = ColorPalette::dracula();
+    assert_eq!(palette.ansi_color(AnsiColor::Blue), rgb(0xBD, 0x93, 0xF9));
+}
+
+#[test]
+fn test_assert_foreground_rgb() {
+    let mut terminal = MockTerminal::new(1, 2);
+    terminal.set_cell(
+        0,
+        0,
+        CellAttributes {
+            fg: rgb(0xFF, 0x55, 0x55),
+            ..Default::default()
+        },
+    );
+    terminal.set_cell(
+        0,
+        1,
+        CellAttributes {
+            fg: rgba(0xFF, 0x55, 0x55, 0x40),
+            ..Default::default()
+        },
+    );
+
+    assert!(terminal
+        .assert_foreground_rgb(0, 0, 0xFF, 0x55, 0x55)
+        .is_ok());
+    // Alpha is ignored
+    assert!(terminal
+        .assert_foreground_rgb(0, 1, 0xFF, 0x55, 0x55)
+        .is_ok());
+
+    let err = terminal
+        .assert_foreground_rgb(0, 0, 0xFF, 0x55, 0x56)
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("at (0, 0)"));
+    assert!(message.contains("expected rgb(255, 85, 86), got rgb(255, 85, 85)"));
+
+    assert!(terminal.assert_foreground_rgb(5, 5, 0, 0, 0).is_err());
+}